@@ -4,12 +4,14 @@
 
 use std::sync::Arc;
 
-use clawchain_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use clawchain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce};
 use jsonrpsee::RpcModule;
+use sc_client_api::BlockchainEvents;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::traits::SpawnNamed;
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -17,6 +19,8 @@ pub struct FullDeps<C, P> {
     pub client: Arc<C>,
     /// Transaction pool instance.
     pub pool: Arc<P>,
+    /// Executor for subscription tasks.
+    pub subscription_executor: Arc<dyn SpawnNamed>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -27,22 +31,43 @@ where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
+        + BlockchainEvents<Block>
         + Send
         + Sync
         + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api:
+        pallet_agent_registry_rpc::AgentRegistryRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: pallet_reputation_rpc::ReputationRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: pallet_task_market_rpc::TaskMarketRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: pallet_validation_rpc::ValidationRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: pallet_messaging_rpc::MessagingRuntimeApi<Block, AccountId, Balance, BlockNumber>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use pallet_agent_registry_rpc::{AgentRegistry, AgentRegistryApiServer};
+    use pallet_messaging_rpc::{Messaging, MessagingApiServer};
+    use pallet_reputation_rpc::{Reputation, ReputationApiServer};
+    use pallet_task_market_rpc::{TaskMarket, TaskMarketApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+    use pallet_validation_rpc::{Validation, ValidationApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
     let mut module = RpcModule::new(());
-    let FullDeps { client, pool } = deps;
+    let FullDeps {
+        client,
+        pool,
+        subscription_executor,
+    } = deps;
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(AgentRegistry::new(client.clone()).into_rpc())?;
+    module.merge(Reputation::new(client.clone()).into_rpc())?;
+    module.merge(TaskMarket::new(client.clone()).into_rpc())?;
+    module.merge(Validation::new(client.clone()).into_rpc())?;
+    module.merge(Messaging::new(client, subscription_executor).into_rpc())?;
 
     Ok(module)
 }
@@ -0,0 +1,345 @@
+//! RPC interface for the Agent Registry pallet.
+//!
+//! Exposes the `AgentRegistryApi` runtime API as `agentRegistry_*` JSON-RPC methods.
+//! `agentRegistry_didDocument` returns the DID document as a JSON object, which a
+//! `did:claw` resolver driver can serve unchanged.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_agent_registry_runtime_api::{
+    AgentId, AgentPage, AgentStatus, AgentSummary, AttestationSummary, CapabilitySummary,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_agent_registry_runtime_api::AgentRegistryApi as AgentRegistryRuntimeApi;
+
+/// Maximum (and default) number of entries returned by a paginated call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait AgentRegistryApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// Look up an agent by ID.
+    #[method(name = "agentRegistry_agent")]
+    fn agent(
+        &self,
+        agent_id: AgentId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>>;
+
+    /// Resolve an agent from its DID string, e.g. `did:claw:agent001`.
+    #[method(name = "agentRegistry_agentByDid")]
+    fn agent_by_did(
+        &self,
+        did: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>>;
+
+    /// All agents owned by `owner`.
+    #[method(name = "agentRegistry_agentsByOwner")]
+    fn agents_by_owner(
+        &self,
+        owner: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AgentSummary<AccountId, BlockNumber>>>;
+
+    /// Agents with the given status (or any status), scanning agent IDs from `start`;
+    /// continue from `next` until it is `null`.
+    #[method(name = "agentRegistry_listAgents")]
+    fn list_agents(
+        &self,
+        status: Option<AgentStatus>,
+        start: Option<AgentId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AgentPage<AccountId, BlockNumber>>;
+
+    /// W3C DID document for a DID string, or `null` if it does not resolve.
+    #[method(name = "agentRegistry_didDocument")]
+    fn did_document(
+        &self,
+        did: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<serde_json::Value>>;
+
+    /// Whether `public_key` was a valid verification key of the agent at block
+    /// `block_number`, e.g. to check a signature made at that block.
+    #[method(name = "agentRegistry_keyValidAt")]
+    fn key_valid_at(
+        &self,
+        agent_id: AgentId,
+        public_key: Bytes,
+        block_number: BlockNumber,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    /// Capabilities declared by an agent.
+    #[method(name = "agentRegistry_agentCapabilities")]
+    fn agent_capabilities(
+        &self,
+        agent_id: AgentId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<CapabilitySummary<Balance>>>;
+
+    /// Active agents declaring a capability ID (e.g. `ocr.rust`) at `minVersion`
+    /// or above, scanning agent IDs from `start`; continue from `next` until it is
+    /// `null`.
+    #[method(name = "agentRegistry_agentsWithCapability")]
+    fn agents_with_capability(
+        &self,
+        capability: String,
+        min_version: Option<u32>,
+        start: Option<AgentId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AgentPage<AccountId, BlockNumber>>;
+
+    /// Resolve an agent from its ERC-8004 domain, e.g. `agent.example.com`.
+    #[method(name = "agentRegistry_agentByDomain")]
+    fn agent_by_domain(
+        &self,
+        domain: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>>;
+
+    /// Resolve an agent from its agent account (the ERC-8004 address).
+    #[method(name = "agentRegistry_agentByAddress")]
+    fn agent_by_address(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>>;
+
+    /// Whether an agent holds a valid claim of a `0x`-prefixed schema from `issuer`.
+    #[method(name = "agentRegistry_hasValidAttestation")]
+    fn has_valid_attestation(
+        &self,
+        agent_id: AgentId,
+        schema: H256,
+        issuer: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    /// Claims attested about an agent, valid or not.
+    #[method(name = "agentRegistry_attestations")]
+    fn attestations(
+        &self,
+        agent_id: AgentId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AttestationSummary<AccountId, BlockNumber>>>;
+}
+
+/// Provides RPC methods to query the agent registry.
+pub struct AgentRegistry<C, Block> {
+    /// Shared reference to the client.
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> AgentRegistry<C, Block> {
+    /// Creates a new instance of the AgentRegistry RPC helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query agent registry.",
+        Some(error.to_string()),
+    )
+}
+
+fn page_size(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    AgentRegistryApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for AgentRegistry<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AgentRegistryRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + Send + Sync + 'static,
+    Balance: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Send + Sync + 'static,
+{
+    fn agent(
+        &self,
+        agent_id: AgentId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agent(at_hash, agent_id)
+            .map_err(runtime_error)
+    }
+
+    fn agent_by_did(
+        &self,
+        did: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agent_by_did(at_hash, did.into_bytes())
+            .map_err(runtime_error)
+    }
+
+    fn agents_by_owner(
+        &self,
+        owner: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<AgentSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agents_by_owner(at_hash, owner)
+            .map_err(runtime_error)
+    }
+
+    fn list_agents(
+        &self,
+        status: Option<AgentStatus>,
+        start: Option<AgentId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<AgentPage<AccountId, BlockNumber>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .list_agents(at_hash, status, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn did_document(
+        &self,
+        did: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<serde_json::Value>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let document = self
+            .client
+            .runtime_api()
+            .did_document(at_hash, did.into_bytes())
+            .map_err(runtime_error)?;
+        document
+            .map(|json| serde_json::from_slice(&json).map_err(runtime_error))
+            .transpose()
+    }
+
+    fn key_valid_at(
+        &self,
+        agent_id: AgentId,
+        public_key: Bytes,
+        block_number: BlockNumber,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<bool> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .key_valid_at(at_hash, agent_id, public_key.to_vec(), block_number)
+            .map_err(runtime_error)
+    }
+
+    fn agent_capabilities(
+        &self,
+        agent_id: AgentId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<CapabilitySummary<Balance>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agent_capabilities(at_hash, agent_id)
+            .map_err(runtime_error)
+    }
+
+    fn agents_with_capability(
+        &self,
+        capability: String,
+        min_version: Option<u32>,
+        start: Option<AgentId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<AgentPage<AccountId, BlockNumber>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agents_with_capability(
+                at_hash,
+                capability.into_bytes(),
+                min_version.unwrap_or_default(),
+                start.unwrap_or_default(),
+                page_size(limit),
+            )
+            .map_err(runtime_error)
+    }
+
+    fn agent_by_domain(
+        &self,
+        domain: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agent_by_domain(at_hash, domain.into_bytes())
+            .map_err(runtime_error)
+    }
+
+    fn agent_by_address(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<AgentSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .agent_by_address(at_hash, account)
+            .map_err(runtime_error)
+    }
+
+    fn has_valid_attestation(
+        &self,
+        agent_id: AgentId,
+        schema: H256,
+        issuer: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<bool> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .has_valid_attestation(at_hash, agent_id, schema.0, issuer)
+            .map_err(runtime_error)
+    }
+
+    fn attestations(
+        &self,
+        agent_id: AgentId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<AttestationSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .attestations(at_hash, agent_id)
+            .map_err(runtime_error)
+    }
+}
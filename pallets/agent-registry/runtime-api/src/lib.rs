@@ -0,0 +1,74 @@
+//! Runtime API definition for the Agent Registry pallet.
+//!
+//! Lets clients resolve agents by ID, DID, owner, capability or ERC-8004 domain
+//! and address without iterating storage, and check the claims issuers attested
+//! about them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_agent_registry::{
+    AgentId, AgentPage, AgentStatus, AgentSummary, AttestationSummary, CapabilitySummary,
+    SchemaId, MAX_SCANNED_AGENTS,
+};
+
+sp_api::decl_runtime_apis! {
+    /// Queries over the agent registry.
+    pub trait AgentRegistryApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Look up an agent by ID.
+        fn agent(agent_id: AgentId) -> Option<AgentSummary<AccountId, BlockNumber>>;
+
+        /// Resolve an agent from its DID.
+        fn agent_by_did(did: Vec<u8>) -> Option<AgentSummary<AccountId, BlockNumber>>;
+
+        /// All agents owned by `owner`.
+        fn agents_by_owner(owner: AccountId) -> Vec<AgentSummary<AccountId, BlockNumber>>;
+
+        /// Agents with the given status (or any status), scanning at most
+        /// `MAX_SCANNED_AGENTS` agent IDs from `start` and returning at most `limit`.
+        fn list_agents(
+            status: Option<AgentStatus>,
+            start: AgentId,
+            limit: u32,
+        ) -> AgentPage<AccountId, BlockNumber>;
+
+        /// The W3C DID document for `did`, rendered as DID Core JSON.
+        fn did_document(did: Vec<u8>) -> Option<Vec<u8>>;
+
+        /// Whether `public_key` was a valid verification key of the agent at block `at`.
+        fn key_valid_at(agent_id: AgentId, public_key: Vec<u8>, at: BlockNumber) -> bool;
+
+        /// Capabilities declared by an agent.
+        fn agent_capabilities(agent_id: AgentId) -> Vec<CapabilitySummary<Balance>>;
+
+        /// Active agents declaring `capability` at `min_version` or above, scanning at
+        /// most `MAX_SCANNED_AGENTS` agent IDs from `start` and returning at most `limit`.
+        fn agents_with_capability(
+            capability: Vec<u8>,
+            min_version: u32,
+            start: AgentId,
+            limit: u32,
+        ) -> AgentPage<AccountId, BlockNumber>;
+
+        /// Resolve an agent from its ERC-8004 domain.
+        fn agent_by_domain(domain: Vec<u8>) -> Option<AgentSummary<AccountId, BlockNumber>>;
+
+        /// Resolve an agent from its agent account (the ERC-8004 address).
+        fn agent_by_address(account: AccountId) -> Option<AgentSummary<AccountId, BlockNumber>>;
+
+        /// Whether the agent holds a valid claim of `schema` from `issuer`.
+        fn has_valid_attestation(agent_id: AgentId, schema: SchemaId, issuer: AccountId) -> bool;
+
+        /// Claims attested about an agent, valid or not.
+        fn attestations(agent_id: AgentId) -> Vec<AttestationSummary<AccountId, BlockNumber>>;
+    }
+}
@@ -0,0 +1,142 @@
+//! DID Core JSON rendering of agent DID documents.
+//!
+//! Keys are rendered as `Multikey` verification methods whose
+//! `publicKeyMultibase` is the base58btc encoding of the multicodec-prefixed
+//! public key, so the key type travels with the key itself.
+
+use crate::pallet::{Config, DidDocument, DidKeyType};
+use alloc::vec::Vec;
+
+const CONTEXTS: [&str; 2] = [
+    "https://www.w3.org/ns/did/v1",
+    "https://w3id.org/security/multikey/v1",
+];
+
+/// Multicodec prefix (unsigned varint) of a public key type.
+fn multicodec_prefix(key_type: DidKeyType) -> [u8; 2] {
+    match key_type {
+        DidKeyType::Ed25519 => [0xed, 0x01],
+        DidKeyType::Sr25519 => [0xef, 0x01],
+        DidKeyType::EcdsaSecp256k1 => [0xe7, 0x01],
+    }
+}
+
+/// Whether `uri` is an absolute URI: a scheme followed by `:` and printable,
+/// JSON-safe characters.
+pub(crate) fn is_valid_uri(uri: &[u8]) -> bool {
+    let Some(colon) = uri.iter().position(|b| *b == b':') else {
+        return false;
+    };
+    let (scheme, rest) = (&uri[..colon], &uri[colon + 1..]);
+    scheme.first().is_some_and(u8::is_ascii_alphabetic)
+        && scheme
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+        && !rest.is_empty()
+        && rest
+            .iter()
+            .all(|b| b.is_ascii_graphic() && !matches!(b, b'"' | b'\\' | b'<' | b'>'))
+}
+
+/// Render `doc` for `did` as a DID Core JSON document.
+///
+/// Every rendered string was validated on insertion to contain no characters
+/// that need JSON escaping.
+pub(crate) fn render_document<T: Config>(did: &[u8], doc: &DidDocument<T>) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    out.extend_from_slice(b"{\"@context\":[");
+    for (i, context) in CONTEXTS.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        out.push(b'"');
+        out.extend_from_slice(context.as_bytes());
+        out.push(b'"');
+    }
+    out.extend_from_slice(b"],\"id\":\"");
+    out.extend_from_slice(did);
+    out.push(b'"');
+
+    out.extend_from_slice(b",\"verificationMethod\":[");
+    for (i, method) in doc.verification_methods.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        let mut key = multicodec_prefix(method.key_type).to_vec();
+        key.extend_from_slice(&method.public_key);
+
+        out.extend_from_slice(b"{\"id\":");
+        push_reference(&mut out, did, &method.fragment);
+        out.extend_from_slice(b",\"type\":\"Multikey\",\"controller\":\"");
+        out.extend_from_slice(did);
+        out.extend_from_slice(b"\",\"publicKeyMultibase\":\"z");
+        out.extend_from_slice(bs58::encode(key).into_string().as_bytes());
+        out.extend_from_slice(b"\"}");
+    }
+    out.push(b']');
+
+    push_relationship(
+        &mut out,
+        did,
+        b"authentication",
+        doc.verification_methods
+            .iter()
+            .filter(|m| m.authentication)
+            .map(|m| &m.fragment[..]),
+    );
+    push_relationship(
+        &mut out,
+        did,
+        b"assertionMethod",
+        doc.verification_methods
+            .iter()
+            .filter(|m| m.assertion_method)
+            .map(|m| &m.fragment[..]),
+    );
+
+    out.extend_from_slice(b",\"service\":[");
+    for (i, service) in doc.services.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        out.extend_from_slice(b"{\"id\":");
+        push_reference(&mut out, did, &service.fragment);
+        out.extend_from_slice(b",\"type\":\"");
+        out.extend_from_slice(&service.service_type);
+        out.extend_from_slice(b"\",\"serviceEndpoint\":\"");
+        out.extend_from_slice(&service.endpoint);
+        out.extend_from_slice(b"\"}");
+    }
+    out.extend_from_slice(b"]}");
+
+    out
+}
+
+/// Append `"<did>#<fragment>"`.
+fn push_reference(out: &mut Vec<u8>, did: &[u8], fragment: &[u8]) {
+    out.push(b'"');
+    out.extend_from_slice(did);
+    out.push(b'#');
+    out.extend_from_slice(fragment);
+    out.push(b'"');
+}
+
+/// Append `,"<name>":[...]` listing references to `fragments`.
+fn push_relationship<'a>(
+    out: &mut Vec<u8>,
+    did: &[u8],
+    name: &[u8],
+    fragments: impl Iterator<Item = &'a [u8]>,
+) {
+    out.extend_from_slice(b",\"");
+    out.extend_from_slice(name);
+    out.extend_from_slice(b"\":[");
+    for (i, fragment) in fragments.enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        push_reference(out, did, fragment);
+    }
+    out.push(b']');
+}
@@ -6,9 +6,170 @@
 //!
 //! This pallet provides functionality for:
 //! - Registering autonomous agents with decentralized identifiers (DIDs)
-//! - Storing agent metadata (name, type, capabilities)
-//! - Tracking agent reputation scores (0-10000 basis points)
-//! - Managing agent lifecycle (Active, Suspended, Deregistered)
+//! - Storing agent metadata (name, type) and structured capability declarations
+//! - Deriving each agent's reputation from pallet-reputation (0-10000 basis points)
+//! - Managing agent lifecycle (Active, Inactive, Suspended, Deregistered)
+//!
+//! ## Registration Deposits
+//!
+//! Registering an agent reserves `AgentDepositBase` plus `AgentDepositPerByte` for
+//! each byte of DID and metadata from the owner. `update_metadata` reserves or
+//! releases the difference when the metadata size changes, and `deregister_agent`
+//! refunds the whole deposit.
+//!
+//! ## Purging
+//!
+//! Deregistering keeps the agent's entry so its history stays queryable. Once
+//! deregistered, the owner (at any time) or anyone (after `PurgeGracePeriod`
+//! blocks) can `purge_agent`: this removes the entry, its DID document, its
+//! attestations and its place in `OwnerAgents`, freeing a slot under
+//! `MaxAgentsPerOwner`, and leaves an `AgentTombstones` record. Agent IDs are
+//! never reused, the DID stays bound to the purged ID for good, and `KeyHistory`
+//! is kept so old signatures remain verifiable.
+//!
+//! ## Ownership Transfer
+//!
+//! The owner proposes a new owner with `propose_transfer`; the transfer completes
+//! when that account calls `accept_transfer`. Accepting moves the agent between the
+//! two `OwnerAgents` lists, refunds the registration deposit to the old owner and
+//! reserves it from the new one, and the agent's reputation follows the new owner's
+//! score from then on. The old owner's keys go with them: every verification method is revoked
+//! in `KeyHistory` and the DID document is emptied of keys and services, so the new
+//! owner starts from a clean document. A pending proposal can be withdrawn with
+//! `cancel_transfer` or replaced by proposing again.
+//!
+//! ## Operator Keys
+//!
+//! Every agent has a keyless agent account derived from `PalletId` and its ID
+//! (see `agent_account`). The owner binds operator keys to the agent with
+//! `add_operator`; an operator then calls `operate` to dispatch any call within
+//! its scopes (whole pallets or single calls, by index) with the agent account as
+//! the signed origin. This is how an autonomous agent posts and bids on tasks,
+//! leaves reviews or moves funds without holding the owner's key.
+//!
+//! An operator may also carry a spending cap: the agent account's free balance
+//! may drop by at most that much per `OperatorSpendingPeriod` through that
+//! operator, and a call that would exceed it is reverted. Reserving funds counts
+//! as spending, since reserved funds (such as a task's escrow) can later be paid
+//! out without another `operate`. A call that fails fails the whole `operate`. Operators are cleared when the agent is deregistered
+//! or transferred.
+//!
+//! ## Capabilities
+//!
+//! Besides free-form metadata, each agent declares a bounded list of typed
+//! capabilities: an ID such as `ocr.rust`, a version, a price hint and an
+//! optional rate limit. `CapabilityIndex` maps every capability ID to the agents
+//! declaring it, so `agents_with_capability` can match a task to capable agents
+//! with one lookup per agent. Capabilities are dropped on deregistration.
+//!
+//! ## Liveness
+//!
+//! A running agent proves it is alive by having its owner or one of its operator
+//! keys call `heartbeat`, which refreshes `last_heartbeat`; calls made through
+//! `operate` count too. An operator key with no scopes is a heartbeat-only key.
+//! To spare the agent the fee, the same key can instead sign a `HeartbeatPayload`
+//! and submit it unsigned through `heartbeat_unsigned`, at most once every
+//! `MinHeartbeatInterval` blocks so free heartbeats cannot flood the transaction
+//! pool. Other owner extrinsics
+//! refresh `last_active` but not `last_heartbeat`, so they say nothing about
+//! whether the agent itself is running. The `on_idle` hook sweeps agents
+//! round-robin and marks Active agents that have not sent a heartbeat for
+//! `InactivityPeriod` blocks as Inactive. The next heartbeat makes them Active
+//! again.
+//!
+//! ## Suspension
+//!
+//! Owners may pause their own agent by setting it Suspended and resume it by
+//! setting it Active. `AdminOrigin` can instead `suspend_agent` with a reason and
+//! an optional expiry block; owners cannot clear an admin suspension, which ends
+//! when `AdminOrigin` calls `lift_suspension` or, once expired, when the idle
+//! sweep reaches the agent.
+//!
+//! Other pallets check whether an agent account is inactive or suspended through
+//! `AgentStanding`; pallet-task-market uses it to refuse bids from suspended
+//! agents and assignments to suspended or inactive ones. An admin suspension also
+//! counts against the agent's owner, so the owner cannot sidestep it by bidding
+//! or validating from its own account; a voluntary pause does not. Pallets that
+//! address agents by ID check them through `AgentDirectory`.
+//!
+//! ## DID Uniqueness
+//!
+//! Every DID resolves to at most one agent through the `DidToAgent` index.
+//! DIDs must follow the W3C syntax `did:<method>:<method-specific-id>`, and
+//! `<method>` must appear in `AllowedDidMethods` (an empty list accepts any
+//! method). When an agent is deregistered its DID stays bound to it for
+//! `DidReleaseCooldown` blocks, after which a new registration may claim it. Once
+//! the agent is purged its DID is retired: a tombstone cannot be revived, so no
+//! other agent can ever present itself under that DID.
+//!
+//! ## Reputation
+//!
+//! An agent's reputation is not stored; `agent_reputation` derives it from
+//! pallet-reputation through `ReputationScore` on every read, so it cannot drift
+//! from the underlying scores (including decay). An agent earns reputation on two
+//! accounts: its owner's, when the owner acts directly, and its agent account's,
+//! when it acts through `operate`. It is credited with the lower of the two, so an
+//! agent can neither borrow a fresh account's clean slate nor escape a poor record
+//! by switching between them. `update_reputation` lets `ReputationOrigin` adjust a
+//! single agent: the adjustment is kept in `ReputationAdjustments` and applied on
+//! top of the derived score.
+//!
+//! ## DID Documents
+//!
+//! Each agent has a W3C DID document holding its verification methods (public
+//! keys with a key type, usable for authentication and/or assertions) and
+//! service endpoints. The owner manages entries by fragment (`#key-1`,
+//! `#messaging`). `did_document` renders the document as DID Core JSON so a
+//! `did:claw` resolver can serve it as-is.
+//!
+//! ## Key Rotation and Revocation
+//!
+//! Every verification key ever added to an agent is kept in `KeyHistory` with the
+//! block it was added at and, once rotated out or revoked, the block it stopped
+//! being valid at. `key_valid_at` answers whether a key belonged to an agent at a
+//! given block, so signatures made before a revocation can still be checked. A
+//! key can be bound to an agent only once: a revoked key never comes back.
+//!
+//! `rotate_verification_method` swaps the key behind a fragment, keeping its
+//! verification relationships; `revoke_verification_method` drops it from the
+//! document.
+//!
+//! If a key is compromised the owner can `freeze_agent`: while frozen, operator
+//! keys cannot act for the agent, the agent cannot be transferred and no key is
+//! reported valid from the freeze block on. The owner revokes or rotates the
+//! affected keys and then calls `unfreeze_agent`; keys that survive the freeze
+//! are valid again.
+//!
+//! ## ERC-8004 Compatibility
+//!
+//! The registry mirrors the identity registry of ERC-8004 (Trustless Agents) so
+//! Ethereum agent tooling can map onto it. The ERC's validation registry is
+//! pallet-validation (`ValidationSubject::Output`), and the reputation registry's
+//! feedback authorisation lives in pallet-reputation.
+//!
+//! The ERC `agentId` is the agent ID, `agentAddress` is the agent account and
+//! `agentDomain` is set with `set_agent_domain`. Domains are unique;
+//! `agent_by_domain` and `agent_by_address` mirror `resolveByDomain` and
+//! `resolveByAddress`.
+//!
+//! Calls made "as an agent" accept either the owner or the agent account, so an
+//! operator key can make them through `operate`.
+//!
+//! ## Attestations
+//!
+//! Third parties such as auditors or KYC providers attest facts about an agent
+//! that the agent does not control. `IssuerOrigin` registers issuer accounts with
+//! `register_issuer`. An issuer `attest`s a claim about an agent under a schema
+//! (the hash of the schema definition, e.g. "passed security review"), with the
+//! hash of the off-chain claim document and an optional expiry block; the signed
+//! extrinsic is the issuer's signature over the claim. Attesting again under the
+//! same schema replaces the claim. Issuers cannot attest agents they control.
+//!
+//! The issuer can `revoke_attestation` at any time. A claim is valid while it is
+//! neither revoked nor expired, its issuer is still registered and the agent is
+//! not deregistered; `has_valid_attestation` answers this for an agent, schema
+//! and issuer. An agent holds at most `MaxAttestationsPerAgent` claims, and they
+//! are removed when the agent is purged.
 //!
 //! ## Interface
 //!
@@ -16,9 +177,38 @@
 //!
 //! - `register_agent` - Register a new agent with a DID and metadata
 //! - `update_metadata` - Update an agent's metadata
-//! - `update_reputation` - Adjust an agent's reputation score
+//! - `update_reputation` - Adjust an agent's reputation score (`ReputationOrigin` only)
 //! - `deregister_agent` - Remove an agent from the registry
-//! - `set_agent_status` - Change an agent's status
+//! - `purge_agent` - Reclaim storage of a deregistered agent, leaving a tombstone
+//! - `set_agent_status` - Pause (Suspended) or resume (Active) an agent
+//! - `add_verification_method` / `revoke_verification_method` - Manage DID document keys
+//! - `rotate_verification_method` - Replace the key behind a verification method
+//! - `freeze_agent` / `unfreeze_agent` - Emergency freeze of an agent's keys
+//! - `heartbeat` - Report an agent as alive
+//! - `heartbeat_unsigned` - Report an agent as alive with a signed payload, fee-free
+//! - `set_capabilities` - Replace an agent's capability declarations
+//! - `suspend_agent` / `lift_suspension` - Admin suspension (`AdminOrigin` only)
+//! - `add_service` / `remove_service` - Manage DID document service endpoints
+//! - `propose_transfer` / `cancel_transfer` / `accept_transfer` - Two-step ownership transfer
+//! - `add_operator` / `remove_operator` - Manage an agent's operator keys
+//! - `operate` - Dispatch a call as the agent account with an operator key
+//! - `set_agent_domain` - Set or clear an agent's ERC-8004 domain
+//! - `register_issuer` / `remove_issuer` - Manage attestation issuers (`IssuerOrigin` only)
+//! - `attest` / `revoke_attestation` - Issue or revoke a claim about an agent
+//!
+//! ### Runtime API Helpers
+//!
+//! - `agent` - Look up an agent by ID, including its agent account
+//! - `agent_by_did` - Resolve an agent from its DID
+//! - `agents_by_owner` - All agents owned by an account
+//! - `list_agents` - Paginated listing, optionally filtered by status
+//! - `did_document` - DID Core JSON document for a DID
+//! - `key_valid_at` - Whether a key belonged to an agent at a given block
+//! - `agent_capabilities` - Capabilities declared by an agent
+//! - `agents_with_capability` - Active agents declaring a capability at a minimum version
+//! - `agent_by_domain` / `agent_by_address` - Resolve an agent from its domain or agent account
+//! - `has_valid_attestation` - Whether an agent holds a valid claim of a schema from an issuer
+//! - `attestations` - Claims issued about an agent
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -26,25 +216,124 @@ extern crate alloc;
 
 pub use pallet::*;
 
+mod did;
+pub mod migrations;
+
 #[cfg(test)]
 mod tests;
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
+use pallet_reputation::ReputationScore;
+
+/// Highest agent reputation score, in basis points.
+pub const MAX_REPUTATION: u32 = 10_000;
+
+/// Standing of agents, for pallets that hand work to agent accounts.
+///
+/// Accounts that are not agent accounts are neither inactive nor suspended. The
+/// `()` implementation treats every account as an agent in good standing.
+pub trait AgentStanding<AccountId> {
+    /// Whether `account` is the agent account of a registered, not deregistered agent.
+    fn is_agent(account: &AccountId) -> bool;
+
+    /// Whether `account` is the agent account of an agent that has gone Inactive
+    /// or been deregistered.
+    fn is_inactive_agent(account: &AccountId) -> bool;
+
+    /// Whether `account` is the agent account of a Suspended agent, or the owner of
+    /// an agent suspended by `AdminOrigin`.
+    fn is_suspended_agent(account: &AccountId) -> bool;
+
+    /// Whether `account` is the agent account or owner of an agent declaring
+    /// `capability` at `min_version` or above.
+    fn has_capability(account: &AccountId, capability: &[u8], min_version: u32) -> bool;
+}
+
+impl<AccountId> AgentStanding<AccountId> for () {
+    fn is_agent(_account: &AccountId) -> bool {
+        true
+    }
+
+    fn is_inactive_agent(_account: &AccountId) -> bool {
+        false
+    }
+
+    fn is_suspended_agent(_account: &AccountId) -> bool {
+        false
+    }
+
+    fn has_capability(_account: &AccountId, _capability: &[u8], _min_version: u32) -> bool {
+        true
+    }
+}
+
+/// Agents by ID, for pallets that address agents rather than accounts.
+///
+/// The `()` implementation knows no agents.
+pub trait AgentDirectory<AccountId> {
+    /// Whether `agent_id` is registered and not deregistered.
+    fn is_registered(agent_id: AgentId) -> bool;
+
+    /// Whether `account` may act as `agent_id`: it is the owner or agent account
+    /// of an agent that is not deregistered.
+    fn is_controller(account: &AccountId, agent_id: AgentId) -> bool;
+
+    /// Accounts that may act as `agent_id`: its owner and agent account, or none if
+    /// it is not registered or deregistered.
+    fn controllers(agent_id: AgentId) -> Vec<AccountId>;
+}
+
+impl<AccountId> AgentDirectory<AccountId> for () {
+    fn is_registered(_agent_id: AgentId) -> bool {
+        false
+    }
+
+    fn is_controller(_account: &AccountId, _agent_id: AgentId) -> bool {
+        false
+    }
+
+    fn controllers(_agent_id: AgentId) -> Vec<AccountId> {
+        Vec::new()
+    }
+}
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        dispatch::{GetDispatchInfo, PostDispatchInfo},
+        pallet_prelude::*,
+        traits::{Currency, ReservableCurrency},
+        PalletId,
+    };
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{
+        AccountIdConversion, Dispatchable, IdentifyAccount, Saturating, UniqueSaturatedInto,
+        Verify,
+    };
 
     /// Type alias for agent IDs (sequential u64).
     pub type AgentId = u64;
 
+    /// Maximum number of agent IDs a runtime API listing scans per call.
+    pub const MAX_SCANNED_AGENTS: u64 = 1_000;
+
+    /// A 32-byte hash, such as the hash of an attested claim.
+    pub type DataHash = [u8; 32];
+
+    /// Hash of the definition of an attestation schema.
+    pub type SchemaId = [u8; 32];
+
+    /// Type alias for balance (compatible with pallet-balances).
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
     /// Agent status enum.
     #[derive(
         Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
         codec::DecodeWithMemTracking,
     )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
     pub enum AgentStatus {
         /// Agent is active and operational.
         Active,
@@ -52,6 +341,8 @@ pub mod pallet {
         Suspended,
         /// Agent has been deregistered by the owner.
         Deregistered,
+        /// Agent has not sent a heartbeat for `InactivityPeriod` blocks.
+        Inactive,
     }
 
     impl Default for AgentStatus {
@@ -70,16 +361,346 @@ pub mod pallet {
         pub did: BoundedVec<u8, T::MaxDidLength>,
         /// JSON metadata (name, type, capabilities, etc.).
         pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
-        /// Reputation score in basis points (0-10000).
-        pub reputation: u32,
         /// Block number when the agent was registered.
         pub registered_at: BlockNumberFor<T>,
         /// Block number of the agent's last activity.
         pub last_active: BlockNumberFor<T>,
+        /// Block number of the agent's last heartbeat, or its registration.
+        pub last_heartbeat: BlockNumberFor<T>,
+        /// Current status of the agent.
+        pub status: AgentStatus,
+    }
+
+    /// Public key type of a DID verification method.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        codec::DecodeWithMemTracking,
+    )]
+    pub enum DidKeyType {
+        /// 32-byte Ed25519 public key.
+        Ed25519,
+        /// 32-byte Schnorrkel/Ristretto sr25519 public key.
+        Sr25519,
+        /// 33-byte compressed secp256k1 public key.
+        EcdsaSecp256k1,
+    }
+
+    impl DidKeyType {
+        /// Length of a public key of this type in bytes.
+        pub fn key_length(&self) -> usize {
+            match self {
+                DidKeyType::Ed25519 | DidKeyType::Sr25519 => 32,
+                DidKeyType::EcdsaSecp256k1 => 33,
+            }
+        }
+    }
+
+    /// Largest public key accepted by any [`DidKeyType`].
+    pub type MaxPublicKeyLength = ConstU32<33>;
+
+    /// A public key in an agent's DID document.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct VerificationMethod<T: Config> {
+        /// Fragment identifying the method within the document (`key-1` in `did:claw:x#key-1`).
+        pub fragment: BoundedVec<u8, T::MaxFragmentLength>,
+        /// Type of the public key.
+        pub key_type: DidKeyType,
+        /// Raw public key bytes.
+        pub public_key: BoundedVec<u8, MaxPublicKeyLength>,
+        /// Whether the key is listed under `authentication`.
+        pub authentication: bool,
+        /// Whether the key is listed under `assertionMethod`.
+        pub assertion_method: bool,
+    }
+
+    /// Lifetime of a verification key bound to an agent.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct KeyRecord<T: Config> {
+        /// Fragment the key was published under.
+        pub fragment: BoundedVec<u8, T::MaxFragmentLength>,
+        /// Type of the public key.
+        pub key_type: DidKeyType,
+        /// Block at which the key was added.
+        pub added_at: BlockNumberFor<T>,
+        /// Block from which the key is no longer valid, once rotated out or revoked.
+        pub revoked_at: Option<BlockNumberFor<T>>,
+    }
+
+    /// A service endpoint in an agent's DID document.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Service<T: Config> {
+        /// Fragment identifying the service within the document.
+        pub fragment: BoundedVec<u8, T::MaxFragmentLength>,
+        /// Service type, e.g. `DIDCommMessaging` or `LinkedDomains`.
+        pub service_type: BoundedVec<u8, T::MaxFragmentLength>,
+        /// Service endpoint URI.
+        pub endpoint: BoundedVec<u8, T::MaxServiceEndpointLength>,
+    }
+
+    /// Verification methods and services of an agent's DID document.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen, frame_support::DefaultNoBound,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct DidDocument<T: Config> {
+        /// Public keys of the agent.
+        pub verification_methods: BoundedVec<VerificationMethod<T>, T::MaxVerificationMethods>,
+        /// Service endpoints of the agent.
+        pub services: BoundedVec<Service<T>, T::MaxServices>,
+    }
+
+    impl<T: Config> DidDocument<T> {
+        fn has_fragment(&self, fragment: &[u8]) -> bool {
+            self.verification_methods
+                .iter()
+                .any(|m| m.fragment.as_slice() == fragment)
+                || self
+                    .services
+                    .iter()
+                    .any(|s| s.fragment.as_slice() == fragment)
+        }
+    }
+
+    /// A set of calls an operator key may dispatch, by pallet and call index.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        codec::DecodeWithMemTracking,
+    )]
+    pub enum CallScope {
+        /// Every call of the pallet at this index in `construct_runtime!`.
+        Pallet(u8),
+        /// A single call, by pallet index and call index.
+        Call(u8, u8),
+    }
+
+    impl CallScope {
+        /// Whether a call with the given pallet and call index is in scope.
+        pub fn allows(&self, pallet_index: u8, call_index: u8) -> bool {
+            match *self {
+                CallScope::Pallet(pallet) => pallet == pallet_index,
+                CallScope::Call(pallet, call) => pallet == pallet_index && call == call_index,
+            }
+        }
+    }
+
+    /// Permissions of an operator key bound to an agent.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct OperatorInfo<T: Config> {
+        /// Calls the operator may dispatch as the agent.
+        pub scopes: BoundedVec<CallScope, T::MaxOperatorScopes>,
+        /// Maximum drop in the agent account's free balance per spending period,
+        /// or `None` for no cap.
+        pub spending_cap: Option<BalanceOf<T>>,
+        /// Amount spent in the current period.
+        pub spent: BalanceOf<T>,
+        /// Block at which the current spending period started.
+        pub period_start: BlockNumberFor<T>,
+    }
+
+    /// A capability an agent declares it can perform.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen, codec::DecodeWithMemTracking,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Capability<T: Config> {
+        /// Capability identifier, e.g. `ocr.rust`: lowercase ASCII letters, digits
+        /// and `.`, `-`, `_`, `/`.
+        pub id: BoundedVec<u8, T::MaxCapabilityIdLength>,
+        /// Version of the capability the agent implements.
+        pub version: u32,
+        /// Indicative price per request.
+        pub price_hint: BalanceOf<T>,
+        /// Maximum requests per hour the agent accepts, if limited.
+        pub rate_limit: Option<u32>,
+    }
+
+    /// Capability view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct CapabilitySummary<Balance> {
+        /// Capability identifier.
+        pub id: Vec<u8>,
+        /// Version of the capability the agent implements.
+        pub version: u32,
+        /// Indicative price per request.
+        pub price_hint: Balance,
+        /// Maximum requests per hour the agent accepts, if limited.
+        pub rate_limit: Option<u32>,
+    }
+
+    /// What remains of a purged agent.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct AgentTombstone<T: Config> {
+        /// The last owner of the agent.
+        pub owner: T::AccountId,
+        /// The agent's DID.
+        pub did: BoundedVec<u8, T::MaxDidLength>,
+        /// Block at which the agent was deregistered.
+        pub deregistered_at: BlockNumberFor<T>,
+        /// Block at which the agent was purged.
+        pub purged_at: BlockNumberFor<T>,
+    }
+
+    /// A suspension imposed by `AdminOrigin`.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct SuspensionInfo<T: Config> {
+        /// Why the agent was suspended.
+        pub reason: BoundedVec<u8, T::MaxSuspensionReasonLength>,
+        /// Block at which the suspension was imposed.
+        pub imposed_at: BlockNumberFor<T>,
+        /// Block from which the suspension no longer applies, if it expires.
+        pub expires_at: Option<BlockNumberFor<T>>,
+    }
+
+    impl<T: Config> SuspensionInfo<T> {
+        /// Whether the suspension still applies at block `now`.
+        pub fn is_in_force(&self, now: BlockNumberFor<T>) -> bool {
+            self.expires_at.is_none_or(|expires_at| now < expires_at)
+        }
+    }
+
+    /// A registered attestation issuer.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct IssuerInfo<T: Config> {
+        /// Human-readable issuer name.
+        pub name: BoundedVec<u8, T::MaxIssuerNameLength>,
+        /// Block at which the issuer was registered.
+        pub registered_at: BlockNumberFor<T>,
+    }
+
+    /// A claim an issuer attested about an agent.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Attestation<T: Config> {
+        /// Hash of the off-chain claim document.
+        pub claim_hash: DataHash,
+        /// Block at which the claim was attested.
+        pub issued_at: BlockNumberFor<T>,
+        /// Block from which the claim is no longer valid, if it expires.
+        pub expires_at: Option<BlockNumberFor<T>>,
+        /// Block at which the issuer revoked the claim.
+        pub revoked_at: Option<BlockNumberFor<T>>,
+    }
+
+    /// Attestation view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct AttestationSummary<AccountId, BlockNumber> {
+        /// Account that attested the claim.
+        pub issuer: AccountId,
+        /// Schema of the claim.
+        pub schema: SchemaId,
+        /// Hash of the off-chain claim document.
+        pub claim_hash: DataHash,
+        /// Block at which the claim was attested.
+        pub issued_at: BlockNumber,
+        /// Block from which the claim is no longer valid, if it expires.
+        pub expires_at: Option<BlockNumber>,
+        /// Block at which the issuer revoked the claim.
+        pub revoked_at: Option<BlockNumber>,
+        /// Whether the claim is currently valid.
+        pub valid: bool,
+    }
+
+    /// Agent view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct AgentSummary<AccountId, BlockNumber> {
+        /// The agent ID.
+        pub agent_id: AgentId,
+        /// The account that owns this agent.
+        pub owner: AccountId,
+        /// The keyless account operator keys act as.
+        pub agent_account: AccountId,
+        /// Decentralized identifier (DID) for the agent.
+        pub did: Vec<u8>,
+        /// The agent's ERC-8004 domain, if set.
+        pub domain: Option<Vec<u8>>,
+        /// JSON metadata (name, type, capabilities, etc.).
+        pub metadata: Vec<u8>,
+        /// Reputation score in basis points (0-10000).
+        pub reputation: u32,
+        /// Block number when the agent was registered.
+        pub registered_at: BlockNumber,
+        /// Block number of the agent's last activity.
+        pub last_active: BlockNumber,
+        /// Block number of the agent's last heartbeat, or its registration.
+        pub last_heartbeat: BlockNumber,
         /// Current status of the agent.
         pub status: AgentStatus,
     }
 
+    /// Page of agents returned by a runtime API listing.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct AgentPage<AccountId, BlockNumber> {
+        /// Matching agents, in agent ID order.
+        pub agents: Vec<AgentSummary<AccountId, BlockNumber>>,
+        /// Agent ID to continue the listing from, or `None` once every agent was scanned.
+        pub next: Option<AgentId>,
+    }
+
+    /// Heartbeat signed by an agent's owner or operator key for `heartbeat_unsigned`.
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, codec::DecodeWithMemTracking,
+    )]
+    pub struct HeartbeatPayload<AccountId, BlockNumber> {
+        /// The agent reported alive.
+        pub agent_id: AgentId,
+        /// The owner or operator key that signed the payload.
+        pub signer: AccountId,
+        /// Block at which the payload was signed.
+        pub block_number: BlockNumber,
+    }
+
+    /// Heartbeat payload type for a given runtime.
+    pub type HeartbeatPayloadOf<T> =
+        HeartbeatPayload<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// Agent summary type for a given runtime.
+    pub type AgentSummaryOf<T> =
+        AgentSummary<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// Agent page type for a given runtime.
+    pub type AgentPageOf<T> = AgentPage<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
     /// The pallet's configuration trait.
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -89,6 +710,68 @@ pub mod pallet {
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
 
+        /// The overarching call type, dispatched by operator keys.
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo
+            + From<frame_system::Call<Self>>;
+
+        /// Pallet ID from which agent accounts are derived.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Maximum number of operator keys per agent.
+        #[pallet::constant]
+        type MaxOperatorsPerAgent: Get<u32>;
+
+        /// Maximum number of call scopes per operator key.
+        #[pallet::constant]
+        type MaxOperatorScopes: Get<u32>;
+
+        /// Length in blocks of an operator's spending-cap period.
+        #[pallet::constant]
+        type OperatorSpendingPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Blocks without a heartbeat after which an Active agent is marked Inactive.
+        #[pallet::constant]
+        type InactivityPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Minimum blocks between an agent's last heartbeat and a fee-less
+        /// `heartbeat_unsigned`; a fraction of `InactivityPeriod`.
+        #[pallet::constant]
+        type MinHeartbeatInterval: Get<BlockNumberFor<Self>>;
+
+        /// Transaction pool priority of `heartbeat_unsigned`.
+        #[pallet::constant]
+        type HeartbeatUnsignedPriority: Get<TransactionPriority>;
+
+        /// Signature over a `HeartbeatPayload`, verified against its signer.
+        type HeartbeatSignature: Parameter
+            + Verify<Signer: IdentifyAccount<AccountId = Self::AccountId>>;
+
+        /// Maximum number of agents checked for inactivity in a single block.
+        #[pallet::constant]
+        type MaxInactivitySweepPerBlock: Get<u32>;
+
+        /// Maximum length of a capability ID.
+        #[pallet::constant]
+        type MaxCapabilityIdLength: Get<u32>;
+
+        /// Maximum number of capabilities an agent can declare.
+        #[pallet::constant]
+        type MaxCapabilitiesPerAgent: Get<u32>;
+
+        /// Currency used for registration deposits.
+        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+
+        /// Base deposit reserved from the owner for every agent.
+        #[pallet::constant]
+        type AgentDepositBase: Get<BalanceOf<Self>>;
+
+        /// Additional deposit per byte of agent DID and metadata.
+        #[pallet::constant]
+        type AgentDepositPerByte: Get<BalanceOf<Self>>;
+
         /// Maximum length of a DID in bytes.
         #[pallet::constant]
         type MaxDidLength: Get<u32>;
@@ -100,9 +783,70 @@ pub mod pallet {
         /// Maximum number of agents a single account can own.
         #[pallet::constant]
         type MaxAgentsPerOwner: Get<u32>;
+
+        /// DID methods accepted at registration (e.g. `b"claw"`). Empty accepts any method.
+        #[pallet::constant]
+        type AllowedDidMethods: Get<Vec<Vec<u8>>>;
+
+        /// Blocks a deregistered agent's DID stays reserved before it can be reused.
+        #[pallet::constant]
+        type DidReleaseCooldown: Get<BlockNumberFor<Self>>;
+
+        /// Blocks after deregistration from which anyone may purge an agent.
+        #[pallet::constant]
+        type PurgeGracePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of verification methods in a DID document.
+        #[pallet::constant]
+        type MaxVerificationMethods: Get<u32>;
+
+        /// Maximum number of services in a DID document.
+        #[pallet::constant]
+        type MaxServices: Get<u32>;
+
+        /// Maximum length of a DID document fragment or service type.
+        #[pallet::constant]
+        type MaxFragmentLength: Get<u32>;
+
+        /// Maximum length of a service endpoint URI.
+        #[pallet::constant]
+        type MaxServiceEndpointLength: Get<u32>;
+
+        /// Source of the owner and agent account scores an agent's reputation is
+        /// derived from.
+        type Reputation: ReputationScore<Self::AccountId>;
+
+        /// Origin allowed to adjust an agent's reputation (e.g. root or governance).
+        type ReputationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to impose and lift suspensions (e.g. root or governance).
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum length of a suspension reason.
+        #[pallet::constant]
+        type MaxSuspensionReasonLength: Get<u32>;
+
+        /// Maximum length of an agent domain.
+        #[pallet::constant]
+        type MaxDomainLength: Get<u32>;
+
+        /// Origin allowed to register and remove attestation issuers.
+        type IssuerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum length of an issuer name.
+        #[pallet::constant]
+        type MaxIssuerNameLength: Get<u32>;
+
+        /// Maximum number of attestations held by a single agent.
+        #[pallet::constant]
+        type MaxAttestationsPerAgent: Get<u32>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ========== Storage ==========
@@ -129,41 +873,282 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    // ========== Events ==========
-
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// A new agent was registered.
-        AgentRegistered {
-            agent_id: AgentId,
-            owner: T::AccountId,
-            did: Vec<u8>,
-        },
-        /// An agent's metadata was updated.
-        AgentUpdated {
-            agent_id: AgentId,
-            metadata: Vec<u8>,
-        },
-        /// An agent's reputation score changed.
-        ReputationChanged {
-            agent_id: AgentId,
-            old_score: u32,
-            new_score: u32,
-        },
-        /// An agent was deregistered.
-        AgentDeregistered { agent_id: AgentId },
-        /// An agent's status was changed.
-        AgentStatusChanged {
-            agent_id: AgentId,
-            status: AgentStatus,
-        },
-    }
+    /// Registration deposit reserved from each agent's owner.
+    #[pallet::storage]
+    #[pallet::getter(fn agent_deposits)]
+    pub type AgentDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, BalanceOf<T>, ValueQuery>;
 
-    // ========== Errors ==========
+    /// Pending ownership transfers: agent ID to the proposed new owner.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfers)]
+    pub type PendingTransfers<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, T::AccountId, OptionQuery>;
 
-    #[pallet::error]
-    pub enum Error<T> {
+    /// Operator keys bound to each agent, with their permissions.
+    #[pallet::storage]
+    pub type Operators<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AgentId,
+        Blake2_128Concat,
+        T::AccountId,
+        OperatorInfo<T>,
+        OptionQuery,
+    >;
+
+    /// Number of operator keys bound to each agent.
+    #[pallet::storage]
+    pub type OperatorCount<T: Config> = StorageMap<_, Blake2_128Concat, AgentId, u32, ValueQuery>;
+
+    /// Reverse index from DID to the agent that holds it.
+    #[pallet::storage]
+    pub type DidToAgent<T: Config> =
+        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDidLength>, AgentId, OptionQuery>;
+
+    /// Block at which a deregistered agent's DID becomes available again.
+    #[pallet::storage]
+    pub type DidReleaseAt<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxDidLength>,
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Tombstones of purged agents.
+    #[pallet::storage]
+    #[pallet::getter(fn agent_tombstones)]
+    pub type AgentTombstones<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, AgentTombstone<T>, OptionQuery>;
+
+    /// DID document of each agent.
+    #[pallet::storage]
+    pub type DidDocuments<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, DidDocument<T>, ValueQuery>;
+
+    /// Every verification key ever bound to each agent. Kept after revocation and
+    /// deregistration so old signatures stay verifiable.
+    #[pallet::storage]
+    pub type KeyHistory<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AgentId,
+        Blake2_128Concat,
+        BoundedVec<u8, MaxPublicKeyLength>,
+        KeyRecord<T>,
+        OptionQuery,
+    >;
+
+    /// Governance adjustment applied on top of each agent's derived reputation.
+    #[pallet::storage]
+    pub type ReputationAdjustments<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, i32, ValueQuery>;
+
+    /// Capabilities declared by each agent.
+    #[pallet::storage]
+    pub type AgentCapabilities<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        AgentId,
+        BoundedVec<Capability<T>, T::MaxCapabilitiesPerAgent>,
+        ValueQuery,
+    >;
+
+    /// Index from capability ID to the agents declaring it.
+    #[pallet::storage]
+    pub type CapabilityIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxCapabilityIdLength>,
+        Blake2_128Concat,
+        AgentId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Suspensions imposed by `AdminOrigin`, by agent.
+    #[pallet::storage]
+    #[pallet::getter(fn suspensions)]
+    pub type Suspensions<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, SuspensionInfo<T>, OptionQuery>;
+
+    /// Next agent ID the inactivity sweep will check.
+    #[pallet::storage]
+    pub type InactivitySweepCursor<T: Config> = StorageValue<_, AgentId, ValueQuery>;
+
+    /// Agents under an emergency freeze, with the block the freeze started at.
+    #[pallet::storage]
+    #[pallet::getter(fn frozen_agents)]
+    pub type FrozenAgents<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, BlockNumberFor<T>, OptionQuery>;
+
+    /// ERC-8004 domain of each agent.
+    #[pallet::storage]
+    pub type AgentDomains<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, BoundedVec<u8, T::MaxDomainLength>, OptionQuery>;
+
+    /// Reverse index from domain to the agent that holds it.
+    #[pallet::storage]
+    pub type DomainToAgent<T: Config> =
+        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDomainLength>, AgentId, OptionQuery>;
+
+    /// Registered attestation issuers.
+    #[pallet::storage]
+    #[pallet::getter(fn issuers)]
+    pub type Issuers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, IssuerInfo<T>, OptionQuery>;
+
+    /// Claims attested about each agent, by issuer and schema.
+    #[pallet::storage]
+    pub type Attestations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AgentId,
+        Blake2_128Concat,
+        (T::AccountId, SchemaId),
+        Attestation<T>,
+        OptionQuery,
+    >;
+
+    /// Number of attestations held by each agent.
+    #[pallet::storage]
+    pub type AttestationCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, AgentId, u32, ValueQuery>;
+
+    // ========== Events ==========
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A new agent was registered.
+        AgentRegistered {
+            agent_id: AgentId,
+            owner: T::AccountId,
+            did: Vec<u8>,
+        },
+        /// An agent's metadata was updated.
+        AgentUpdated {
+            agent_id: AgentId,
+            metadata: Vec<u8>,
+        },
+        /// `ReputationOrigin` adjusted an agent's reputation score.
+        ReputationChanged {
+            agent_id: AgentId,
+            old_score: u32,
+            new_score: u32,
+        },
+        /// An agent was deregistered.
+        AgentDeregistered { agent_id: AgentId },
+        /// A deregistered agent was purged, leaving a tombstone.
+        AgentPurged {
+            agent_id: AgentId,
+            owner: T::AccountId,
+        },
+        /// An agent's status was changed.
+        AgentStatusChanged {
+            agent_id: AgentId,
+            status: AgentStatus,
+        },
+        /// A verification method was added to an agent's DID document.
+        VerificationMethodAdded {
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+            key_type: DidKeyType,
+        },
+        /// A verification method was revoked and removed from an agent's DID document.
+        VerificationMethodRevoked {
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+        },
+        /// The key behind a verification method was replaced.
+        VerificationMethodRotated {
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+            key_type: DidKeyType,
+        },
+        /// `AdminOrigin` suspended an agent.
+        AgentSuspended {
+            agent_id: AgentId,
+            reason: Vec<u8>,
+            expires_at: Option<BlockNumberFor<T>>,
+        },
+        /// An admin suspension was lifted or expired.
+        SuspensionLifted { agent_id: AgentId },
+        /// An agent's capability declarations were replaced.
+        CapabilitiesUpdated { agent_id: AgentId, count: u32 },
+        /// An agent was frozen by its owner.
+        AgentFrozen { agent_id: AgentId },
+        /// An agent's freeze was lifted.
+        AgentUnfrozen { agent_id: AgentId },
+        /// A service was added to an agent's DID document.
+        ServiceAdded {
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+        },
+        /// A service was removed from an agent's DID document.
+        ServiceRemoved {
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+        },
+        /// The owner proposed transferring an agent to a new owner.
+        TransferProposed {
+            agent_id: AgentId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// A pending transfer was cancelled by the owner.
+        TransferCancelled { agent_id: AgentId },
+        /// An agent was transferred to a new owner.
+        AgentTransferred {
+            agent_id: AgentId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// An operator key was bound to an agent or its permissions were replaced.
+        OperatorSet {
+            agent_id: AgentId,
+            operator: T::AccountId,
+        },
+        /// An operator key was unbound from an agent.
+        OperatorRemoved {
+            agent_id: AgentId,
+            operator: T::AccountId,
+        },
+        /// An operator dispatched a call as the agent account.
+        OperatorCallExecuted {
+            agent_id: AgentId,
+            operator: T::AccountId,
+        },
+        /// An agent's domain was set (`Some`) or cleared (`None`).
+        AgentDomainSet {
+            agent_id: AgentId,
+            domain: Option<Vec<u8>>,
+        },
+        /// An attestation issuer was registered.
+        IssuerRegistered { issuer: T::AccountId },
+        /// An attestation issuer was removed; its claims are no longer valid.
+        IssuerRemoved { issuer: T::AccountId },
+        /// An issuer attested a claim about an agent.
+        AttestationIssued {
+            agent_id: AgentId,
+            issuer: T::AccountId,
+            schema: SchemaId,
+            claim_hash: DataHash,
+            expires_at: Option<BlockNumberFor<T>>,
+        },
+        /// An issuer revoked a claim about an agent.
+        AttestationRevoked {
+            agent_id: AgentId,
+            issuer: T::AccountId,
+            schema: SchemaId,
+        },
+    }
+
+    // ========== Errors ==========
+
+    #[pallet::error]
+    pub enum Error<T> {
         /// The agent ID was not found in the registry.
         AgentNotFound,
         /// Only the agent owner can perform this action.
@@ -180,6 +1165,147 @@ pub mod pallet {
         ReputationOverflow,
         /// Reputation score would underflow (min 0).
         ReputationUnderflow,
+        /// The owner cannot cover the registration deposit.
+        InsufficientDeposit,
+        /// The DID is not of the form `did:<method>:<method-specific-id>`.
+        InvalidDid,
+        /// The DID method is not in the allow-list.
+        DidMethodNotAllowed,
+        /// The DID is already held by another agent.
+        DidAlreadyRegistered,
+        /// The DID belongs to a deregistered agent and its cooldown has not elapsed.
+        DidInCooldown,
+        /// The fragment is empty, too long, or contains characters outside `A-Z a-z 0-9 . - _`.
+        InvalidFragment,
+        /// The fragment is already used by a method or service in the document.
+        DuplicateFragment,
+        /// No method or service with this fragment exists in the document.
+        FragmentNotFound,
+        /// The public key length does not match the key type.
+        InvalidPublicKey,
+        /// The service type is empty, too long, or not alphanumeric.
+        InvalidServiceType,
+        /// The service endpoint is too long or not a URI.
+        InvalidServiceEndpoint,
+        /// The DID document already holds the maximum number of verification methods.
+        TooManyVerificationMethods,
+        /// The DID document already holds the maximum number of services.
+        TooManyServices,
+        /// An agent cannot be transferred to its current owner.
+        TransferToSelf,
+        /// There is no pending transfer for this agent.
+        NoPendingTransfer,
+        /// The caller is not the proposed new owner.
+        NotTransferRecipient,
+        /// The caller is not an operator of this agent.
+        NotOperator,
+        /// The agent already has the maximum number of operator keys.
+        TooManyOperators,
+        /// More call scopes than `MaxOperatorScopes`.
+        TooManyScopes,
+        /// The call is outside the operator's scopes.
+        CallNotPermitted,
+        /// The call would take the operator past its spending cap.
+        SpendingCapExceeded,
+        /// Only active agents can act through operator keys.
+        AgentNotActive,
+        /// The public key has already been bound to this agent.
+        KeyAlreadyUsed,
+        /// The agent is frozen.
+        AgentFrozen,
+        /// The agent is not frozen.
+        AgentNotFrozen,
+        /// A capability ID is empty or contains disallowed characters.
+        InvalidCapabilityId,
+        /// The same capability ID is declared twice.
+        DuplicateCapability,
+        /// Owners can only set an agent Active or Suspended.
+        InvalidStatus,
+        /// The agent is under an admin suspension the owner cannot clear.
+        AgentSuspendedByAdmin,
+        /// Suspension reason exceeds `MaxSuspensionReasonLength`.
+        SuspensionReasonTooLong,
+        /// The suspension would already have expired.
+        InvalidExpiry,
+        /// The agent is not under an admin suspension.
+        NotSuspended,
+        /// Only deregistered agents can be purged.
+        AgentNotDeregistered,
+        /// Only the owner can purge before `PurgeGracePeriod` has elapsed.
+        PurgeTooEarly,
+        /// The caller is neither the agent's owner nor its agent account.
+        NotAgentController,
+        /// The domain is empty, too long, or not a lowercase DNS name.
+        InvalidDomain,
+        /// The domain is already held by another agent.
+        DomainAlreadyRegistered,
+        /// The account is already a registered issuer.
+        IssuerAlreadyRegistered,
+        /// The account is not a registered issuer.
+        NotIssuer,
+        /// Issuer name exceeds `MaxIssuerNameLength`.
+        IssuerNameTooLong,
+        /// An issuer cannot attest an agent it controls.
+        SelfAttestation,
+        /// The agent already holds `MaxAttestationsPerAgent` attestations.
+        TooManyAttestations,
+        /// The issuer has no attestation of this schema for the agent.
+        AttestationNotFound,
+        /// The attestation has already been revoked.
+        AttestationAlreadyRevoked,
+        /// The DID belonged to a purged agent and can never be claimed again.
+        DidRetired,
+        /// The heartbeat was signed before the agent's last heartbeat or in the future.
+        StaleHeartbeat,
+        /// The agent's last heartbeat was less than `MinHeartbeatInterval` blocks
+        /// before the unsigned heartbeat.
+        HeartbeatTooFrequent,
+    }
+
+    // ========== Hooks ==========
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::sweep_inactive_agents(now, remaining_weight)
+        }
+    }
+
+    // ========== Unsigned Transactions ==========
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// Accept a `heartbeat_unsigned` whose payload is signed by its signer and
+        /// would be accepted in this block, once per agent in the pool. The
+        /// transaction lives for `MinHeartbeatInterval` blocks.
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let Call::heartbeat_unsigned { payload, signature } = call else {
+                return InvalidTransaction::Call.into();
+            };
+            if !signature.verify(&payload.encode()[..], &payload.signer) {
+                return InvalidTransaction::BadProof.into();
+            }
+            Self::ensure_fresh_heartbeat(payload).map_err(|e| {
+                if e == Error::<T>::StaleHeartbeat.into()
+                    || e == Error::<T>::HeartbeatTooFrequent.into()
+                {
+                    InvalidTransaction::Stale
+                } else {
+                    InvalidTransaction::Call
+                }
+            })?;
+
+            let longevity: TransactionLongevity =
+                T::MinHeartbeatInterval::get().unique_saturated_into();
+            ValidTransaction::with_tag_prefix("AgentHeartbeat")
+                .priority(T::HeartbeatUnsignedPriority::get())
+                .and_provides(payload.agent_id)
+                .longevity(longevity.max(1))
+                .propagate(true)
+                .build()
+        }
     }
 
     // ========== Extrinsics ==========
@@ -189,13 +1315,15 @@ pub mod pallet {
         /// Register a new agent on-chain.
         ///
         /// The caller becomes the owner of the agent. The agent starts with
-        /// a reputation score of 5000 (50%) and Active status.
+        /// Active status. The DID must be
+        /// well-formed, use an allowed method, and not be held by another agent.
+        /// Reserves the registration deposit for the DID and metadata.
         ///
         /// # Arguments
         /// * `did` - Decentralized identifier for the agent
         /// * `metadata` - JSON metadata (name, type, capabilities)
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 3))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(5, 6))]
         pub fn register_agent(
             origin: OriginFor<T>,
             did: Vec<u8>,
@@ -208,21 +1336,32 @@ pub mod pallet {
             let bounded_metadata: BoundedVec<u8, T::MaxMetadataLength> =
                 metadata.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
 
+            Self::validate_did(&did)?;
+
+            let deposit =
+                Self::agent_deposit(bounded_did.len().saturating_add(bounded_metadata.len()));
+            T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+
             let agent_id = AgentCount::<T>::get();
             let current_block = <frame_system::Pallet<T>>::block_number();
 
+            Self::claim_did(&bounded_did, agent_id, current_block)?;
+
             let agent_info = AgentInfo::<T> {
                 owner: who.clone(),
                 did: bounded_did,
                 metadata: bounded_metadata,
-                reputation: 5000, // Start at 50%
                 registered_at: current_block,
                 last_active: current_block,
+                last_heartbeat: current_block,
                 status: AgentStatus::Active,
             };
 
             // Store the agent
             AgentRegistry::<T>::insert(agent_id, agent_info);
+            if !deposit.is_zero() {
+                AgentDeposits::<T>::insert(agent_id, deposit);
+            }
 
             // Update agent count
             AgentCount::<T>::put(agent_id.saturating_add(1));
@@ -245,9 +1384,10 @@ pub mod pallet {
 
         /// Update an agent's metadata.
         ///
-        /// Only the agent owner can update the metadata.
+        /// Only the agent owner can update the metadata. The registration deposit is
+        /// topped up or partially refunded to match the new metadata size.
         #[pallet::call_index(1)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 3))]
         pub fn update_metadata(
             origin: OriginFor<T>,
             agent_id: AgentId,
@@ -266,6 +1406,10 @@ pub mod pallet {
                 let bounded_metadata: BoundedVec<u8, T::MaxMetadataLength> =
                     metadata.clone().try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
 
+                let new_deposit =
+                    Self::agent_deposit(agent.did.len().saturating_add(bounded_metadata.len()));
+                Self::set_deposit(agent_id, &who, new_deposit)?;
+
                 agent.metadata = bounded_metadata;
                 agent.last_active = <frame_system::Pallet<T>>::block_number();
 
@@ -282,17 +1426,18 @@ pub mod pallet {
 
         /// Update an agent's reputation score.
         ///
-        /// Can be called by anyone (in production, this would be restricted to
-        /// a reputation oracle or governance). The delta is applied to the current
-        /// score, clamped to 0-10000.
+        /// Only `ReputationOrigin` may call this. The delta is added to the agent's
+        /// adjustment in `ReputationAdjustments`, which stays applied on top of the
+        /// derived score, clamped to 0-10000, until adjusted again. The adjustment is
+        /// capped so that it never exceeds the full 0-10000 range.
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(4, 2))]
         pub fn update_reputation(
             origin: OriginFor<T>,
             agent_id: AgentId,
             delta: i32,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            T::ReputationOrigin::ensure_origin(origin)?;
 
             AgentRegistry::<T>::try_mutate(agent_id, |maybe_agent| -> DispatchResult {
                 let agent = maybe_agent.as_mut().ok_or(Error::<T>::AgentNotFound)?;
@@ -301,13 +1446,12 @@ pub mod pallet {
                     Error::<T>::AgentAlreadyDeregistered
                 );
 
-                let old_score = agent.reputation;
-                let new_score = if delta >= 0 {
-                    old_score.saturating_add(delta as u32).min(10000)
-                } else {
-                    old_score.saturating_sub(delta.unsigned_abs())
-                };
-                agent.reputation = new_score;
+                let old_score = Self::reputation_of(agent_id, agent);
+                let max = MAX_REPUTATION as i32;
+                ReputationAdjustments::<T>::mutate(agent_id, |adjustment| {
+                    *adjustment = adjustment.saturating_add(delta).clamp(-max, max);
+                });
+                let new_score = Self::reputation_of(agent_id, agent);
                 agent.last_active = <frame_system::Pallet<T>>::block_number();
 
                 Self::deposit_event(Event::ReputationChanged {
@@ -323,9 +1467,11 @@ pub mod pallet {
         /// Deregister an agent.
         ///
         /// Only the agent owner can deregister. Sets the status to Deregistered.
-        /// The agent data remains on-chain for historical purposes.
+        /// The agent data remains on-chain for historical purposes. The DID is
+        /// released for reuse after `DidReleaseCooldown` blocks and the registration
+        /// deposit is refunded.
         #[pallet::call_index(3)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 5))]
         pub fn deregister_agent(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -337,8 +1483,22 @@ pub mod pallet {
                     Error::<T>::AgentAlreadyDeregistered
                 );
 
+                let now = <frame_system::Pallet<T>>::block_number();
                 agent.status = AgentStatus::Deregistered;
-                agent.last_active = <frame_system::Pallet<T>>::block_number();
+                agent.last_active = now;
+
+                DidReleaseAt::<T>::insert(
+                    &agent.did,
+                    now.saturating_add(T::DidReleaseCooldown::get()),
+                );
+
+                T::Currency::unreserve(&who, AgentDeposits::<T>::take(agent_id));
+                PendingTransfers::<T>::remove(agent_id);
+                Self::clear_operators(agent_id);
+                FrozenAgents::<T>::remove(agent_id);
+                Self::clear_capabilities(agent_id);
+                Suspensions::<T>::remove(agent_id);
+                Self::clear_domain(agent_id);
 
                 Ok(())
             })?;
@@ -350,16 +1510,21 @@ pub mod pallet {
 
         /// Set an agent's status.
         ///
-        /// Only the agent owner can change the status.
-        /// Cannot change status of a deregistered agent.
+        /// Only the agent owner can change the status, and only to Active or
+        /// Suspended (a voluntary pause). Cannot change status of a deregistered
+        /// agent or of one under an admin suspension.
         #[pallet::call_index(4)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
         pub fn set_agent_status(
             origin: OriginFor<T>,
             agent_id: AgentId,
             status: AgentStatus,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            ensure!(
+                matches!(status, AgentStatus::Active | AgentStatus::Suspended),
+                Error::<T>::InvalidStatus
+            );
 
             AgentRegistry::<T>::try_mutate(agent_id, |maybe_agent| -> DispatchResult {
                 let agent = maybe_agent.as_mut().ok_or(Error::<T>::AgentNotFound)?;
@@ -368,6 +1533,15 @@ pub mod pallet {
                     agent.status != AgentStatus::Deregistered,
                     Error::<T>::AgentAlreadyDeregistered
                 );
+                if let Some(suspension) = Suspensions::<T>::get(agent_id) {
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    ensure!(
+                        !suspension.is_in_force(now),
+                        Error::<T>::AgentSuspendedByAdmin
+                    );
+                    Suspensions::<T>::remove(agent_id);
+                    Self::deposit_event(Event::SuspensionLifted { agent_id });
+                }
 
                 agent.status = status.clone();
                 agent.last_active = <frame_system::Pallet<T>>::block_number();
@@ -379,34 +1553,1669 @@ pub mod pallet {
 
             Ok(())
         }
-    }
 
-    // ========== Weight Info Trait ==========
+        /// Add a public key to an agent's DID document.
+        ///
+        /// Only the agent owner can modify the document. `fragment` must be unique
+        /// within the document and `public_key` must match the length of `key_type`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn add_verification_method(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+            key_type: DidKeyType,
+            public_key: Vec<u8>,
+            authentication: bool,
+            assertion_method: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
 
-    /// Weight information for the pallet's extrinsics.
-    pub trait WeightInfo {
-        fn register_agent() -> Weight;
-        fn update_metadata() -> Weight;
-        fn update_reputation() -> Weight;
-        fn deregister_agent() -> Weight;
-        fn set_agent_status() -> Weight;
-    }
+            let bounded_fragment = Self::bounded_fragment(&fragment)?;
+            let public_key = Self::unused_key(agent_id, key_type, public_key)?;
 
-    /// Default weights for testing.
-    impl WeightInfo for () {
-        fn register_agent() -> Weight {
-            Weight::from_parts(10_000, 0)
+            DidDocuments::<T>::try_mutate(agent_id, |doc| -> DispatchResult {
+                ensure!(!doc.has_fragment(&fragment), Error::<T>::DuplicateFragment);
+                Self::record_key(agent_id, &public_key, &bounded_fragment, key_type);
+                doc.verification_methods
+                    .try_push(VerificationMethod {
+                        fragment: bounded_fragment,
+                        key_type,
+                        public_key,
+                        authentication,
+                        assertion_method,
+                    })
+                    .map_err(|_| Error::<T>::TooManyVerificationMethods)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationMethodAdded {
+                agent_id,
+                fragment,
+                key_type,
+            });
+
+            Ok(())
         }
-        fn update_metadata() -> Weight {
-            Weight::from_parts(10_000, 0)
+
+        /// Revoke a public key and remove it from an agent's DID document.
+        ///
+        /// Only the agent owner can modify the document. The key stays in
+        /// `KeyHistory`, valid up to the current block, and cannot be added again.
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 2))]
+        pub fn revoke_verification_method(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            DidDocuments::<T>::try_mutate(agent_id, |doc| -> DispatchResult {
+                let index = doc
+                    .verification_methods
+                    .iter()
+                    .position(|m| m.fragment.as_slice() == fragment.as_slice())
+                    .ok_or(Error::<T>::FragmentNotFound)?;
+                let method = doc.verification_methods.remove(index);
+                Self::revoke_key(agent_id, &method.public_key);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationMethodRevoked { agent_id, fragment });
+
+            Ok(())
         }
-        fn update_reputation() -> Weight {
-            Weight::from_parts(10_000, 0)
+
+        /// Add a service endpoint to an agent's DID document.
+        ///
+        /// Only the agent owner can modify the document. `service_type` must be
+        /// alphanumeric and `endpoint` an absolute URI.
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn add_service(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+            service_type: Vec<u8>,
+            endpoint: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            let bounded_fragment = Self::bounded_fragment(&fragment)?;
+            ensure!(
+                !service_type.is_empty() && service_type.iter().all(u8::is_ascii_alphanumeric),
+                Error::<T>::InvalidServiceType
+            );
+            let service_type =
+                BoundedVec::try_from(service_type).map_err(|_| Error::<T>::InvalidServiceType)?;
+            ensure!(
+                did::is_valid_uri(&endpoint),
+                Error::<T>::InvalidServiceEndpoint
+            );
+            let endpoint =
+                BoundedVec::try_from(endpoint).map_err(|_| Error::<T>::InvalidServiceEndpoint)?;
+
+            DidDocuments::<T>::try_mutate(agent_id, |doc| -> DispatchResult {
+                ensure!(!doc.has_fragment(&fragment), Error::<T>::DuplicateFragment);
+                doc.services
+                    .try_push(Service {
+                        fragment: bounded_fragment,
+                        service_type,
+                        endpoint,
+                    })
+                    .map_err(|_| Error::<T>::TooManyServices)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ServiceAdded { agent_id, fragment });
+
+            Ok(())
         }
-        fn deregister_agent() -> Weight {
-            Weight::from_parts(10_000, 0)
+
+        /// Remove a service endpoint from an agent's DID document.
+        ///
+        /// Only the agent owner can modify the document.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn remove_service(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            DidDocuments::<T>::try_mutate(agent_id, |doc| -> DispatchResult {
+                let index = doc
+                    .services
+                    .iter()
+                    .position(|s| s.fragment.as_slice() == fragment.as_slice())
+                    .ok_or(Error::<T>::FragmentNotFound)?;
+                doc.services.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ServiceRemoved { agent_id, fragment });
+
+            Ok(())
         }
-        fn set_agent_status() -> Weight {
+
+        /// Propose transferring an agent to `new_owner`.
+        ///
+        /// Only the agent owner can propose. Replaces any earlier pending proposal.
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        pub fn propose_transfer(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+            ensure!(who != new_owner, Error::<T>::TransferToSelf);
+            ensure!(
+                !FrozenAgents::<T>::contains_key(agent_id),
+                Error::<T>::AgentFrozen
+            );
+
+            PendingTransfers::<T>::insert(agent_id, &new_owner);
+
+            Self::deposit_event(Event::TransferProposed {
+                agent_id,
+                from: who,
+                to: new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel a pending transfer.
+        ///
+        /// Only the agent owner can cancel.
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn cancel_transfer(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(agent.owner == who, Error::<T>::NotAgentOwner);
+            PendingTransfers::<T>::take(agent_id).ok_or(Error::<T>::NoPendingTransfer)?;
+
+            Self::deposit_event(Event::TransferCancelled { agent_id });
+
+            Ok(())
+        }
+
+        /// Accept a pending transfer, becoming the agent's owner.
+        ///
+        /// Only the proposed new owner can accept. The registration deposit is
+        /// refunded to the old owner and reserved from the caller, and the caller
+        /// must have room in their `OwnerAgents` list. The old owner's verification
+        /// methods are revoked and the DID document's services removed.
+        #[pallet::call_index(11)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(6, 7)
+                + T::DbWeight::get().writes(T::MaxVerificationMethods::get().into())
+        )]
+        pub fn accept_transfer(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let proposed =
+                PendingTransfers::<T>::get(agent_id).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(proposed == who, Error::<T>::NotTransferRecipient);
+            ensure!(
+                !FrozenAgents::<T>::contains_key(agent_id),
+                Error::<T>::AgentFrozen
+            );
+
+            let old_owner = AgentRegistry::<T>::try_mutate(
+                agent_id,
+                |maybe_agent| -> Result<T::AccountId, DispatchError> {
+                    let agent = maybe_agent.as_mut().ok_or(Error::<T>::AgentNotFound)?;
+                    ensure!(
+                        agent.status != AgentStatus::Deregistered,
+                        Error::<T>::AgentAlreadyDeregistered
+                    );
+
+                    agent.last_active = <frame_system::Pallet<T>>::block_number();
+                    Ok(core::mem::replace(&mut agent.owner, who.clone()))
+                },
+            )?;
+
+            OwnerAgents::<T>::mutate(&old_owner, |agents| agents.retain(|id| *id != agent_id));
+            OwnerAgents::<T>::try_mutate(&who, |agents| {
+                agents
+                    .try_push(agent_id)
+                    .map_err(|_| Error::<T>::TooManyAgents)
+            })?;
+
+            let deposit = AgentDeposits::<T>::get(agent_id);
+            T::Currency::unreserve(&old_owner, deposit);
+            T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+
+            PendingTransfers::<T>::remove(agent_id);
+            Self::clear_operators(agent_id);
+            Self::clear_did_document(agent_id);
+
+            Self::deposit_event(Event::AgentTransferred {
+                agent_id,
+                from: old_owner,
+                to: who,
+            });
+
+            Ok(())
+        }
+
+        /// Bind an operator key to an agent, or replace its permissions.
+        ///
+        /// Only the agent owner can manage operators. `scopes` lists the calls the
+        /// operator may dispatch through `operate`; `spending_cap` bounds how far
+        /// the agent account's free balance may drop per `OperatorSpendingPeriod`.
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 2))]
+        pub fn add_operator(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            operator: T::AccountId,
+            scopes: Vec<CallScope>,
+            spending_cap: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            let scopes: BoundedVec<CallScope, T::MaxOperatorScopes> =
+                scopes.try_into().map_err(|_| Error::<T>::TooManyScopes)?;
+
+            if !Operators::<T>::contains_key(agent_id, &operator) {
+                OperatorCount::<T>::try_mutate(agent_id, |count| -> DispatchResult {
+                    ensure!(
+                        *count < T::MaxOperatorsPerAgent::get(),
+                        Error::<T>::TooManyOperators
+                    );
+                    *count = count.saturating_add(1);
+                    Ok(())
+                })?;
+            }
+
+            Operators::<T>::insert(
+                agent_id,
+                &operator,
+                OperatorInfo {
+                    scopes,
+                    spending_cap,
+                    spent: Zero::zero(),
+                    period_start: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::OperatorSet { agent_id, operator });
+
+            Ok(())
+        }
+
+        /// Unbind an operator key from an agent.
+        ///
+        /// Only the agent owner can manage operators.
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        pub fn remove_operator(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(agent.owner == who, Error::<T>::NotAgentOwner);
+
+            Operators::<T>::take(agent_id, &operator).ok_or(Error::<T>::NotOperator)?;
+            OperatorCount::<T>::mutate(agent_id, |count| *count = count.saturating_sub(1));
+
+            Self::deposit_event(Event::OperatorRemoved { agent_id, operator });
+
+            Ok(())
+        }
+
+        /// Dispatch `call` as the agent account using an operator key.
+        ///
+        /// The call must be within the operator's scopes and the agent must be
+        /// Active or Inactive; an Inactive agent becomes Active again. If the call
+        /// fails, or lowers the agent account's free balance beyond the operator's
+        /// remaining spending cap, the whole extrinsic fails and is reverted.
+        #[pallet::call_index(14)]
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            dispatch_info
+                .call_weight
+                .saturating_add(Weight::from_parts(10_000, 0))
+                .saturating_add(T::DbWeight::get().reads_writes(3, 1))
+        })]
+        pub fn operate(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            call: Box<<T as Config>::RuntimeCall>,
+        ) -> DispatchResult {
+            let operator = ensure_signed(origin)?;
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(
+                matches!(agent.status, AgentStatus::Active | AgentStatus::Inactive),
+                Error::<T>::AgentNotActive
+            );
+            ensure!(
+                !FrozenAgents::<T>::contains_key(agent_id),
+                Error::<T>::AgentFrozen
+            );
+
+            let mut info =
+                Operators::<T>::get(agent_id, &operator).ok_or(Error::<T>::NotOperator)?;
+            let (pallet_index, call_index) =
+                Self::call_indices(&call).ok_or(Error::<T>::CallNotPermitted)?;
+            ensure!(
+                info.scopes
+                    .iter()
+                    .any(|scope| scope.allows(pallet_index, call_index)),
+                Error::<T>::CallNotPermitted
+            );
+
+            let agent_account = Self::agent_account(agent_id);
+            let balance_before = T::Currency::free_balance(&agent_account);
+            call.dispatch(frame_system::RawOrigin::Signed(agent_account.clone()).into())
+                .map_err(|e| e.error)?;
+            let spent = balance_before.saturating_sub(T::Currency::free_balance(&agent_account));
+
+            if let Some(cap) = info.spending_cap {
+                let now = <frame_system::Pallet<T>>::block_number();
+                let period_end = info
+                    .period_start
+                    .saturating_add(T::OperatorSpendingPeriod::get());
+                if now >= period_end {
+                    info.period_start = now;
+                    info.spent = Zero::zero();
+                }
+                info.spent = info.spent.saturating_add(spent);
+                ensure!(info.spent <= cap, Error::<T>::SpendingCapExceeded);
+                Operators::<T>::insert(agent_id, &operator, info);
+            }
+
+            Self::mark_alive(agent_id);
+
+            Self::deposit_event(Event::OperatorCallExecuted { agent_id, operator });
+
+            Ok(())
+        }
+
+        /// Replace the key behind one of an agent's verification methods.
+        ///
+        /// Only the agent owner can modify the document. The method keeps its
+        /// fragment and verification relationships; the old key is revoked as of
+        /// the current block.
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(4, 3))]
+        pub fn rotate_verification_method(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            fragment: Vec<u8>,
+            key_type: DidKeyType,
+            public_key: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            let public_key = Self::unused_key(agent_id, key_type, public_key)?;
+
+            DidDocuments::<T>::try_mutate(agent_id, |doc| -> DispatchResult {
+                let method = doc
+                    .verification_methods
+                    .iter_mut()
+                    .find(|m| m.fragment.as_slice() == fragment.as_slice())
+                    .ok_or(Error::<T>::FragmentNotFound)?;
+                Self::revoke_key(agent_id, &method.public_key);
+                Self::record_key(agent_id, &public_key, &method.fragment, key_type);
+                method.key_type = key_type;
+                method.public_key = public_key;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationMethodRotated {
+                agent_id,
+                fragment,
+                key_type,
+            });
+
+            Ok(())
+        }
+
+        /// Freeze an agent after a key compromise.
+        ///
+        /// Only the agent owner can freeze. Until `unfreeze_agent`, operator keys
+        /// cannot act for the agent, it cannot be transferred, and `key_valid_at`
+        /// reports none of its keys valid from the current block on.
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn freeze_agent(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+            ensure!(
+                !FrozenAgents::<T>::contains_key(agent_id),
+                Error::<T>::AgentFrozen
+            );
+
+            FrozenAgents::<T>::insert(agent_id, <frame_system::Pallet<T>>::block_number());
+
+            Self::deposit_event(Event::AgentFrozen { agent_id });
+
+            Ok(())
+        }
+
+        /// Lift an agent's freeze.
+        ///
+        /// Only the agent owner can unfreeze. Keys not revoked during the freeze
+        /// become valid again.
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn unfreeze_agent(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+            FrozenAgents::<T>::take(agent_id).ok_or(Error::<T>::AgentNotFrozen)?;
+
+            Self::deposit_event(Event::AgentUnfrozen { agent_id });
+
+            Ok(())
+        }
+
+        /// Report an agent as alive.
+        ///
+        /// Callable by the agent owner or any of its operator keys. Refreshes
+        /// `last_heartbeat` and turns an Inactive agent Active again. Operator keys
+        /// cannot heartbeat for a frozen agent.
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(5_000, 0) + T::DbWeight::get().reads_writes(3, 1))]
+        pub fn heartbeat(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_may_heartbeat(agent_id, &who)?;
+
+            Self::mark_alive(agent_id);
+
+            Ok(())
+        }
+
+        /// Report an agent as alive without paying a fee.
+        ///
+        /// Submitted unsigned with a `HeartbeatPayload` signed by the agent owner or
+        /// one of its operator keys, under the same rules as `heartbeat`. The
+        /// signature is checked when the transaction is validated. A payload must be
+        /// signed after the agent's last heartbeat, so it cannot be replayed, and at
+        /// least `MinHeartbeatInterval` blocks after it.
+        #[pallet::call_index(30)]
+        #[pallet::weight(Weight::from_parts(5_000, 0) + T::DbWeight::get().reads_writes(3, 1))]
+        pub fn heartbeat_unsigned(
+            origin: OriginFor<T>,
+            payload: HeartbeatPayloadOf<T>,
+            _signature: T::HeartbeatSignature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            Self::ensure_fresh_heartbeat(&payload)?;
+
+            Self::mark_alive(payload.agent_id);
+
+            Ok(())
+        }
+
+        /// Replace an agent's capability declarations.
+        ///
+        /// Only the agent owner can declare capabilities. Each capability ID may
+        /// appear once; `CapabilityIndex` is updated to match.
+        #[pallet::call_index(19)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(2, 1)
+                + T::DbWeight::get()
+                    .writes(2u64.saturating_mul(T::MaxCapabilitiesPerAgent::get().into()))
+        )]
+        pub fn set_capabilities(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            capabilities: BoundedVec<Capability<T>, T::MaxCapabilitiesPerAgent>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_owner(&who, agent_id)?;
+
+            for (i, capability) in capabilities.iter().enumerate() {
+                ensure!(
+                    Self::is_valid_capability_id(&capability.id),
+                    Error::<T>::InvalidCapabilityId
+                );
+                ensure!(
+                    capabilities[..i].iter().all(|c| c.id != capability.id),
+                    Error::<T>::DuplicateCapability
+                );
+            }
+
+            Self::clear_capabilities(agent_id);
+            for capability in capabilities.iter() {
+                CapabilityIndex::<T>::insert(&capability.id, agent_id, ());
+            }
+            let count = capabilities.len() as u32;
+            if !capabilities.is_empty() {
+                AgentCapabilities::<T>::insert(agent_id, capabilities);
+            }
+
+            Self::deposit_event(Event::CapabilitiesUpdated { agent_id, count });
+
+            Ok(())
+        }
+
+        /// Suspend an agent on behalf of governance.
+        ///
+        /// Only `AdminOrigin` can suspend. The owner cannot clear the suspension;
+        /// it ends with `lift_suspension` or at `expires_at`, if given. Suspending
+        /// an already suspended agent replaces the reason and expiry.
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 2))]
+        pub fn suspend_agent(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            reason: Vec<u8>,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                expires_at.is_none_or(|expires_at| expires_at > now),
+                Error::<T>::InvalidExpiry
+            );
+            let bounded_reason: BoundedVec<u8, T::MaxSuspensionReasonLength> = reason
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::SuspensionReasonTooLong)?;
+
+            AgentRegistry::<T>::try_mutate(agent_id, |maybe_agent| -> DispatchResult {
+                let agent = maybe_agent.as_mut().ok_or(Error::<T>::AgentNotFound)?;
+                ensure!(
+                    agent.status != AgentStatus::Deregistered,
+                    Error::<T>::AgentAlreadyDeregistered
+                );
+                agent.status = AgentStatus::Suspended;
+                Ok(())
+            })?;
+
+            Suspensions::<T>::insert(
+                agent_id,
+                SuspensionInfo {
+                    reason: bounded_reason,
+                    imposed_at: now,
+                    expires_at,
+                },
+            );
+
+            Self::deposit_event(Event::AgentSuspended {
+                agent_id,
+                reason,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Lift an admin suspension, making the agent Active again.
+        ///
+        /// Only `AdminOrigin` can lift a suspension.
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        pub fn lift_suspension(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Suspensions::<T>::take(agent_id).ok_or(Error::<T>::NotSuspended)?;
+
+            AgentRegistry::<T>::mutate(agent_id, |maybe_agent| {
+                if let Some(agent) = maybe_agent {
+                    agent.status = AgentStatus::Active;
+                    agent.last_active = <frame_system::Pallet<T>>::block_number();
+                }
+            });
+
+            Self::deposit_event(Event::SuspensionLifted { agent_id });
+
+            Ok(())
+        }
+
+        /// Remove a deregistered agent from storage, leaving a tombstone.
+        ///
+        /// The owner can purge as soon as the agent is deregistered; anyone can
+        /// once `PurgeGracePeriod` blocks have passed since deregistration. Any
+        /// deposit still held is returned to the owner. A DID still bound to the
+        /// agent is retired rather than released.
+        #[pallet::call_index(22)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(4, 8)
+                + T::DbWeight::get().writes(T::MaxAttestationsPerAgent::get().into())
+        )]
+        pub fn purge_agent(origin: OriginFor<T>, agent_id: AgentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(
+                agent.status == AgentStatus::Deregistered,
+                Error::<T>::AgentNotDeregistered
+            );
+
+            // `last_active` is frozen at the deregistration block.
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                who == agent.owner
+                    || now >= agent.last_active.saturating_add(T::PurgeGracePeriod::get()),
+                Error::<T>::PurgeTooEarly
+            );
+
+            AgentRegistry::<T>::remove(agent_id);
+            OwnerAgents::<T>::mutate(&agent.owner, |agents| agents.retain(|id| *id != agent_id));
+            DidDocuments::<T>::remove(agent_id);
+            let _ =
+                Attestations::<T>::clear_prefix(agent_id, T::MaxAttestationsPerAgent::get(), None);
+            AttestationCount::<T>::remove(agent_id);
+            ReputationAdjustments::<T>::remove(agent_id);
+            T::Currency::unreserve(&agent.owner, AgentDeposits::<T>::take(agent_id));
+            if DidToAgent::<T>::get(&agent.did) == Some(agent_id) {
+                DidReleaseAt::<T>::remove(&agent.did);
+            }
+
+            AgentTombstones::<T>::insert(
+                agent_id,
+                AgentTombstone {
+                    owner: agent.owner.clone(),
+                    did: agent.did,
+                    deregistered_at: agent.last_active,
+                    purged_at: now,
+                },
+            );
+
+            Self::deposit_event(Event::AgentPurged {
+                agent_id,
+                owner: agent.owner,
+            });
+
+            Ok(())
+        }
+
+        /// Set or clear an agent's ERC-8004 domain (`agentDomain`).
+        ///
+        /// Callable by the agent owner or the agent account. The domain must be a
+        /// lowercase DNS name not held by another agent; it is released when the
+        /// agent is deregistered.
+        #[pallet::call_index(23)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 3))]
+        pub fn set_agent_domain(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            domain: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_agent_controller(&who, agent_id)?;
+
+            let bounded = domain
+                .as_ref()
+                .map(|domain| -> Result<_, DispatchError> {
+                    ensure!(Self::is_valid_domain(domain), Error::<T>::InvalidDomain);
+                    let bounded: BoundedVec<u8, T::MaxDomainLength> =
+                        domain.clone().try_into().map_err(|_| Error::<T>::InvalidDomain)?;
+                    ensure!(
+                        DomainToAgent::<T>::get(&bounded).is_none_or(|holder| holder == agent_id),
+                        Error::<T>::DomainAlreadyRegistered
+                    );
+                    Ok(bounded)
+                })
+                .transpose()?;
+
+            Self::clear_domain(agent_id);
+            if let Some(bounded) = bounded {
+                DomainToAgent::<T>::insert(&bounded, agent_id);
+                AgentDomains::<T>::insert(agent_id, bounded);
+            }
+
+            Self::deposit_event(Event::AgentDomainSet { agent_id, domain });
+
+            Ok(())
+        }
+
+        /// Register `issuer` as an attestation issuer.
+        ///
+        /// Only `IssuerOrigin` can register issuers.
+        #[pallet::call_index(26)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        pub fn register_issuer(
+            origin: OriginFor<T>,
+            issuer: T::AccountId,
+            name: Vec<u8>,
+        ) -> DispatchResult {
+            T::IssuerOrigin::ensure_origin(origin)?;
+            ensure!(
+                !Issuers::<T>::contains_key(&issuer),
+                Error::<T>::IssuerAlreadyRegistered
+            );
+            let name: BoundedVec<u8, T::MaxIssuerNameLength> =
+                name.try_into().map_err(|_| Error::<T>::IssuerNameTooLong)?;
+
+            Issuers::<T>::insert(
+                &issuer,
+                IssuerInfo {
+                    name,
+                    registered_at: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::IssuerRegistered { issuer });
+
+            Ok(())
+        }
+
+        /// Remove an attestation issuer. Its claims stay on-chain but are no
+        /// longer valid.
+        ///
+        /// Only `IssuerOrigin` can remove issuers.
+        #[pallet::call_index(27)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        pub fn remove_issuer(origin: OriginFor<T>, issuer: T::AccountId) -> DispatchResult {
+            T::IssuerOrigin::ensure_origin(origin)?;
+            Issuers::<T>::take(&issuer).ok_or(Error::<T>::NotIssuer)?;
+
+            Self::deposit_event(Event::IssuerRemoved { issuer });
+
+            Ok(())
+        }
+
+        /// Attest a claim of `schema` about `agent_id`, replacing the caller's
+        /// earlier claim of that schema.
+        ///
+        /// Only registered issuers can attest, and not about agents they control.
+        ///
+        /// # Arguments
+        /// * `agent_id` - The agent the claim is about
+        /// * `schema` - Hash of the claim's schema definition
+        /// * `claim_hash` - Hash of the off-chain claim document
+        /// * `expires_at` - Block from which the claim is no longer valid, if any
+        #[pallet::call_index(28)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(4, 2))]
+        pub fn attest(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            schema: SchemaId,
+            claim_hash: DataHash,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            ensure!(Issuers::<T>::contains_key(&issuer), Error::<T>::NotIssuer);
+
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(
+                agent.status != AgentStatus::Deregistered,
+                Error::<T>::AgentAlreadyDeregistered
+            );
+            ensure!(
+                agent.owner != issuer && issuer != Self::agent_account(agent_id),
+                Error::<T>::SelfAttestation
+            );
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                expires_at.is_none_or(|expires_at| expires_at > now),
+                Error::<T>::InvalidExpiry
+            );
+
+            let key = (issuer.clone(), schema);
+            if !Attestations::<T>::contains_key(agent_id, &key) {
+                AttestationCount::<T>::try_mutate(agent_id, |count| -> DispatchResult {
+                    ensure!(
+                        *count < T::MaxAttestationsPerAgent::get(),
+                        Error::<T>::TooManyAttestations
+                    );
+                    *count = count.saturating_add(1);
+                    Ok(())
+                })?;
+            }
+            Attestations::<T>::insert(
+                agent_id,
+                key,
+                Attestation {
+                    claim_hash,
+                    issued_at: now,
+                    expires_at,
+                    revoked_at: None,
+                },
+            );
+
+            Self::deposit_event(Event::AttestationIssued {
+                agent_id,
+                issuer,
+                schema,
+                claim_hash,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke the caller's claim of `schema` about `agent_id`.
+        ///
+        /// The claim stays on-chain, marked revoked. Issuers can revoke their
+        /// claims even after being removed.
+        #[pallet::call_index(29)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        pub fn revoke_attestation(
+            origin: OriginFor<T>,
+            agent_id: AgentId,
+            schema: SchemaId,
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+
+            Attestations::<T>::try_mutate(agent_id, (&issuer, schema), |maybe_attestation| {
+                let attestation = maybe_attestation
+                    .as_mut()
+                    .ok_or(Error::<T>::AttestationNotFound)?;
+                ensure!(
+                    attestation.revoked_at.is_none(),
+                    Error::<T>::AttestationAlreadyRevoked
+                );
+                attestation.revoked_at = Some(<frame_system::Pallet<T>>::block_number());
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::AttestationRevoked {
+                agent_id,
+                issuer,
+                schema,
+            });
+
+            Ok(())
+        }
+    }
+
+    // ========== Internal Functions ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Check `did` against the W3C DID syntax and the method allow-list.
+        ///
+        /// Accepts `did:<method>:<method-specific-id>` where the method is one or
+        /// more lowercase letters or digits, and the method-specific ID is one or
+        /// more colon-separated segments of `A-Z a-z 0-9 . - _` or `%XX` escapes.
+        /// Only the final segment must be non-empty.
+        pub fn validate_did(did: &[u8]) -> DispatchResult {
+            let rest = did.strip_prefix(b"did:").ok_or(Error::<T>::InvalidDid)?;
+            let sep = rest
+                .iter()
+                .position(|b| *b == b':')
+                .ok_or(Error::<T>::InvalidDid)?;
+            let (method, id) = (&rest[..sep], &rest[sep + 1..]);
+
+            ensure!(
+                !method.is_empty()
+                    && method
+                        .iter()
+                        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()),
+                Error::<T>::InvalidDid
+            );
+            ensure!(
+                Self::is_valid_method_specific_id(id),
+                Error::<T>::InvalidDid
+            );
+
+            let allowed = T::AllowedDidMethods::get();
+            ensure!(
+                allowed.is_empty() || allowed.iter().any(|m| m.as_slice() == method),
+                Error::<T>::DidMethodNotAllowed
+            );
+
+            Ok(())
+        }
+
+        fn is_valid_method_specific_id(id: &[u8]) -> bool {
+            if id.last().is_none_or(|b| *b == b':') {
+                return false;
+            }
+            let mut i = 0;
+            while i < id.len() {
+                match id[i] {
+                    b'%' => {
+                        let escaped = id.get(i + 1..i + 3);
+                        if !escaped.is_some_and(|h| h.iter().all(u8::is_ascii_hexdigit)) {
+                            return false;
+                        }
+                        i += 3;
+                    }
+                    b if b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_' | b':') => {
+                        i += 1;
+                    }
+                    _ => return false,
+                }
+            }
+            true
+        }
+
+        /// The keyless account that operator keys of `agent_id` act as.
+        pub fn agent_account(agent_id: AgentId) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(agent_id)
+        }
+
+        /// Validate a new verification key for `agent_id`: it must match the length
+        /// of `key_type` and never have been bound to the agent before.
+        fn unused_key(
+            agent_id: AgentId,
+            key_type: DidKeyType,
+            public_key: Vec<u8>,
+        ) -> Result<BoundedVec<u8, MaxPublicKeyLength>, DispatchError> {
+            ensure!(
+                public_key.len() == key_type.key_length(),
+                Error::<T>::InvalidPublicKey
+            );
+            let public_key: BoundedVec<u8, MaxPublicKeyLength> = public_key
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidPublicKey)?;
+            ensure!(
+                !KeyHistory::<T>::contains_key(agent_id, &public_key),
+                Error::<T>::KeyAlreadyUsed
+            );
+            Ok(public_key)
+        }
+
+        /// Record `public_key` in the agent's key history as added now.
+        fn record_key(
+            agent_id: AgentId,
+            public_key: &BoundedVec<u8, MaxPublicKeyLength>,
+            fragment: &BoundedVec<u8, T::MaxFragmentLength>,
+            key_type: DidKeyType,
+        ) {
+            KeyHistory::<T>::insert(
+                agent_id,
+                public_key,
+                KeyRecord {
+                    fragment: fragment.clone(),
+                    key_type,
+                    added_at: <frame_system::Pallet<T>>::block_number(),
+                    revoked_at: None,
+                },
+            );
+        }
+
+        /// Mark `public_key` revoked as of now in the agent's key history.
+        fn revoke_key(agent_id: AgentId, public_key: &BoundedVec<u8, MaxPublicKeyLength>) {
+            KeyHistory::<T>::mutate(agent_id, public_key, |maybe_record| {
+                if let Some(record) = maybe_record {
+                    record.revoked_at = Some(<frame_system::Pallet<T>>::block_number());
+                }
+            });
+        }
+
+        /// Check that `who` may report `agent_id` alive: the agent is Active or
+        /// Inactive, and `who` is its owner or an operator key of an unfrozen agent.
+        fn ensure_may_heartbeat(
+            agent_id: AgentId,
+            who: &T::AccountId,
+        ) -> Result<AgentInfo<T>, DispatchError> {
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(
+                matches!(agent.status, AgentStatus::Active | AgentStatus::Inactive),
+                Error::<T>::AgentNotActive
+            );
+            if agent.owner != *who {
+                ensure!(
+                    Operators::<T>::contains_key(agent_id, who),
+                    Error::<T>::NotOperator
+                );
+                ensure!(
+                    !FrozenAgents::<T>::contains_key(agent_id),
+                    Error::<T>::AgentFrozen
+                );
+            }
+            Ok(agent)
+        }
+
+        /// Check a `heartbeat_unsigned` payload against the agent's current state;
+        /// the signature is checked separately.
+        fn ensure_fresh_heartbeat(payload: &HeartbeatPayloadOf<T>) -> DispatchResult {
+            let agent = Self::ensure_may_heartbeat(payload.agent_id, &payload.signer)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                payload.block_number > agent.last_heartbeat && payload.block_number <= now,
+                Error::<T>::StaleHeartbeat
+            );
+            ensure!(
+                payload.block_number
+                    >= agent
+                        .last_heartbeat
+                        .saturating_add(T::MinHeartbeatInterval::get()),
+                Error::<T>::HeartbeatTooFrequent
+            );
+            Ok(())
+        }
+
+        /// Refresh an agent's `last_heartbeat` and `last_active`, turning it Active
+        /// if it was Inactive.
+        fn mark_alive(agent_id: AgentId) {
+            AgentRegistry::<T>::mutate(agent_id, |maybe_agent| {
+                let Some(agent) = maybe_agent else { return };
+                let now = <frame_system::Pallet<T>>::block_number();
+                agent.last_active = now;
+                agent.last_heartbeat = now;
+                if agent.status == AgentStatus::Inactive {
+                    agent.status = AgentStatus::Active;
+                    Self::deposit_event(Event::AgentStatusChanged {
+                        agent_id,
+                        status: AgentStatus::Active,
+                    });
+                }
+            });
+        }
+
+        /// Mark Active agents silent for `InactivityPeriod` as Inactive and lift
+        /// expired admin suspensions, resuming
+        /// from `InactivitySweepCursor`, within `remaining_weight` and at most
+        /// `MaxInactivitySweepPerBlock` agents per call.
+        pub(crate) fn sweep_inactive_agents(
+            now: BlockNumberFor<T>,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let mut used = T::DbWeight::get().reads_writes(2, 1);
+            let count = AgentCount::<T>::get();
+            if remaining_weight.any_lt(used) || count == 0 {
+                return Weight::zero();
+            }
+
+            let per_agent = T::DbWeight::get().reads_writes(2, 2);
+            let period = T::InactivityPeriod::get();
+            let mut cursor = InactivitySweepCursor::<T>::get();
+            let mut checked = 0u32;
+
+            while checked < T::MaxInactivitySweepPerBlock::get()
+                && u64::from(checked) < count
+                && !remaining_weight.any_lt(used.saturating_add(per_agent))
+            {
+                if cursor >= count {
+                    cursor = 0;
+                }
+                AgentRegistry::<T>::mutate(cursor, |maybe_agent| {
+                    let Some(agent) = maybe_agent else { return };
+                    match agent.status {
+                        AgentStatus::Active
+                            if now >= agent.last_heartbeat.saturating_add(period) =>
+                        {
+                            agent.status = AgentStatus::Inactive;
+                        }
+                        AgentStatus::Suspended
+                            if Suspensions::<T>::get(cursor)
+                                .is_some_and(|suspension| !suspension.is_in_force(now)) =>
+                        {
+                            Suspensions::<T>::remove(cursor);
+                            Self::deposit_event(Event::SuspensionLifted { agent_id: cursor });
+                            agent.status = AgentStatus::Active;
+                            agent.last_active = now;
+                        }
+                        _ => return,
+                    }
+                    Self::deposit_event(Event::AgentStatusChanged {
+                        agent_id: cursor,
+                        status: agent.status.clone(),
+                    });
+                });
+                used = used.saturating_add(per_agent);
+                cursor = cursor.saturating_add(1);
+                checked = checked.saturating_add(1);
+            }
+
+            InactivitySweepCursor::<T>::put(cursor);
+            used
+        }
+
+        /// The pallet index and call index of `call`, decoded from the front of its
+        /// SCALE encoding, where a runtime call starts with the `construct_runtime!`
+        /// index of its pallet followed by the `call_index` of the call.
+        fn call_indices(call: &<T as Config>::RuntimeCall) -> Option<(u8, u8)> {
+            call.using_encoded(|bytes| <(u8, u8)>::decode(&mut &bytes[..]).ok())
+        }
+
+        /// Whether `id` is a well-formed capability ID.
+        fn is_valid_capability_id(id: &[u8]) -> bool {
+            !id.is_empty()
+                && id.iter().all(|b| {
+                    b.is_ascii_lowercase()
+                        || b.is_ascii_digit()
+                        || matches!(b, b'.' | b'-' | b'_' | b'/')
+                })
+        }
+
+        /// Drop every capability of `agent_id` and its index entries.
+        fn clear_capabilities(agent_id: AgentId) {
+            for capability in AgentCapabilities::<T>::take(agent_id) {
+                CapabilityIndex::<T>::remove(&capability.id, agent_id);
+            }
+        }
+
+        /// Unbind every operator key of `agent_id`.
+        fn clear_operators(agent_id: AgentId) {
+            let _ = Operators::<T>::clear_prefix(agent_id, T::MaxOperatorsPerAgent::get(), None);
+            OperatorCount::<T>::remove(agent_id);
+        }
+
+        /// Revoke every verification method of `agent_id` and remove its services.
+        fn clear_did_document(agent_id: AgentId) {
+            let document = DidDocuments::<T>::take(agent_id);
+            for method in document.verification_methods {
+                Self::revoke_key(agent_id, &method.public_key);
+            }
+        }
+
+        /// Registration deposit for an agent whose DID and metadata take `bytes` bytes.
+        pub fn agent_deposit(bytes: usize) -> BalanceOf<T> {
+            let bytes: u32 = bytes.try_into().unwrap_or(u32::MAX);
+            T::AgentDepositBase::get()
+                .saturating_add(T::AgentDepositPerByte::get().saturating_mul(bytes.into()))
+        }
+
+        /// Reserve or release from `owner` so that `agent_id`'s deposit equals `new_deposit`.
+        fn set_deposit(
+            agent_id: AgentId,
+            owner: &T::AccountId,
+            new_deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let old_deposit = AgentDeposits::<T>::get(agent_id);
+            if new_deposit > old_deposit {
+                T::Currency::reserve(owner, new_deposit.saturating_sub(old_deposit))
+                    .map_err(|_| Error::<T>::InsufficientDeposit)?;
+            } else {
+                T::Currency::unreserve(owner, old_deposit.saturating_sub(new_deposit));
+            }
+
+            if new_deposit.is_zero() {
+                AgentDeposits::<T>::remove(agent_id);
+            } else {
+                AgentDeposits::<T>::insert(agent_id, new_deposit);
+            }
+            Ok(())
+        }
+
+        fn ensure_agent_owner(who: &T::AccountId, agent_id: AgentId) -> DispatchResult {
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(&agent.owner == who, Error::<T>::NotAgentOwner);
+            ensure!(
+                agent.status != AgentStatus::Deregistered,
+                Error::<T>::AgentAlreadyDeregistered
+            );
+            Ok(())
+        }
+
+        /// Ensure `who` may act as `agent_id`: it is the agent's owner or agent
+        /// account, and the agent is not deregistered.
+        fn ensure_agent_controller(who: &T::AccountId, agent_id: AgentId) -> DispatchResult {
+            let agent = AgentRegistry::<T>::get(agent_id).ok_or(Error::<T>::AgentNotFound)?;
+            ensure!(
+                &agent.owner == who || *who == Self::agent_account(agent_id),
+                Error::<T>::NotAgentController
+            );
+            ensure!(
+                agent.status != AgentStatus::Deregistered,
+                Error::<T>::AgentAlreadyDeregistered
+            );
+            Ok(())
+        }
+
+        /// Whether `domain` is a lowercase DNS name: dot-separated, non-empty
+        /// labels of `a-z 0-9 -` that neither start nor end with a hyphen.
+        fn is_valid_domain(domain: &[u8]) -> bool {
+            !domain.is_empty()
+                && domain.split(|b| *b == b'.').all(|label| {
+                    !label.is_empty()
+                        && label.first() != Some(&b'-')
+                        && label.last() != Some(&b'-')
+                        && label
+                            .iter()
+                            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
+                })
+        }
+
+        /// Release the domain of `agent_id`, if any.
+        fn clear_domain(agent_id: AgentId) {
+            if let Some(domain) = AgentDomains::<T>::take(agent_id) {
+                DomainToAgent::<T>::remove(domain);
+            }
+        }
+
+        fn bounded_fragment(
+            fragment: &[u8],
+        ) -> Result<BoundedVec<u8, T::MaxFragmentLength>, DispatchError> {
+            ensure!(
+                !fragment.is_empty()
+                    && fragment
+                        .iter()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_')),
+                Error::<T>::InvalidFragment
+            );
+            Ok(BoundedVec::try_from(fragment.to_vec()).map_err(|_| Error::<T>::InvalidFragment)?)
+        }
+
+        /// Bind `did` to `agent_id`, reclaiming it from a deregistered agent whose
+        /// cooldown has elapsed. A DID bound to a purged agent is never reclaimed.
+        fn claim_did(
+            did: &BoundedVec<u8, T::MaxDidLength>,
+            agent_id: AgentId,
+            now: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            if let Some(holder) = DidToAgent::<T>::get(did) {
+                ensure!(
+                    !AgentTombstones::<T>::contains_key(holder),
+                    Error::<T>::DidRetired
+                );
+                let release_at =
+                    DidReleaseAt::<T>::get(did).ok_or(Error::<T>::DidAlreadyRegistered)?;
+                ensure!(now >= release_at, Error::<T>::DidInCooldown);
+                DidReleaseAt::<T>::remove(did);
+            }
+            DidToAgent::<T>::insert(did, agent_id);
+            Ok(())
+        }
+    }
+
+    // ========== Reputation ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Current reputation of `agent_id`, if it is registered.
+        pub fn agent_reputation(agent_id: AgentId) -> Option<u32> {
+            AgentRegistry::<T>::get(agent_id).map(|agent| Self::reputation_of(agent_id, &agent))
+        }
+
+        /// Reputation of `agent`: the lower of its owner's and its agent account's
+        /// scores, plus its governance adjustment, clamped to 0-10000.
+        fn reputation_of(agent_id: AgentId, agent: &AgentInfo<T>) -> u32 {
+            let earned = T::Reputation::score(&agent.owner)
+                .min(T::Reputation::score(&Self::agent_account(agent_id)));
+            let adjusted =
+                i64::from(earned).saturating_add(ReputationAdjustments::<T>::get(agent_id).into());
+            adjusted.clamp(0, MAX_REPUTATION.into()) as u32
+        }
+    }
+
+    // ========== Agent Standing ==========
+
+    impl<T: Config> AgentStanding<T::AccountId> for Pallet<T> {
+        fn is_agent(account: &T::AccountId) -> bool {
+            Self::status_of_agent_account(account)
+                .is_some_and(|status| status != AgentStatus::Deregistered)
+        }
+
+        fn is_inactive_agent(account: &T::AccountId) -> bool {
+            Self::status_of_agent_account(account).is_some_and(|status| {
+                matches!(status, AgentStatus::Inactive | AgentStatus::Deregistered)
+            })
+        }
+
+        fn is_suspended_agent(account: &T::AccountId) -> bool {
+            Self::status_of_agent_account(account) == Some(AgentStatus::Suspended)
+                || OwnerAgents::<T>::get(account)
+                    .iter()
+                    .any(Suspensions::<T>::contains_key)
+        }
+
+        fn has_capability(account: &T::AccountId, capability: &[u8], min_version: u32) -> bool {
+            let declares = |agent_id: &AgentId| {
+                AgentCapabilities::<T>::get(agent_id)
+                    .iter()
+                    .any(|c| c.id.as_slice() == capability && c.version >= min_version)
+            };
+            Self::agent_id_of_account(account).is_some_and(|agent_id| declares(&agent_id))
+                || OwnerAgents::<T>::get(account).iter().any(declares)
+        }
+    }
+
+    impl<T: Config> AgentDirectory<T::AccountId> for Pallet<T> {
+        fn is_registered(agent_id: AgentId) -> bool {
+            AgentRegistry::<T>::get(agent_id)
+                .is_some_and(|agent| agent.status != AgentStatus::Deregistered)
+        }
+
+        fn is_controller(account: &T::AccountId, agent_id: AgentId) -> bool {
+            Self::ensure_agent_controller(account, agent_id).is_ok()
+        }
+
+        fn controllers(agent_id: AgentId) -> Vec<T::AccountId> {
+            match AgentRegistry::<T>::get(agent_id) {
+                Some(agent) if agent.status != AgentStatus::Deregistered => {
+                    alloc::vec![agent.owner, Self::agent_account(agent_id)]
+                }
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Status of the agent whose agent account is `account`, if any.
+        fn status_of_agent_account(account: &T::AccountId) -> Option<AgentStatus> {
+            let agent_id = Self::agent_id_of_account(account)?;
+            AgentRegistry::<T>::get(agent_id).map(|agent| agent.status)
+        }
+
+        /// The agent ID `account` is the agent account of, if it is one.
+        fn agent_id_of_account(account: &T::AccountId) -> Option<AgentId> {
+            let (pallet_id, agent_id) = PalletId::try_from_sub_account::<AgentId>(account)?;
+            (pallet_id == T::PalletId::get()).then_some(agent_id)
+        }
+    }
+
+    // ========== Runtime API Helpers ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Look up an agent by ID.
+        pub fn agent(agent_id: AgentId) -> Option<AgentSummaryOf<T>> {
+            AgentRegistry::<T>::get(agent_id).map(|agent| Self::summarize(agent_id, agent))
+        }
+
+        /// Resolve an agent from its DID.
+        pub fn agent_by_did(did: &[u8]) -> Option<AgentSummaryOf<T>> {
+            let did: BoundedVec<u8, T::MaxDidLength> = did.to_vec().try_into().ok()?;
+            DidToAgent::<T>::get(did).and_then(Self::agent)
+        }
+
+        /// All agents owned by `owner`.
+        pub fn agents_by_owner(owner: &T::AccountId) -> Vec<AgentSummaryOf<T>> {
+            OwnerAgents::<T>::get(owner)
+                .into_iter()
+                .filter_map(Self::agent)
+                .collect()
+        }
+
+        /// Agents with the given status (or any status), scanning at most
+        /// `MAX_SCANNED_AGENTS` agent IDs from `start` and returning at most `limit`.
+        pub fn list_agents(
+            status: Option<AgentStatus>,
+            start: AgentId,
+            limit: u32,
+        ) -> AgentPageOf<T> {
+            Self::scan_agents(start, limit, |_, agent| {
+                status.as_ref().is_none_or(|s| &agent.status == s)
+            })
+        }
+
+        /// The DID Core JSON document for `did`, if it resolves to an agent.
+        pub fn did_document(did: &[u8]) -> Option<Vec<u8>> {
+            let bounded: BoundedVec<u8, T::MaxDidLength> = did.to_vec().try_into().ok()?;
+            let agent_id = DidToAgent::<T>::get(bounded)?;
+            if !AgentRegistry::<T>::contains_key(agent_id) {
+                return None;
+            }
+            let document = DidDocuments::<T>::get(agent_id);
+            Some(did::render_document::<T>(did, &document))
+        }
+
+        /// Capabilities declared by `agent_id`.
+        pub fn agent_capabilities(agent_id: AgentId) -> Vec<CapabilitySummary<BalanceOf<T>>> {
+            AgentCapabilities::<T>::get(agent_id)
+                .into_iter()
+                .map(|capability| CapabilitySummary {
+                    id: capability.id.into_inner(),
+                    version: capability.version,
+                    price_hint: capability.price_hint,
+                    rate_limit: capability.rate_limit,
+                })
+                .collect()
+        }
+
+        /// Active agents declaring `capability` at `min_version` or above, scanning at
+        /// most `MAX_SCANNED_AGENTS` agent IDs from `start` and returning at most `limit`.
+        pub fn agents_with_capability(
+            capability: &[u8],
+            min_version: u32,
+            start: AgentId,
+            limit: u32,
+        ) -> AgentPageOf<T> {
+            let Ok(id) = BoundedVec::<u8, T::MaxCapabilityIdLength>::try_from(capability.to_vec())
+            else {
+                return AgentPage {
+                    agents: Vec::new(),
+                    next: None,
+                };
+            };
+            Self::scan_agents(start, limit, |agent_id, agent| {
+                agent.status == AgentStatus::Active
+                    && CapabilityIndex::<T>::contains_key(&id, agent_id)
+                    && AgentCapabilities::<T>::get(agent_id)
+                        .iter()
+                        .any(|c| c.id == id && c.version >= min_version)
+            })
+        }
+
+        /// Agents passing `filter`, at most `limit`, scanning at most
+        /// `MAX_SCANNED_AGENTS` agent IDs from `start` so a listing stays bounded
+        /// however many agents have registered.
+        fn scan_agents(
+            start: AgentId,
+            limit: u32,
+            filter: impl Fn(AgentId, &AgentInfo<T>) -> bool,
+        ) -> AgentPageOf<T> {
+            let count = AgentCount::<T>::get();
+            let end = count.min(start.saturating_add(MAX_SCANNED_AGENTS));
+            let mut agents = Vec::new();
+            let mut agent_id = start;
+            while agent_id < end && agents.len() < limit as usize {
+                if let Some(agent) =
+                    AgentRegistry::<T>::get(agent_id).filter(|agent| filter(agent_id, agent))
+                {
+                    agents.push(Self::summarize(agent_id, agent));
+                }
+                agent_id = agent_id.saturating_add(1);
+            }
+            AgentPage {
+                agents,
+                next: (agent_id < count).then_some(agent_id),
+            }
+        }
+
+        /// Whether `public_key` was a valid verification key of `agent_id` at
+        /// block `at`: added at or before `at`, not revoked by then, and not
+        /// covered by a current freeze.
+        pub fn key_valid_at(agent_id: AgentId, public_key: &[u8], at: BlockNumberFor<T>) -> bool {
+            let Ok(public_key) =
+                BoundedVec::<u8, MaxPublicKeyLength>::try_from(public_key.to_vec())
+            else {
+                return false;
+            };
+            let Some(record) = KeyHistory::<T>::get(agent_id, public_key) else {
+                return false;
+            };
+            record.added_at <= at
+                && record.revoked_at.is_none_or(|revoked_at| at < revoked_at)
+                && FrozenAgents::<T>::get(agent_id).is_none_or(|frozen_at| at < frozen_at)
+        }
+
+        /// Resolve an agent from its ERC-8004 domain.
+        pub fn agent_by_domain(domain: &[u8]) -> Option<AgentSummaryOf<T>> {
+            let domain: BoundedVec<u8, T::MaxDomainLength> = domain.to_vec().try_into().ok()?;
+            DomainToAgent::<T>::get(domain).and_then(Self::agent)
+        }
+
+        /// Resolve an agent from its agent account (the ERC-8004 `agentAddress`).
+        pub fn agent_by_address(account: &T::AccountId) -> Option<AgentSummaryOf<T>> {
+            Self::agent_id_of_account(account).and_then(Self::agent)
+        }
+
+        /// Whether `agent_id` holds a valid claim of `schema` from `issuer`: not
+        /// revoked or expired, from a registered issuer, about an agent that is
+        /// not deregistered.
+        pub fn has_valid_attestation(
+            agent_id: AgentId,
+            schema: SchemaId,
+            issuer: &T::AccountId,
+        ) -> bool {
+            Attestations::<T>::get(agent_id, (issuer, schema)).is_some_and(|attestation| {
+                Self::is_valid_attestation(agent_id, issuer, &attestation)
+            })
+        }
+
+        /// Claims attested about `agent_id`, valid or not.
+        pub fn attestations(
+            agent_id: AgentId,
+        ) -> Vec<AttestationSummary<T::AccountId, BlockNumberFor<T>>> {
+            Attestations::<T>::iter_prefix(agent_id)
+                .map(|((issuer, schema), attestation)| AttestationSummary {
+                    valid: Self::is_valid_attestation(agent_id, &issuer, &attestation),
+                    issuer,
+                    schema,
+                    claim_hash: attestation.claim_hash,
+                    issued_at: attestation.issued_at,
+                    expires_at: attestation.expires_at,
+                    revoked_at: attestation.revoked_at,
+                })
+                .collect()
+        }
+
+        fn is_valid_attestation(
+            agent_id: AgentId,
+            issuer: &T::AccountId,
+            attestation: &Attestation<T>,
+        ) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            attestation.revoked_at.is_none()
+                && attestation
+                    .expires_at
+                    .is_none_or(|expires_at| now < expires_at)
+                && Issuers::<T>::contains_key(issuer)
+                && AgentRegistry::<T>::get(agent_id)
+                    .is_some_and(|agent| agent.status != AgentStatus::Deregistered)
+        }
+
+        fn summarize(agent_id: AgentId, agent: AgentInfo<T>) -> AgentSummaryOf<T> {
+            let reputation = Self::reputation_of(agent_id, &agent);
+            AgentSummary {
+                agent_id,
+                owner: agent.owner,
+                agent_account: Self::agent_account(agent_id),
+                did: agent.did.into_inner(),
+                domain: AgentDomains::<T>::get(agent_id).map(BoundedVec::into_inner),
+                metadata: agent.metadata.into_inner(),
+                reputation,
+                registered_at: agent.registered_at,
+                last_active: agent.last_active,
+                last_heartbeat: agent.last_heartbeat,
+                status: agent.status,
+            }
+        }
+    }
+
+    // ========== Weight Info Trait ==========
+
+    /// Weight information for the pallet's extrinsics.
+    pub trait WeightInfo {
+        fn register_agent() -> Weight;
+        fn update_metadata() -> Weight;
+        fn update_reputation() -> Weight;
+        fn deregister_agent() -> Weight;
+        fn set_agent_status() -> Weight;
+        fn add_verification_method() -> Weight;
+        fn revoke_verification_method() -> Weight;
+        fn add_service() -> Weight;
+        fn remove_service() -> Weight;
+        fn propose_transfer() -> Weight;
+        fn cancel_transfer() -> Weight;
+        fn accept_transfer() -> Weight;
+        fn add_operator() -> Weight;
+        fn remove_operator() -> Weight;
+        fn rotate_verification_method() -> Weight;
+        fn freeze_agent() -> Weight;
+        fn unfreeze_agent() -> Weight;
+        fn heartbeat() -> Weight;
+        fn heartbeat_unsigned() -> Weight;
+        fn set_capabilities() -> Weight;
+        fn suspend_agent() -> Weight;
+        fn lift_suspension() -> Weight;
+        fn purge_agent() -> Weight;
+        fn set_agent_domain() -> Weight;
+        fn register_issuer() -> Weight;
+        fn remove_issuer() -> Weight;
+        fn attest() -> Weight;
+        fn revoke_attestation() -> Weight;
+    }
+
+    /// Default weights for testing.
+    impl WeightInfo for () {
+        fn register_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn update_metadata() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn update_reputation() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn deregister_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn set_agent_status() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn add_verification_method() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn revoke_verification_method() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn add_service() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn remove_service() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn propose_transfer() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn cancel_transfer() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn accept_transfer() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn add_operator() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn remove_operator() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn rotate_verification_method() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn freeze_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn unfreeze_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn heartbeat() -> Weight {
+            Weight::from_parts(5_000, 0)
+        }
+        fn heartbeat_unsigned() -> Weight {
+            Weight::from_parts(5_000, 0)
+        }
+        fn set_capabilities() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn suspend_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn lift_suspension() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn purge_agent() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn set_agent_domain() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn register_issuer() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn remove_issuer() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn attest() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn revoke_attestation() -> Weight {
             Weight::from_parts(10_000, 0)
         }
     }
@@ -0,0 +1,300 @@
+//! Storage migrations for the Agent Registry pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_runtime::traits::Saturating;
+
+/// Version 0 -> 1: build the `DidToAgent` index from `AgentRegistry`.
+///
+/// Before v1 DIDs were not unique. The lowest agent ID holding a DID keeps it;
+/// later duplicates stay registered but cannot be resolved by DID. DIDs of agents
+/// that were already deregistered get a release block of their deregistration
+/// block plus `DidReleaseCooldown`.
+pub mod v1 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`BuildDidIndex`] in the runtime.
+    pub struct BuildDidIndexInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for BuildDidIndexInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut writes: u64 = 0;
+
+            for agent_id in 0..AgentCount::<T>::get() {
+                reads = reads.saturating_add(2);
+                let Some(agent) = v3::AgentRegistry::<T>::get(agent_id) else {
+                    continue;
+                };
+
+                if let Some(holder) = DidToAgent::<T>::get(&agent.did) {
+                    log::warn!(
+                        target: "runtime::agent-registry",
+                        "v1: agent {} shares its DID with agent {}, not indexed",
+                        agent_id,
+                        holder,
+                    );
+                    continue;
+                }
+
+                DidToAgent::<T>::insert(&agent.did, agent_id);
+                writes = writes.saturating_add(1);
+
+                if agent.status == AgentStatus::Deregistered {
+                    DidReleaseAt::<T>::insert(
+                        &agent.did,
+                        agent
+                            .last_active
+                            .saturating_add(T::DidReleaseCooldown::get()),
+                    );
+                    writes = writes.saturating_add(1);
+                }
+            }
+
+            log::info!(
+                target: "runtime::agent-registry",
+                "v1: indexed {} DIDs",
+                writes,
+            );
+
+            T::DbWeight::get().reads_writes(reads.saturating_add(1), writes)
+        }
+    }
+
+    /// Builds `DidToAgent`, then bumps the storage version to 1.
+    pub type BuildDidIndex<T> = VersionedMigration<
+        0,
+        1,
+        BuildDidIndexInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 1 -> 2: sync `AgentInfo::reputation` with pallet-reputation.
+///
+/// Before v2 any signed account could set an agent's reputation, so stored scores
+/// are untrusted. Every live agent is reset to its owner's current score.
+pub mod v2 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`SyncAgentReputation`] in the runtime.
+    pub struct SyncAgentReputationInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for SyncAgentReputationInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut writes: u64 = 0;
+
+            v3::AgentRegistry::<T>::translate_values::<v3::OldAgentInfo<T>, _>(|mut agent| {
+                reads = reads.saturating_add(2);
+                if agent.status != AgentStatus::Deregistered {
+                    agent.reputation = T::Reputation::score(&agent.owner);
+                }
+                writes = writes.saturating_add(1);
+                Some(agent)
+            });
+
+            log::info!(
+                target: "runtime::agent-registry",
+                "v2: synced reputation of {} agents",
+                writes,
+            );
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Syncs agent reputation with pallet-reputation, then bumps the storage version to 2.
+    pub type SyncAgentReputation<T> = VersionedMigration<
+        1,
+        2,
+        SyncAgentReputationInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 2 -> 3: stop storing `AgentInfo::reputation`.
+///
+/// An agent's reputation is now derived from pallet-reputation on every read, so the
+/// stored copy is dropped from every agent. Governance overrides made through
+/// `update_reputation` before v3 were reset by the next score change and are not
+/// carried over.
+pub mod v3 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// `AgentInfo` before v3, with its stored reputation.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct OldAgentInfo<T: Config> {
+        pub owner: T::AccountId,
+        pub did: BoundedVec<u8, T::MaxDidLength>,
+        pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
+        pub reputation: u32,
+        pub registered_at: BlockNumberFor<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub status: AgentStatus,
+    }
+
+    /// `AgentRegistry` before v3.
+    #[frame_support::storage_alias]
+    pub type AgentRegistry<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, AgentId, OldAgentInfo<T>, OptionQuery>;
+
+    /// Unversioned inner migration. Use [`DropStoredReputation`] in the runtime.
+    pub struct DropStoredReputationInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for DropStoredReputationInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            v4::AgentRegistry::<T>::translate_values::<OldAgentInfo<T>, _>(|old| {
+                translated = translated.saturating_add(1);
+                Some(v4::OldAgentInfo {
+                    owner: old.owner,
+                    did: old.did,
+                    metadata: old.metadata,
+                    registered_at: old.registered_at,
+                    last_active: old.last_active,
+                    status: old.status,
+                })
+            });
+
+            log::info!(
+                target: "runtime::agent-registry",
+                "v3: dropped stored reputation of {} agents",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Drops `AgentInfo::reputation`, then bumps the storage version to 3.
+    pub type DropStoredReputation<T> = VersionedMigration<
+        2,
+        3,
+        DropStoredReputationInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 3 -> 4: add `AgentInfo::last_heartbeat`.
+///
+/// Before v4 heartbeats and owner extrinsics both refreshed `last_active`, so it is
+/// the best available estimate of each agent's last heartbeat.
+pub mod v4 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// `AgentInfo` before v4, without `last_heartbeat`.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct OldAgentInfo<T: Config> {
+        pub owner: T::AccountId,
+        pub did: BoundedVec<u8, T::MaxDidLength>,
+        pub metadata: BoundedVec<u8, T::MaxMetadataLength>,
+        pub registered_at: BlockNumberFor<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub status: AgentStatus,
+    }
+
+    /// `AgentRegistry` before v4.
+    #[frame_support::storage_alias]
+    pub type AgentRegistry<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, AgentId, OldAgentInfo<T>, OptionQuery>;
+
+    /// Unversioned inner migration. Use [`AddLastHeartbeat`] in the runtime.
+    pub struct AddLastHeartbeatInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for AddLastHeartbeatInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            crate::AgentRegistry::<T>::translate_values::<OldAgentInfo<T>, _>(|old| {
+                translated = translated.saturating_add(1);
+                Some(AgentInfo {
+                    owner: old.owner,
+                    did: old.did,
+                    metadata: old.metadata,
+                    registered_at: old.registered_at,
+                    last_active: old.last_active,
+                    last_heartbeat: old.last_active,
+                    status: old.status,
+                })
+            });
+
+            log::info!(
+                target: "runtime::agent-registry",
+                "v4: added last heartbeat to {} agents",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Adds `AgentInfo::last_heartbeat`, then bumps the storage version to 4.
+    pub type AddLastHeartbeat<T> = VersionedMigration<
+        3,
+        4,
+        AddLastHeartbeatInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 4 -> 5: remove the agent-to-agent validation registry.
+///
+/// ERC-8004 validation now goes through pallet-validation, so the requests and
+/// responses recorded in `Validations` are dropped.
+pub mod v5 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// A validation request as stored before v5.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ValidationRecord<T: Config> {
+        pub validator: AgentId,
+        pub server: AgentId,
+        pub requested_at: BlockNumberFor<T>,
+        pub response: Option<(u8, BlockNumberFor<T>)>,
+    }
+
+    /// Validation requests and responses by data hash, removed in v5.
+    #[frame_support::storage_alias]
+    pub type Validations<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, DataHash, ValidationRecord<T>, OptionQuery>;
+
+    /// Unversioned inner migration. Use [`DropValidations`] in the runtime.
+    pub struct DropValidationsInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for DropValidationsInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let cleared = Validations::<T>::clear(u32::MAX, None).unique as u64;
+
+            log::info!(
+                target: "runtime::agent-registry",
+                "v5: removed {} validation records",
+                cleared,
+            );
+
+            T::DbWeight::get().reads_writes(cleared, cleared)
+        }
+    }
+
+    /// Removes `Validations`, then bumps the storage version to 5.
+    pub type DropValidations<T> = VersionedMigration<
+        4,
+        5,
+        DropValidationsInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -1,10 +1,16 @@
 //! Unit tests for the Agent Registry pallet.
 
 use crate as pallet_agent_registry;
-use crate::pallet::{AgentCount, AgentRegistry, AgentStatus, OwnerAgents};
+use crate::pallet::{
+    AgentCapabilities, AgentCount, AgentDeposits, AgentRegistry, AgentStatus, AgentTombstones,
+    CallScope, Capability, CapabilityIndex, DidDocuments, DidKeyType, DidReleaseAt, DidToAgent,
+    FrozenAgents, KeyHistory, OperatorCount, Operators, OwnerAgents, PendingTransfers, Suspensions,
+    MAX_SCANNED_AGENTS,
+};
 use frame_support::{
     assert_noop, assert_ok, derive_impl, parameter_types,
     traits::{ConstU32, ConstU64},
+    BoundedVec, PalletId,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -18,6 +24,7 @@ type Block = frame_system::mocking::MockBlock<Test>;
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
+        Balances: pallet_balances,
         AgentRegistryPallet: pallet_agent_registry,
     }
 );
@@ -27,21 +34,90 @@ impl frame_system::Config for Test {
     type Block = Block;
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
 }
 
 impl pallet_agent_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type RuntimeCall = RuntimeCall;
+    type PalletId = AgentPalletId;
+    type MaxOperatorsPerAgent = ConstU32<2>;
+    type MaxOperatorScopes = ConstU32<4>;
+    type OperatorSpendingPeriod = ConstU64<100>;
+    type InactivityPeriod = ConstU64<50>;
+    type MinHeartbeatInterval = ConstU64<5>;
+    type HeartbeatUnsignedPriority = ConstU64<100>;
+    type HeartbeatSignature = sp_runtime::testing::TestSignature;
+    type MaxInactivitySweepPerBlock = ConstU32<2>;
+    type MaxCapabilityIdLength = ConstU32<16>;
+    type MaxCapabilitiesPerAgent = ConstU32<3>;
+    type Currency = Balances;
+    type AgentDepositBase = AgentDepositBase;
+    type AgentDepositPerByte = AgentDepositPerByte;
     type MaxDidLength = ConstU32<256>;
     type MaxMetadataLength = ConstU32<4096>;
     type MaxAgentsPerOwner = ConstU32<10>;
+    type AllowedDidMethods = AllowedDidMethods;
+    type DidReleaseCooldown = ConstU64<10>;
+    type PurgeGracePeriod = ConstU64<20>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxFragmentLength = ConstU32<32>;
+    type MaxServiceEndpointLength = ConstU32<128>;
+    type Reputation = MockReputation;
+    type ReputationOrigin = frame_system::EnsureRoot<u64>;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type MaxSuspensionReasonLength = ConstU32<32>;
+    type MaxDomainLength = ConstU32<32>;
+    type IssuerOrigin = frame_system::EnsureRoot<u64>;
+    type MaxIssuerNameLength = ConstU32<32>;
+    type MaxAttestationsPerAgent = ConstU32<2>;
+}
+
+parameter_types! {
+    /// Score returned by [`MockReputation`] for accounts not in `AccountReputation`.
+    pub static OwnerReputation: u32 = 5000;
+    /// Scores of individual accounts.
+    pub static AccountReputation: Vec<(u64, u32)> = vec![];
+}
+
+pub struct MockReputation;
+
+impl pallet_reputation::ReputationScore<u64> for MockReputation {
+    fn score(account: &u64) -> u32 {
+        AccountReputation::get()
+            .into_iter()
+            .find(|(a, _)| a == account)
+            .map_or_else(OwnerReputation::get, |(_, score)| score)
+    }
+}
+
+parameter_types! {
+    pub static AgentDepositBase: u64 = 0;
+    pub static AgentDepositPerByte: u64 = 0;
+    pub AllowedDidMethods: Vec<Vec<u8>> = vec![b"claw".to_vec(), b"key".to_vec()];
+    pub const AgentPalletId: PalletId = PalletId(*b"clw/agnt");
 }
 
 // Build test externalities from genesis storage.
 fn new_test_ext() -> sp_io::TestExternalities {
-    let t = frame_system::GenesisConfig::<Test>::default()
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 10000), (2, 10000), (3, 100)],
+        dev_accounts: Default::default(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
     ext
@@ -70,7 +146,7 @@ fn register_agent_works() {
         assert_eq!(agent.owner, 1u64);
         assert_eq!(agent.did.to_vec(), did);
         assert_eq!(agent.metadata.to_vec(), metadata);
-        assert_eq!(agent.reputation, 5000);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(5000));
         assert_eq!(agent.status, AgentStatus::Active);
         assert_eq!(agent.registered_at, 1);
 
@@ -211,18 +287,25 @@ fn update_reputation_works() {
         ));
 
         // Increase reputation
-        assert_ok!(AgentRegistryPallet::update_reputation(account(1), 0, 1000));
-        let agent = AgentRegistry::<Test>::get(0).unwrap();
-        assert_eq!(agent.reputation, 6000);
+        assert_ok!(AgentRegistryPallet::update_reputation(
+            RuntimeOrigin::root(),
+            0,
+            1000
+        ));
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(6000));
 
         // Decrease reputation
         assert_ok!(AgentRegistryPallet::update_reputation(
-            account(1),
+            RuntimeOrigin::root(),
             0,
             -2000
         ));
-        let agent = AgentRegistry::<Test>::get(0).unwrap();
-        assert_eq!(agent.reputation, 4000);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(4000));
+        assert_eq!(crate::ReputationAdjustments::<Test>::get(0), -1000);
+
+        // The adjustment stays applied as the underlying score moves
+        OwnerReputation::set(7000);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(6000));
     });
 }
 
@@ -236,18 +319,21 @@ fn update_reputation_clamps_at_bounds() {
         ));
 
         // Try to exceed max (10000)
-        assert_ok!(AgentRegistryPallet::update_reputation(account(1), 0, 9999));
-        let agent = AgentRegistry::<Test>::get(0).unwrap();
-        assert_eq!(agent.reputation, 10000); // Clamped
+        assert_ok!(AgentRegistryPallet::update_reputation(
+            RuntimeOrigin::root(),
+            0,
+            9999
+        ));
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(10000)); // Clamped
 
         // Try to go below 0
         assert_ok!(AgentRegistryPallet::update_reputation(
-            account(1),
+            RuntimeOrigin::root(),
             0,
             -20000
         ));
-        let agent = AgentRegistry::<Test>::get(0).unwrap();
-        assert_eq!(agent.reputation, 0); // Clamped
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(0)); // Clamped
+        assert_eq!(crate::ReputationAdjustments::<Test>::get(0), -10000);
     });
 }
 
@@ -301,7 +387,7 @@ fn cannot_update_deregistered_agent() {
 
         // Cannot update reputation
         assert_noop!(
-            AgentRegistryPallet::update_reputation(account(1), 0, 100),
+            AgentRegistryPallet::update_reputation(RuntimeOrigin::root(), 0, 100),
             crate::Error::<Test>::AgentAlreadyDeregistered
         );
 
@@ -357,3 +443,2101 @@ fn set_agent_status_fails_for_non_owner() {
         );
     });
 }
+
+#[test]
+fn runtime_api_agent_queries_work() {
+    new_test_ext().execute_with(|| {
+        for i in 0..3u64 {
+            let did = format!("did:claw:agent{:03}", i).into_bytes();
+            assert_ok!(AgentRegistryPallet::register_agent(
+                account(1),
+                did,
+                b"{}".to_vec()
+            ));
+        }
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(2),
+            b"did:claw:other".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            1,
+            AgentStatus::Suspended
+        ));
+
+        let agent = AgentRegistryPallet::agent(0).unwrap();
+        assert_eq!(agent.agent_id, 0);
+        assert_eq!(agent.owner, 1);
+        assert_eq!(agent.did, b"did:claw:agent000".to_vec());
+        assert!(AgentRegistryPallet::agent(9).is_none());
+
+        assert_eq!(
+            AgentRegistryPallet::agent_by_did(b"did:claw:other")
+                .unwrap()
+                .agent_id,
+            3
+        );
+        assert!(AgentRegistryPallet::agent_by_did(b"did:claw:missing").is_none());
+
+        let owned: Vec<_> = AgentRegistryPallet::agents_by_owner(&1)
+            .into_iter()
+            .map(|a| a.agent_id)
+            .collect();
+        assert_eq!(owned, vec![0, 1, 2]);
+
+        let all = AgentRegistryPallet::list_agents(None, 0, 10);
+        assert_eq!(
+            all.agents.iter().map(|a| a.agent_id).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(all.next, None);
+        let active = AgentRegistryPallet::list_agents(Some(AgentStatus::Active), 1, 1);
+        assert_eq!(
+            active.agents.iter().map(|a| a.agent_id).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(active.next, Some(3));
+    });
+}
+
+#[test]
+fn register_agent_rejects_duplicate_did() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_eq!(
+            DidToAgent::<Test>::get(BoundedVec::truncate_from(b"did:claw:test".to_vec())),
+            Some(0)
+        );
+
+        assert_noop!(
+            AgentRegistryPallet::register_agent(
+                account(2),
+                b"did:claw:test".to_vec(),
+                b"{}".to_vec()
+            ),
+            crate::Error::<Test>::DidAlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn register_agent_validates_did_syntax() {
+    new_test_ext().execute_with(|| {
+        for did in [
+            &b"claw:agent"[..],
+            b"did:claw",
+            b"did::agent",
+            b"did:Claw:agent",
+            b"did:claw:",
+            b"did:claw:agent:",
+            b"did:claw:agent one",
+            b"did:claw:bad%2",
+            b"did:claw:bad%zz",
+        ] {
+            assert_noop!(
+                AgentRegistryPallet::register_agent(account(1), did.to_vec(), b"{}".to_vec()),
+                crate::Error::<Test>::InvalidDid
+            );
+        }
+
+        assert_noop!(
+            AgentRegistryPallet::register_agent(
+                account(1),
+                b"did:web:example.com".to_vec(),
+                b"{}".to_vec()
+            ),
+            crate::Error::<Test>::DidMethodNotAllowed
+        );
+
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:ns:agent-1.v2_%41".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_vec(),
+            b"{}".to_vec()
+        ));
+    });
+}
+
+#[test]
+fn deregistered_did_released_after_cooldown() {
+    new_test_ext().execute_with(|| {
+        let did = b"did:claw:test".to_vec();
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            did.clone(),
+            b"{}".to_vec()
+        ));
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert_eq!(
+            DidReleaseAt::<Test>::get(BoundedVec::truncate_from(did.clone())),
+            Some(11)
+        );
+
+        // Still resolves to the deregistered agent during the cooldown.
+        assert_eq!(AgentRegistryPallet::agent_by_did(&did).unwrap().agent_id, 0);
+
+        System::set_block_number(10);
+        assert_noop!(
+            AgentRegistryPallet::register_agent(account(2), did.clone(), b"{}".to_vec()),
+            crate::Error::<Test>::DidInCooldown
+        );
+
+        System::set_block_number(11);
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(2),
+            did.clone(),
+            b"{}".to_vec()
+        ));
+        assert_eq!(AgentRegistryPallet::agent_by_did(&did).unwrap().agent_id, 1);
+        assert_eq!(
+            DidReleaseAt::<Test>::get(BoundedVec::truncate_from(did)),
+            None
+        );
+    });
+}
+
+#[test]
+fn v1_migration_builds_did_index() {
+    use crate::migrations::v3::{AgentRegistry as OldAgentRegistry, OldAgentInfo};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let info = |owner: u64, did: &[u8], status: AgentStatus| OldAgentInfo::<Test> {
+            owner,
+            did: BoundedVec::truncate_from(did.to_vec()),
+            metadata: Default::default(),
+            reputation: 5000,
+            registered_at: 1,
+            last_active: 3,
+            status,
+        };
+        OldAgentRegistry::<Test>::insert(0, info(1, b"did:claw:a", AgentStatus::Active));
+        OldAgentRegistry::<Test>::insert(1, info(2, b"did:claw:a", AgentStatus::Active));
+        OldAgentRegistry::<Test>::insert(2, info(2, b"did:claw:b", AgentStatus::Deregistered));
+        AgentCount::<Test>::put(3);
+        StorageVersion::new(0).put::<AgentRegistryPallet>();
+
+        crate::migrations::v1::BuildDidIndex::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            DidToAgent::<Test>::get(BoundedVec::truncate_from(b"did:claw:a".to_vec())),
+            Some(0)
+        );
+        assert_eq!(
+            DidToAgent::<Test>::get(BoundedVec::truncate_from(b"did:claw:b".to_vec())),
+            Some(2)
+        );
+        assert_eq!(
+            DidReleaseAt::<Test>::get(BoundedVec::truncate_from(b"did:claw:b".to_vec())),
+            Some(13)
+        );
+        assert_eq!(AgentRegistryPallet::on_chain_storage_version(), 1);
+    });
+}
+
+#[test]
+fn did_document_methods_and_services_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+
+        assert_ok!(AgentRegistryPallet::add_verification_method(
+            account(1),
+            0,
+            b"key-1".to_vec(),
+            DidKeyType::Ed25519,
+            vec![1u8; 32],
+            true,
+            false
+        ));
+        assert_ok!(AgentRegistryPallet::add_service(
+            account(1),
+            0,
+            b"messaging".to_vec(),
+            b"DIDCommMessaging".to_vec(),
+            b"https://agent.example.com/didcomm".to_vec()
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(2),
+                0,
+                b"key-2".to_vec(),
+                DidKeyType::Sr25519,
+                vec![2u8; 32],
+                true,
+                true
+            ),
+            crate::Error::<Test>::NotAgentOwner
+        );
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(1),
+                0,
+                b"messaging".to_vec(),
+                DidKeyType::Sr25519,
+                vec![2u8; 32],
+                true,
+                true
+            ),
+            crate::Error::<Test>::DuplicateFragment
+        );
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(1),
+                0,
+                b"key-2".to_vec(),
+                DidKeyType::EcdsaSecp256k1,
+                vec![2u8; 32],
+                true,
+                true
+            ),
+            crate::Error::<Test>::InvalidPublicKey
+        );
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(1),
+                0,
+                b"key#2".to_vec(),
+                DidKeyType::Sr25519,
+                vec![2u8; 32],
+                true,
+                true
+            ),
+            crate::Error::<Test>::InvalidFragment
+        );
+        assert_noop!(
+            AgentRegistryPallet::add_service(
+                account(1),
+                0,
+                b"web".to_vec(),
+                b"LinkedDomains".to_vec(),
+                b"https://example.com/\"}".to_vec()
+            ),
+            crate::Error::<Test>::InvalidServiceEndpoint
+        );
+
+        assert_ok!(AgentRegistryPallet::add_verification_method(
+            account(1),
+            0,
+            b"key-2".to_vec(),
+            DidKeyType::EcdsaSecp256k1,
+            vec![2u8; 33],
+            false,
+            true
+        ));
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(1),
+                0,
+                b"key-3".to_vec(),
+                DidKeyType::Sr25519,
+                vec![3u8; 32],
+                true,
+                true
+            ),
+            crate::Error::<Test>::TooManyVerificationMethods
+        );
+
+        assert_ok!(AgentRegistryPallet::revoke_verification_method(
+            account(1),
+            0,
+            b"key-2".to_vec()
+        ));
+        assert_noop!(
+            AgentRegistryPallet::remove_service(account(1), 0, b"key-1".to_vec()),
+            crate::Error::<Test>::FragmentNotFound
+        );
+
+        let doc = DidDocuments::<Test>::get(0);
+        assert_eq!(doc.verification_methods.len(), 1);
+        assert_eq!(doc.services.len(), 1);
+    });
+}
+
+#[test]
+fn did_document_renders_did_core_json() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert!(AgentRegistryPallet::did_document(b"did:claw:missing").is_none());
+
+        assert_ok!(AgentRegistryPallet::add_verification_method(
+            account(1),
+            0,
+            b"key-1".to_vec(),
+            DidKeyType::Ed25519,
+            vec![0u8; 32],
+            true,
+            false
+        ));
+        assert_ok!(AgentRegistryPallet::add_verification_method(
+            account(1),
+            0,
+            b"key-2".to_vec(),
+            DidKeyType::Sr25519,
+            vec![1u8; 32],
+            true,
+            true
+        ));
+        assert_ok!(AgentRegistryPallet::add_service(
+            account(1),
+            0,
+            b"messaging".to_vec(),
+            b"DIDCommMessaging".to_vec(),
+            b"https://agent.example.com/didcomm".to_vec()
+        ));
+
+        let json = AgentRegistryPallet::did_document(b"did:claw:test").unwrap();
+        let doc: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(
+            doc,
+            serde_json::json!({
+                "@context": [
+                    "https://www.w3.org/ns/did/v1",
+                    "https://w3id.org/security/multikey/v1"
+                ],
+                "id": "did:claw:test",
+                "verificationMethod": [
+                    {
+                        "id": "did:claw:test#key-1",
+                        "type": "Multikey",
+                        "controller": "did:claw:test",
+                        "publicKeyMultibase": "z6MkeTG3bFFSLYVU7VqhgZxqr6YzpaGrQtFMh1uvqGy1vDnP"
+                    },
+                    {
+                        "id": "did:claw:test#key-2",
+                        "type": "Multikey",
+                        "controller": "did:claw:test",
+                        "publicKeyMultibase": "z6QNk5M6sRbbR1L9Hp25RiBYByzo1axXWW5asVy5aHeb5F8L"
+                    }
+                ],
+                "authentication": ["did:claw:test#key-1", "did:claw:test#key-2"],
+                "assertionMethod": ["did:claw:test#key-2"],
+                "service": [
+                    {
+                        "id": "did:claw:test#messaging",
+                        "type": "DIDCommMessaging",
+                        "serviceEndpoint": "https://agent.example.com/didcomm"
+                    }
+                ]
+            })
+        );
+    });
+}
+
+#[test]
+fn update_reputation_requires_reputation_origin() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::update_reputation(account(1), 0, 1000),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AgentRegistryPallet::update_reputation(account(2), 0, i32::MIN),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(5000));
+    });
+}
+
+#[test]
+fn agent_reputation_is_derived_from_owner_and_agent_account() {
+    new_test_ext().execute_with(|| {
+        OwnerReputation::set(7000);
+        for i in 0..2u64 {
+            let did = format!("did:claw:agent{:03}", i).into_bytes();
+            assert_ok!(AgentRegistryPallet::register_agent(
+                account(1),
+                did,
+                b"{}".to_vec()
+            ));
+        }
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(7000));
+
+        // Scores are read, never copied: every change shows up at once
+        OwnerReputation::set(6500);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(6500));
+        assert_eq!(AgentRegistryPallet::agent(1).unwrap().reputation, 6500);
+
+        // Work done through `operate` counts against the agent account, and the
+        // agent gets the lower of the two scores
+        let agent_account = AgentRegistryPallet::agent_account(0);
+        AccountReputation::set(vec![(agent_account, 4000)]);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(4000));
+        AccountReputation::set(vec![(agent_account, 9000)]);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(6500));
+
+        assert_eq!(AgentRegistryPallet::agent_reputation(2), None);
+    });
+}
+
+#[test]
+fn v2_migration_syncs_agent_reputation() {
+    use crate::migrations::v3::{AgentRegistry as OldAgentRegistry, OldAgentInfo};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let info = |status: AgentStatus| OldAgentInfo::<Test> {
+            owner: 1,
+            did: BoundedVec::truncate_from(b"did:claw:a".to_vec()),
+            metadata: Default::default(),
+            reputation: 10000,
+            registered_at: 1,
+            last_active: 1,
+            status,
+        };
+        OldAgentRegistry::<Test>::insert(0, info(AgentStatus::Active));
+        OldAgentRegistry::<Test>::insert(1, info(AgentStatus::Deregistered));
+        OwnerReputation::set(4200);
+        StorageVersion::new(1).put::<AgentRegistryPallet>();
+
+        crate::migrations::v2::SyncAgentReputation::<Test>::on_runtime_upgrade();
+
+        assert_eq!(OldAgentRegistry::<Test>::get(0).unwrap().reputation, 4200);
+        assert_eq!(OldAgentRegistry::<Test>::get(1).unwrap().reputation, 10000);
+        assert_eq!(AgentRegistryPallet::on_chain_storage_version(), 2);
+    });
+}
+
+#[test]
+fn v3_migration_drops_stored_reputation() {
+    use crate::migrations::v3::{AgentRegistry as OldAgentRegistry, OldAgentInfo};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        OldAgentRegistry::<Test>::insert(
+            0,
+            OldAgentInfo::<Test> {
+                owner: 1,
+                did: BoundedVec::truncate_from(b"did:claw:a".to_vec()),
+                metadata: BoundedVec::truncate_from(b"{}".to_vec()),
+                reputation: 10000,
+                registered_at: 1,
+                last_active: 3,
+                status: AgentStatus::Active,
+            },
+        );
+        AgentCount::<Test>::put(1);
+        OwnerReputation::set(4200);
+        StorageVersion::new(2).put::<AgentRegistryPallet>();
+
+        crate::migrations::v3::DropStoredReputation::<Test>::on_runtime_upgrade();
+
+        let agent = crate::migrations::v4::AgentRegistry::<Test>::get(0).unwrap();
+        assert_eq!(agent.owner, 1);
+        assert_eq!(agent.metadata.to_vec(), b"{}".to_vec());
+        assert_eq!((agent.registered_at, agent.last_active), (1, 3));
+        assert_eq!(agent.status, AgentStatus::Active);
+        assert_eq!(AgentRegistryPallet::on_chain_storage_version(), 3);
+
+        crate::migrations::v4::AddLastHeartbeat::<Test>::on_runtime_upgrade();
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(4200));
+    });
+}
+
+#[test]
+fn v4_migration_seeds_last_heartbeat() {
+    use crate::migrations::v4::{AgentRegistry as OldAgentRegistry, OldAgentInfo};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        OldAgentRegistry::<Test>::insert(
+            0,
+            OldAgentInfo::<Test> {
+                owner: 1,
+                did: BoundedVec::truncate_from(b"did:claw:a".to_vec()),
+                metadata: BoundedVec::truncate_from(b"{}".to_vec()),
+                registered_at: 1,
+                last_active: 3,
+                status: AgentStatus::Inactive,
+            },
+        );
+        AgentCount::<Test>::put(1);
+        StorageVersion::new(3).put::<AgentRegistryPallet>();
+
+        crate::migrations::v4::AddLastHeartbeat::<Test>::on_runtime_upgrade();
+
+        let agent = AgentRegistry::<Test>::get(0).unwrap();
+        assert_eq!(
+            (agent.registered_at, agent.last_active, agent.last_heartbeat),
+            (1, 3, 3)
+        );
+        assert_eq!(agent.status, AgentStatus::Inactive);
+        assert_eq!(AgentRegistryPallet::on_chain_storage_version(), 4);
+    });
+}
+
+#[test]
+fn v5_migration_drops_validations() {
+    use crate::migrations::v5::{ValidationRecord, Validations};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        for data_hash in [[1u8; 32], [2u8; 32]] {
+            Validations::<Test>::insert(
+                data_hash,
+                ValidationRecord::<Test> {
+                    validator: 1,
+                    server: 0,
+                    requested_at: 1,
+                    response: Some((90, 2)),
+                },
+            );
+        }
+        StorageVersion::new(4).put::<AgentRegistryPallet>();
+
+        crate::migrations::v5::DropValidations::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Validations::<Test>::iter().count(), 0);
+        assert_eq!(AgentRegistryPallet::on_chain_storage_version(), 5);
+    });
+}
+
+#[test]
+fn registration_reserves_and_refunds_deposit() {
+    new_test_ext().execute_with(|| {
+        AgentDepositBase::set(100);
+        AgentDepositPerByte::set(2);
+
+        // 13 bytes of DID + 2 bytes of metadata
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_eq!(AgentDeposits::<Test>::get(0), 130);
+        assert_eq!(Balances::reserved_balance(1), 130);
+
+        // Growing the metadata to 12 bytes reserves 20 more
+        assert_ok!(AgentRegistryPallet::update_metadata(
+            account(1),
+            0,
+            b"{\"v\": 12345}".to_vec()
+        ));
+        assert_eq!(AgentDeposits::<Test>::get(0), 150);
+        assert_eq!(Balances::reserved_balance(1), 150);
+
+        // Shrinking it releases the difference
+        assert_ok!(AgentRegistryPallet::update_metadata(
+            account(1),
+            0,
+            b"".to_vec()
+        ));
+        assert_eq!(AgentDeposits::<Test>::get(0), 126);
+        assert_eq!(Balances::reserved_balance(1), 126);
+
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert!(!AgentDeposits::<Test>::contains_key(0));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 10000);
+    });
+}
+
+#[test]
+fn registration_fails_without_deposit() {
+    new_test_ext().execute_with(|| {
+        AgentDepositBase::set(100);
+        AgentDepositPerByte::set(3);
+
+        assert_noop!(
+            AgentRegistryPallet::register_agent(
+                account(3),
+                b"did:claw:test".to_vec(),
+                b"{}".to_vec()
+            ),
+            crate::Error::<Test>::InsufficientDeposit
+        );
+
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(2),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_noop!(
+            AgentRegistryPallet::update_metadata(account(2), 0, vec![b'x'; 4096]),
+            crate::Error::<Test>::InsufficientDeposit
+        );
+    });
+}
+
+#[test]
+fn two_step_transfer_works() {
+    new_test_ext().execute_with(|| {
+        AgentDepositBase::set(100);
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::propose_transfer(account(2), 0, 2),
+            crate::Error::<Test>::NotAgentOwner
+        );
+        assert_noop!(
+            AgentRegistryPallet::propose_transfer(account(1), 0, 1),
+            crate::Error::<Test>::TransferToSelf
+        );
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 2));
+        assert_eq!(PendingTransfers::<Test>::get(0), Some(2));
+
+        assert_noop!(
+            AgentRegistryPallet::accept_transfer(account(3), 0),
+            crate::Error::<Test>::NotTransferRecipient
+        );
+
+        OwnerReputation::set(8000);
+        assert_ok!(AgentRegistryPallet::accept_transfer(account(2), 0));
+
+        let agent = AgentRegistry::<Test>::get(0).unwrap();
+        assert_eq!(agent.owner, 2);
+        assert_eq!(AgentRegistryPallet::agent_reputation(0), Some(8000));
+        assert!(OwnerAgents::<Test>::get(1).is_empty());
+        assert_eq!(OwnerAgents::<Test>::get(2).to_vec(), vec![0]);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::reserved_balance(2), 100);
+        assert!(PendingTransfers::<Test>::get(0).is_none());
+        System::assert_last_event(
+            crate::Event::AgentTransferred {
+                agent_id: 0,
+                from: 1,
+                to: 2,
+            }
+            .into(),
+        );
+
+        // The old owner lost control.
+        assert_noop!(
+            AgentRegistryPallet::update_metadata(account(1), 0, b"{}".to_vec()),
+            crate::Error::<Test>::NotAgentOwner
+        );
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(2), 0));
+        assert_eq!(Balances::reserved_balance(2), 0);
+    });
+}
+
+#[test]
+fn transfer_can_be_cancelled_and_respects_limits() {
+    new_test_ext().execute_with(|| {
+        AgentDepositBase::set(1000);
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:test".to_vec(),
+            b"{}".to_vec()
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::cancel_transfer(account(1), 0),
+            crate::Error::<Test>::NoPendingTransfer
+        );
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 2));
+        assert_ok!(AgentRegistryPallet::cancel_transfer(account(1), 0));
+        assert_noop!(
+            AgentRegistryPallet::accept_transfer(account(2), 0),
+            crate::Error::<Test>::NoPendingTransfer
+        );
+
+        // Account 3 cannot cover the deposit.
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 3));
+        assert_noop!(
+            AgentRegistryPallet::accept_transfer(account(3), 0),
+            crate::Error::<Test>::InsufficientDeposit
+        );
+
+        // Account 2 already owns the maximum number of agents.
+        AgentDepositBase::set(0);
+        for i in 0..10u64 {
+            let did = format!("did:claw:agent{:03}", i).into_bytes();
+            assert_ok!(AgentRegistryPallet::register_agent(
+                account(2),
+                did,
+                b"{}".to_vec()
+            ));
+        }
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 2));
+        assert_noop!(
+            AgentRegistryPallet::accept_transfer(account(2), 0),
+            crate::Error::<Test>::TooManyAgents
+        );
+
+        // Deregistering drops the pending proposal.
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert!(PendingTransfers::<Test>::get(0).is_none());
+    });
+}
+
+// ========== Operator Key Tests ==========
+
+/// Index of `Balances` in the mock `construct_runtime!`.
+const BALANCES_INDEX: u8 = 1;
+
+/// Index of `AgentRegistryPallet` in the mock `construct_runtime!`.
+const AGENT_REGISTRY_INDEX: u8 = 2;
+
+fn transfer_call(dest: u64, value: u64) -> Box<RuntimeCall> {
+    Box::new(RuntimeCall::Balances(
+        pallet_balances::Call::transfer_allow_death { dest, value },
+    ))
+}
+
+/// Register agent 0 for account 1 and fund its agent account.
+fn setup_funded_agent() -> u64 {
+    assert_ok!(AgentRegistryPallet::register_agent(
+        account(1),
+        b"did:claw:agent001".to_vec(),
+        b"{}".to_vec()
+    ));
+    let agent_account = AgentRegistryPallet::agent_account(0);
+    assert_ok!(Balances::transfer_allow_death(
+        account(1),
+        agent_account,
+        1000
+    ));
+    agent_account
+}
+
+#[test]
+fn operator_can_act_as_agent() {
+    new_test_ext().execute_with(|| {
+        let agent_account = setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Pallet(BALANCES_INDEX)],
+            None
+        ));
+        assert_eq!(OperatorCount::<Test>::get(0), 1);
+
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            transfer_call(3, 300)
+        ));
+
+        assert_eq!(Balances::free_balance(agent_account), 700);
+        assert_eq!(Balances::free_balance(3), 400);
+        System::assert_last_event(
+            crate::Event::OperatorCallExecuted {
+                agent_id: 0,
+                operator: 2,
+            }
+            .into(),
+        );
+
+        // A failing call fails the whole extrinsic.
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 5000)),
+            sp_runtime::TokenError::FundsUnavailable
+        );
+    });
+}
+
+#[test]
+fn operate_rejects_non_operators_and_out_of_scope_calls() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 1)),
+            pallet_agent_registry::Error::<Test>::NotOperator
+        );
+
+        // Only `remark` on System is permitted.
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Call(0, 0)],
+            None
+        ));
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 1)),
+            pallet_agent_registry::Error::<Test>::CallNotPermitted
+        );
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            Box::new(RuntimeCall::System(frame_system::Call::remark {
+                remark: b"hello".to_vec()
+            }))
+        ));
+    });
+}
+
+#[test]
+fn operate_requires_active_agent() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Pallet(BALANCES_INDEX)],
+            None
+        ));
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            0,
+            AgentStatus::Suspended
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 1)),
+            pallet_agent_registry::Error::<Test>::AgentNotActive
+        );
+    });
+}
+
+#[test]
+fn spending_cap_is_enforced_per_period() {
+    new_test_ext().execute_with(|| {
+        let agent_account = setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Pallet(BALANCES_INDEX)],
+            Some(500)
+        ));
+
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            transfer_call(3, 300)
+        ));
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 300)),
+            pallet_agent_registry::Error::<Test>::SpendingCapExceeded
+        );
+        assert_eq!(Balances::free_balance(agent_account), 700);
+        assert_eq!(Operators::<Test>::get(0, 2).unwrap().spent, 300);
+
+        // A new period starts once `OperatorSpendingPeriod` has elapsed.
+        System::set_block_number(101);
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            transfer_call(3, 300)
+        ));
+        assert_eq!(Balances::free_balance(agent_account), 400);
+        assert_eq!(Operators::<Test>::get(0, 2).unwrap().spent, 300);
+    });
+}
+
+#[test]
+fn spending_cap_counts_reserves() {
+    new_test_ext().execute_with(|| {
+        let agent_account = setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Pallet(AGENT_REGISTRY_INDEX)],
+            Some(150)
+        ));
+        AgentDepositBase::set(100);
+        let register = |did: &[u8]| {
+            Box::new(RuntimeCall::AgentRegistryPallet(
+                crate::Call::register_agent {
+                    did: did.to_vec(),
+                    metadata: b"{}".to_vec(),
+                },
+            ))
+        };
+
+        // Reserved funds can leave the agent account later, so reserving is spending.
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            register(b"did:claw:sub1")
+        ));
+        assert_eq!(Balances::reserved_balance(agent_account), 100);
+        assert_eq!(Operators::<Test>::get(0, 2).unwrap().spent, 100);
+
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, register(b"did:claw:sub2")),
+            pallet_agent_registry::Error::<Test>::SpendingCapExceeded
+        );
+    });
+}
+
+#[test]
+fn add_operator_checks_owner_and_limits() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::add_operator(account(2), 0, 2, vec![], None),
+            pallet_agent_registry::Error::<Test>::NotAgentOwner
+        );
+        assert_noop!(
+            AgentRegistryPallet::add_operator(
+                account(1),
+                0,
+                2,
+                vec![CallScope::Pallet(0); 5],
+                None
+            ),
+            pallet_agent_registry::Error::<Test>::TooManyScopes
+        );
+
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![],
+            None
+        ));
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            3,
+            vec![],
+            None
+        ));
+        // Replacing an existing operator's permissions does not count against the limit.
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            3,
+            vec![CallScope::Pallet(0)],
+            None
+        ));
+        assert_noop!(
+            AgentRegistryPallet::add_operator(account(1), 0, 4, vec![], None),
+            pallet_agent_registry::Error::<Test>::TooManyOperators
+        );
+
+        assert_ok!(AgentRegistryPallet::remove_operator(account(1), 0, 2));
+        assert_eq!(OperatorCount::<Test>::get(0), 1);
+        assert_noop!(
+            AgentRegistryPallet::remove_operator(account(1), 0, 2),
+            pallet_agent_registry::Error::<Test>::NotOperator
+        );
+    });
+}
+
+#[test]
+fn operators_are_cleared_on_deregister_and_transfer() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            3,
+            vec![],
+            None
+        ));
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 2));
+        assert_ok!(AgentRegistryPallet::accept_transfer(account(2), 0));
+        assert!(Operators::<Test>::get(0, 3).is_none());
+        assert_eq!(OperatorCount::<Test>::get(0), 0);
+
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(2),
+            0,
+            3,
+            vec![],
+            None
+        ));
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(2), 0));
+        assert!(Operators::<Test>::get(0, 3).is_none());
+        assert_eq!(OperatorCount::<Test>::get(0), 0);
+    });
+}
+
+// ========== Key Rotation Tests ==========
+
+fn add_key(fragment: &[u8], public_key: Vec<u8>) {
+    assert_ok!(AgentRegistryPallet::add_verification_method(
+        account(1),
+        0,
+        fragment.to_vec(),
+        DidKeyType::Ed25519,
+        public_key,
+        true,
+        false
+    ));
+}
+
+#[test]
+fn accept_transfer_revokes_the_old_owners_keys() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        add_key(b"key-1", vec![1u8; 32]);
+        add_key(b"key-2", vec![2u8; 32]);
+        assert_ok!(AgentRegistryPallet::add_service(
+            account(1),
+            0,
+            b"messaging".to_vec(),
+            b"DIDCommMessaging".to_vec(),
+            b"https://agent.example.com/didcomm".to_vec()
+        ));
+
+        System::set_block_number(5);
+        assert_ok!(AgentRegistryPallet::propose_transfer(account(1), 0, 2));
+        assert_ok!(AgentRegistryPallet::accept_transfer(account(2), 0));
+
+        let document = DidDocuments::<Test>::get(0);
+        assert!(document.verification_methods.is_empty());
+        assert!(document.services.is_empty());
+        for key in [[1u8; 32], [2u8; 32]] {
+            let record = KeyHistory::<Test>::get(0, BoundedVec::truncate_from(key.to_vec()));
+            assert_eq!(record.unwrap().revoked_at, Some(5));
+            assert!(AgentRegistryPallet::key_valid_at(0, &key, 4));
+            assert!(!AgentRegistryPallet::key_valid_at(0, &key, 5));
+        }
+
+        // The new owner binds fresh keys; the old ones never come back.
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(2),
+                0,
+                b"key-1".to_vec(),
+                DidKeyType::Ed25519,
+                vec![1u8; 32],
+                true,
+                false
+            ),
+            crate::Error::<Test>::KeyAlreadyUsed
+        );
+        assert_ok!(AgentRegistryPallet::add_verification_method(
+            account(2),
+            0,
+            b"key-1".to_vec(),
+            DidKeyType::Ed25519,
+            vec![3u8; 32],
+            true,
+            false
+        ));
+        assert!(AgentRegistryPallet::key_valid_at(0, &[3u8; 32], 5));
+    });
+}
+
+#[test]
+fn rotate_verification_method_keeps_key_history() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        add_key(b"key-1", vec![1u8; 32]);
+
+        System::set_block_number(5);
+        assert_ok!(AgentRegistryPallet::rotate_verification_method(
+            account(1),
+            0,
+            b"key-1".to_vec(),
+            DidKeyType::Sr25519,
+            vec![2u8; 32]
+        ));
+
+        let method = DidDocuments::<Test>::get(0).verification_methods[0].clone();
+        assert_eq!(method.key_type, DidKeyType::Sr25519);
+        assert_eq!(method.public_key.to_vec(), vec![2u8; 32]);
+        assert!(method.authentication);
+
+        let old = KeyHistory::<Test>::get(0, BoundedVec::truncate_from(vec![1u8; 32])).unwrap();
+        assert_eq!((old.added_at, old.revoked_at), (1, Some(5)));
+
+        // Signatures made before the rotation remain verifiable.
+        assert!(AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 4));
+        assert!(!AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 5));
+        assert!(!AgentRegistryPallet::key_valid_at(0, &[2u8; 32], 4));
+        assert!(AgentRegistryPallet::key_valid_at(0, &[2u8; 32], 5));
+
+        assert_noop!(
+            AgentRegistryPallet::rotate_verification_method(
+                account(1),
+                0,
+                b"key-2".to_vec(),
+                DidKeyType::Ed25519,
+                vec![3u8; 32]
+            ),
+            pallet_agent_registry::Error::<Test>::FragmentNotFound
+        );
+        assert_noop!(
+            AgentRegistryPallet::rotate_verification_method(
+                account(2),
+                0,
+                b"key-1".to_vec(),
+                DidKeyType::Ed25519,
+                vec![3u8; 32]
+            ),
+            pallet_agent_registry::Error::<Test>::NotAgentOwner
+        );
+    });
+}
+
+#[test]
+fn revoked_keys_cannot_be_reused() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        add_key(b"key-1", vec![1u8; 32]);
+
+        System::set_block_number(3);
+        assert_ok!(AgentRegistryPallet::revoke_verification_method(
+            account(1),
+            0,
+            b"key-1".to_vec()
+        ));
+        assert!(DidDocuments::<Test>::get(0).verification_methods.is_empty());
+        assert!(AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 2));
+        assert!(!AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 3));
+
+        assert_noop!(
+            AgentRegistryPallet::add_verification_method(
+                account(1),
+                0,
+                b"key-2".to_vec(),
+                DidKeyType::Ed25519,
+                vec![1u8; 32],
+                true,
+                false
+            ),
+            pallet_agent_registry::Error::<Test>::KeyAlreadyUsed
+        );
+
+        // History survives deregistration.
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert!(AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 2));
+    });
+}
+
+#[test]
+fn freeze_blocks_operators_transfers_and_keys() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        add_key(b"key-1", vec![1u8; 32]);
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![CallScope::Pallet(BALANCES_INDEX)],
+            None
+        ));
+
+        assert_noop!(
+            AgentRegistryPallet::freeze_agent(account(2), 0),
+            pallet_agent_registry::Error::<Test>::NotAgentOwner
+        );
+        System::set_block_number(4);
+        assert_ok!(AgentRegistryPallet::freeze_agent(account(1), 0));
+        assert_eq!(FrozenAgents::<Test>::get(0), Some(4));
+        System::assert_last_event(crate::Event::AgentFrozen { agent_id: 0 }.into());
+
+        assert_noop!(
+            AgentRegistryPallet::operate(account(2), 0, transfer_call(3, 1)),
+            pallet_agent_registry::Error::<Test>::AgentFrozen
+        );
+        assert_noop!(
+            AgentRegistryPallet::propose_transfer(account(1), 0, 2),
+            pallet_agent_registry::Error::<Test>::AgentFrozen
+        );
+        assert!(AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 3));
+        assert!(!AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 4));
+
+        // The owner can still rotate keys while frozen.
+        assert_ok!(AgentRegistryPallet::rotate_verification_method(
+            account(1),
+            0,
+            b"key-1".to_vec(),
+            DidKeyType::Ed25519,
+            vec![2u8; 32]
+        ));
+
+        assert_ok!(AgentRegistryPallet::unfreeze_agent(account(1), 0));
+        assert_noop!(
+            AgentRegistryPallet::unfreeze_agent(account(1), 0),
+            pallet_agent_registry::Error::<Test>::AgentNotFrozen
+        );
+        assert!(AgentRegistryPallet::key_valid_at(0, &[2u8; 32], 4));
+        assert!(!AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 4));
+        assert_ok!(AgentRegistryPallet::operate(
+            account(2),
+            0,
+            transfer_call(3, 1)
+        ));
+    });
+}
+
+// ========== Liveness Tests ==========
+
+/// Run `on_idle` for block `n` with unlimited weight.
+fn run_idle(n: u64) {
+    use frame_support::traits::OnIdle;
+    System::set_block_number(n);
+    <AgentRegistryPallet as OnIdle<u64>>::on_idle(n, frame_support::weights::Weight::MAX);
+}
+
+#[test]
+fn heartbeat_requires_owner_or_operator() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::heartbeat(account(2), 0),
+            pallet_agent_registry::Error::<Test>::NotOperator
+        );
+
+        // An operator without scopes is a heartbeat-only key.
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![],
+            None
+        ));
+        System::set_block_number(7);
+        assert_ok!(AgentRegistryPallet::heartbeat(account(2), 0));
+        let agent = AgentRegistry::<Test>::get(0).unwrap();
+        assert_eq!((agent.last_active, agent.last_heartbeat), (7, 7));
+
+        // Owner extrinsics are activity, not heartbeats.
+        System::set_block_number(9);
+        assert_ok!(AgentRegistryPallet::update_metadata(
+            account(1),
+            0,
+            b"{\"v\":2}".to_vec()
+        ));
+        let agent = AgentRegistry::<Test>::get(0).unwrap();
+        assert_eq!((agent.last_active, agent.last_heartbeat), (9, 7));
+
+        assert_ok!(AgentRegistryPallet::freeze_agent(account(1), 0));
+        assert_noop!(
+            AgentRegistryPallet::heartbeat(account(2), 0),
+            pallet_agent_registry::Error::<Test>::AgentFrozen
+        );
+        assert_ok!(AgentRegistryPallet::heartbeat(account(1), 0));
+
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            0,
+            AgentStatus::Suspended
+        ));
+        assert_noop!(
+            AgentRegistryPallet::heartbeat(account(1), 0),
+            pallet_agent_registry::Error::<Test>::AgentNotActive
+        );
+    });
+}
+
+#[test]
+fn idle_sweep_marks_silent_agents_inactive() {
+    new_test_ext().execute_with(|| {
+        for i in 0..3u8 {
+            assert_ok!(AgentRegistryPallet::register_agent(
+                account(1),
+                format!("did:claw:agent{i}").into_bytes(),
+                b"{}".to_vec()
+            ));
+        }
+        System::set_block_number(40);
+        assert_ok!(AgentRegistryPallet::heartbeat(account(1), 1));
+        // Owner activity does not keep an agent alive.
+        assert_ok!(AgentRegistryPallet::update_metadata(
+            account(1),
+            0,
+            b"{\"v\":2}".to_vec()
+        ));
+
+        // At most two agents are checked per block, resuming where the last sweep stopped.
+        run_idle(51);
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Inactive
+        );
+        assert_eq!(
+            AgentRegistry::<Test>::get(1).unwrap().status,
+            AgentStatus::Active
+        );
+        assert_eq!(
+            AgentRegistry::<Test>::get(2).unwrap().status,
+            AgentStatus::Active
+        );
+
+        run_idle(52);
+        assert_eq!(
+            AgentRegistry::<Test>::get(2).unwrap().status,
+            AgentStatus::Inactive
+        );
+
+        run_idle(90);
+        assert_eq!(
+            AgentRegistry::<Test>::get(1).unwrap().status,
+            AgentStatus::Inactive
+        );
+
+        assert_ok!(AgentRegistryPallet::heartbeat(account(1), 0));
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Active
+        );
+        System::assert_last_event(
+            crate::Event::AgentStatusChanged {
+                agent_id: 0,
+                status: AgentStatus::Active,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn unsigned_heartbeat_requires_a_fresh_signed_payload() {
+    use crate::pallet::{Call as AgentRegistryCall, HeartbeatPayload};
+    use frame_support::{pallet_prelude::*, unsigned::ValidateUnsigned};
+    use sp_runtime::testing::TestSignature;
+
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::add_operator(
+            account(1),
+            0,
+            2,
+            vec![],
+            None
+        ));
+        System::set_block_number(6);
+
+        let payload = |signer: u64, block_number: u64| HeartbeatPayload {
+            agent_id: 0,
+            signer,
+            block_number,
+        };
+        let call = |payload: HeartbeatPayload<u64, u64>, signer: u64| {
+            let signature = TestSignature(signer, payload.encode());
+            AgentRegistryCall::heartbeat_unsigned { payload, signature }
+        };
+        let validate = |call: &AgentRegistryCall<Test>| {
+            AgentRegistryPallet::validate_unsigned(TransactionSource::External, call)
+        };
+
+        // Signed by someone other than the payload's signer
+        assert_eq!(
+            validate(&call(payload(2, 6), 3)),
+            InvalidTransaction::BadProof.into()
+        );
+        // Signed by an account that is neither owner nor operator
+        assert_eq!(
+            validate(&call(payload(3, 6), 3)),
+            InvalidTransaction::Call.into()
+        );
+        // Signed for a future block
+        assert_eq!(
+            validate(&call(payload(2, 7), 2)),
+            InvalidTransaction::Stale.into()
+        );
+
+        assert!(validate(&call(payload(2, 6), 2)).is_ok());
+        assert_ok!(AgentRegistryPallet::heartbeat_unsigned(
+            RuntimeOrigin::none(),
+            payload(2, 6),
+            TestSignature(2, payload(2, 6).encode())
+        ));
+        assert_eq!(AgentRegistry::<Test>::get(0).unwrap().last_heartbeat, 6);
+
+        // The payload cannot be replayed
+        assert_eq!(
+            validate(&call(payload(2, 6), 2)),
+            InvalidTransaction::Stale.into()
+        );
+        assert_noop!(
+            AgentRegistryPallet::heartbeat_unsigned(
+                RuntimeOrigin::none(),
+                payload(2, 6),
+                TestSignature(2, payload(2, 6).encode())
+            ),
+            pallet_agent_registry::Error::<Test>::StaleHeartbeat
+        );
+    });
+}
+
+#[test]
+fn unsigned_heartbeats_respect_min_interval() {
+    use crate::pallet::{Call as AgentRegistryCall, HeartbeatPayload};
+    use frame_support::{pallet_prelude::*, unsigned::ValidateUnsigned};
+    use sp_runtime::testing::TestSignature;
+
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        let heartbeat = |block_number: u64| {
+            let payload = HeartbeatPayload {
+                agent_id: 0,
+                signer: 1,
+                block_number,
+            };
+            let signature = TestSignature(1, payload.encode());
+            (payload, signature)
+        };
+        let validate = |block_number: u64| {
+            let (payload, signature) = heartbeat(block_number);
+            AgentRegistryPallet::validate_unsigned(
+                TransactionSource::External,
+                &AgentRegistryCall::heartbeat_unsigned { payload, signature },
+            )
+        };
+
+        // Registration at block 1 counts as a heartbeat.
+        System::set_block_number(5);
+        assert_eq!(validate(5), InvalidTransaction::Stale.into());
+
+        System::set_block_number(6);
+        let valid = validate(6).unwrap();
+        assert_eq!(valid.priority, 100);
+        assert_eq!(valid.longevity, 5);
+        let (payload, signature) = heartbeat(6);
+        assert_ok!(AgentRegistryPallet::heartbeat_unsigned(
+            RuntimeOrigin::none(),
+            payload,
+            signature
+        ));
+
+        // A second heartbeat inside the interval is rejected.
+        System::set_block_number(10);
+        assert_eq!(validate(10), InvalidTransaction::Stale.into());
+        let (payload, signature) = heartbeat(10);
+        assert_noop!(
+            AgentRegistryPallet::heartbeat_unsigned(RuntimeOrigin::none(), payload, signature),
+            pallet_agent_registry::Error::<Test>::HeartbeatTooFrequent
+        );
+
+        System::set_block_number(11);
+        assert!(validate(11).is_ok());
+    });
+}
+
+// ========== Capability Tests ==========
+
+fn capability(id: &[u8], version: u32) -> Capability<Test> {
+    Capability {
+        id: BoundedVec::truncate_from(id.to_vec()),
+        version,
+        price_hint: 10,
+        rate_limit: Some(60),
+    }
+}
+
+#[test]
+fn set_capabilities_updates_index() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::set_capabilities(
+            account(1),
+            0,
+            BoundedVec::truncate_from(vec![
+                capability(b"ocr.rust", 2),
+                capability(b"translate", 1)
+            ])
+        ));
+        assert_eq!(AgentCapabilities::<Test>::get(0).len(), 2);
+        assert!(CapabilityIndex::<Test>::contains_key(
+            BoundedVec::truncate_from(b"ocr.rust".to_vec()),
+            0
+        ));
+
+        // Replacing the list drops stale index entries.
+        assert_ok!(AgentRegistryPallet::set_capabilities(
+            account(1),
+            0,
+            BoundedVec::truncate_from(vec![capability(b"ocr.rust", 3)])
+        ));
+        assert!(!CapabilityIndex::<Test>::contains_key(
+            BoundedVec::truncate_from(b"translate".to_vec()),
+            0
+        ));
+        let summaries = AgentRegistryPallet::agent_capabilities(0);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(
+            (summaries[0].id.clone(), summaries[0].version),
+            (b"ocr.rust".to_vec(), 3)
+        );
+
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert!(AgentCapabilities::<Test>::get(0).is_empty());
+        assert_eq!(CapabilityIndex::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn owner_has_its_agents_capabilities() {
+    use crate::AgentStanding;
+
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::set_capabilities(
+            account(1),
+            0,
+            BoundedVec::truncate_from(vec![capability(b"ocr.rust", 2)])
+        ));
+
+        assert!(AgentRegistryPallet::has_capability(&1, b"ocr.rust", 2));
+        assert!(!AgentRegistryPallet::has_capability(&1, b"ocr.rust", 3));
+        assert!(!AgentRegistryPallet::has_capability(&1, b"translate", 0));
+        assert!(!AgentRegistryPallet::has_capability(&2, b"ocr.rust", 0));
+    });
+}
+
+#[test]
+fn set_capabilities_validates_input() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::set_capabilities(
+                account(2),
+                0,
+                BoundedVec::truncate_from(vec![capability(b"ocr", 1)])
+            ),
+            pallet_agent_registry::Error::<Test>::NotAgentOwner
+        );
+        assert_noop!(
+            AgentRegistryPallet::set_capabilities(
+                account(1),
+                0,
+                BoundedVec::truncate_from(vec![capability(b"OCR", 1)])
+            ),
+            pallet_agent_registry::Error::<Test>::InvalidCapabilityId
+        );
+        assert_noop!(
+            AgentRegistryPallet::set_capabilities(
+                account(1),
+                0,
+                BoundedVec::truncate_from(vec![capability(b"ocr", 1), capability(b"ocr", 2)])
+            ),
+            pallet_agent_registry::Error::<Test>::DuplicateCapability
+        );
+    });
+}
+
+#[test]
+fn agents_with_capability_matches_version_and_status() {
+    new_test_ext().execute_with(|| {
+        for (i, version) in [(0u8, 1u32), (1, 2), (2, 3)] {
+            assert_ok!(AgentRegistryPallet::register_agent(
+                account(1),
+                format!("did:claw:agent{i}").into_bytes(),
+                b"{}".to_vec()
+            ));
+            assert_ok!(AgentRegistryPallet::set_capabilities(
+                account(1),
+                i.into(),
+                BoundedVec::truncate_from(vec![capability(b"ocr.rust", version)])
+            ));
+        }
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            2,
+            AgentStatus::Suspended
+        ));
+
+        let ids = |min_version, start, limit| -> Vec<u64> {
+            AgentRegistryPallet::agents_with_capability(b"ocr.rust", min_version, start, limit)
+                .agents
+                .into_iter()
+                .map(|agent| agent.agent_id)
+                .collect()
+        };
+        assert_eq!(ids(0, 0, 10), vec![0, 1]);
+        assert_eq!(ids(2, 0, 10), vec![1]);
+        assert_eq!(ids(0, 1, 10), vec![1]);
+        assert_eq!(ids(0, 0, 1), vec![0]);
+        assert_eq!(
+            AgentRegistryPallet::agents_with_capability(b"ocr.rust", 0, 0, 1).next,
+            Some(1)
+        );
+        assert!(
+            AgentRegistryPallet::agents_with_capability(b"translate", 0, 0, 10)
+                .agents
+                .is_empty()
+        );
+    });
+}
+
+#[test]
+fn agent_listings_scan_a_bounded_window() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:first".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_ok!(AgentRegistryPallet::set_capabilities(
+            account(1),
+            0,
+            BoundedVec::truncate_from(vec![capability(b"ocr.rust", 1)])
+        ));
+        // Agent IDs past the scan window, as if the registry had grown that large
+        AgentCount::<Test>::put(MAX_SCANNED_AGENTS + 2);
+        AgentRegistry::<Test>::insert(
+            MAX_SCANNED_AGENTS + 1,
+            AgentRegistry::<Test>::get(0).unwrap(),
+        );
+
+        let page = AgentRegistryPallet::list_agents(None, 0, 10);
+        assert_eq!(
+            page.agents.iter().map(|a| a.agent_id).collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert_eq!(page.next, Some(MAX_SCANNED_AGENTS));
+        let page = AgentRegistryPallet::list_agents(None, MAX_SCANNED_AGENTS, 10);
+        assert_eq!(
+            page.agents.iter().map(|a| a.agent_id).collect::<Vec<_>>(),
+            vec![MAX_SCANNED_AGENTS + 1]
+        );
+        assert_eq!(page.next, None);
+
+        let page = AgentRegistryPallet::agents_with_capability(b"ocr.rust", 0, 0, 10);
+        assert_eq!(page.agents.len(), 1);
+        assert_eq!(page.next, Some(MAX_SCANNED_AGENTS));
+    });
+}
+
+// ========== Suspension Tests ==========
+
+#[test]
+fn owner_cannot_clear_admin_suspension() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::suspend_agent(account(1), 0, b"spam".to_vec(), None),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(AgentRegistryPallet::suspend_agent(
+            RuntimeOrigin::root(),
+            0,
+            b"spam".to_vec(),
+            None
+        ));
+        System::assert_last_event(
+            crate::Event::AgentSuspended {
+                agent_id: 0,
+                reason: b"spam".to_vec(),
+                expires_at: None,
+            }
+            .into(),
+        );
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Suspended
+        );
+
+        assert_noop!(
+            AgentRegistryPallet::set_agent_status(account(1), 0, AgentStatus::Active),
+            pallet_agent_registry::Error::<Test>::AgentSuspendedByAdmin
+        );
+        assert_noop!(
+            AgentRegistryPallet::heartbeat(account(1), 0),
+            pallet_agent_registry::Error::<Test>::AgentNotActive
+        );
+
+        assert_ok!(AgentRegistryPallet::lift_suspension(
+            RuntimeOrigin::root(),
+            0
+        ));
+        assert!(Suspensions::<Test>::get(0).is_none());
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Active
+        );
+        assert_noop!(
+            AgentRegistryPallet::lift_suspension(RuntimeOrigin::root(), 0),
+            pallet_agent_registry::Error::<Test>::NotSuspended
+        );
+    });
+}
+
+#[test]
+fn admin_suspension_counts_against_the_owner() {
+    use crate::AgentStanding;
+
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+
+        // A voluntary pause does not count against the owner
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            0,
+            AgentStatus::Suspended
+        ));
+        assert!(!AgentRegistryPallet::is_suspended_agent(&1));
+
+        assert_ok!(AgentRegistryPallet::suspend_agent(
+            RuntimeOrigin::root(),
+            0,
+            b"spam".to_vec(),
+            None
+        ));
+        assert!(AgentRegistryPallet::is_suspended_agent(&1));
+        assert!(!AgentRegistryPallet::is_suspended_agent(&2));
+
+        assert_ok!(AgentRegistryPallet::lift_suspension(
+            RuntimeOrigin::root(),
+            0
+        ));
+        assert!(!AgentRegistryPallet::is_suspended_agent(&1));
+    });
+}
+
+#[test]
+fn admin_suspension_expires() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_noop!(
+            AgentRegistryPallet::suspend_agent(RuntimeOrigin::root(), 0, vec![], Some(1)),
+            pallet_agent_registry::Error::<Test>::InvalidExpiry
+        );
+        assert_noop!(
+            AgentRegistryPallet::suspend_agent(RuntimeOrigin::root(), 0, vec![b'x'; 33], None),
+            pallet_agent_registry::Error::<Test>::SuspensionReasonTooLong
+        );
+        assert_ok!(AgentRegistryPallet::suspend_agent(
+            RuntimeOrigin::root(),
+            0,
+            b"review".to_vec(),
+            Some(20)
+        ));
+
+        run_idle(19);
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Suspended
+        );
+        run_idle(20);
+        assert_eq!(
+            AgentRegistry::<Test>::get(0).unwrap().status,
+            AgentStatus::Active
+        );
+        assert!(Suspensions::<Test>::get(0).is_none());
+
+        // Once expired, the owner may clear it before the sweep gets there.
+        assert_ok!(AgentRegistryPallet::suspend_agent(
+            RuntimeOrigin::root(),
+            0,
+            b"review".to_vec(),
+            Some(30)
+        ));
+        System::set_block_number(30);
+        assert_ok!(AgentRegistryPallet::set_agent_status(
+            account(1),
+            0,
+            AgentStatus::Active
+        ));
+        assert!(Suspensions::<Test>::get(0).is_none());
+    });
+}
+
+#[test]
+fn owners_can_only_pause_or_resume() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        for status in [AgentStatus::Inactive, AgentStatus::Deregistered] {
+            assert_noop!(
+                AgentRegistryPallet::set_agent_status(account(1), 0, status),
+                pallet_agent_registry::Error::<Test>::InvalidStatus
+            );
+        }
+    });
+}
+
+// ========== Purge Tests ==========
+
+#[test]
+fn purge_agent_leaves_tombstone_and_frees_owner_slot() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        add_key(b"key-1", vec![1u8; 32]);
+        assert_noop!(
+            AgentRegistryPallet::purge_agent(account(1), 0),
+            pallet_agent_registry::Error::<Test>::AgentNotDeregistered
+        );
+
+        System::set_block_number(5);
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert_ok!(AgentRegistryPallet::purge_agent(account(1), 0));
+        System::assert_last_event(
+            crate::Event::AgentPurged {
+                agent_id: 0,
+                owner: 1,
+            }
+            .into(),
+        );
+
+        assert!(AgentRegistry::<Test>::get(0).is_none());
+        assert!(OwnerAgents::<Test>::get(1).is_empty());
+        assert!(DidDocuments::<Test>::get(0).verification_methods.is_empty());
+        assert!(AgentRegistryPallet::did_document(b"did:claw:agent001").is_none());
+        let tombstone = AgentTombstones::<Test>::get(0).unwrap();
+        assert_eq!(tombstone.owner, 1);
+        assert_eq!(tombstone.did.to_vec(), b"did:claw:agent001".to_vec());
+        assert_eq!((tombstone.deregistered_at, tombstone.purged_at), (5, 5));
+        assert!(DidReleaseAt::<Test>::get(tombstone.did).is_none());
+
+        // Key history survives the purge.
+        assert!(AgentRegistryPallet::key_valid_at(0, &[1u8; 32], 2));
+
+        // The DID is retired for good, even after its cooldown, and IDs are not
+        // reused.
+        for block in [5, 15, 1000] {
+            System::set_block_number(block);
+            assert_noop!(
+                AgentRegistryPallet::register_agent(
+                    account(1),
+                    b"did:claw:agent001".to_vec(),
+                    b"{}".to_vec()
+                ),
+                pallet_agent_registry::Error::<Test>::DidRetired
+            );
+        }
+        assert_ok!(AgentRegistryPallet::register_agent(
+            account(1),
+            b"did:claw:agent002".to_vec(),
+            b"{}".to_vec()
+        ));
+        assert_eq!(OwnerAgents::<Test>::get(1).to_vec(), vec![1]);
+        assert!(AgentRegistryPallet::did_document(b"did:claw:agent001").is_none());
+    });
+}
+
+#[test]
+fn others_can_purge_after_grace_period() {
+    new_test_ext().execute_with(|| {
+        setup_funded_agent();
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+
+        System::set_block_number(20);
+        assert_noop!(
+            AgentRegistryPallet::purge_agent(account(2), 0),
+            pallet_agent_registry::Error::<Test>::PurgeTooEarly
+        );
+        System::set_block_number(21);
+        assert_ok!(AgentRegistryPallet::purge_agent(account(2), 0));
+        assert_noop!(
+            AgentRegistryPallet::purge_agent(account(2), 0),
+            pallet_agent_registry::Error::<Test>::AgentNotFound
+        );
+    });
+}
+
+// ========== ERC-8004 Tests ==========
+
+fn register_test_agent(owner: u64, did: &[u8]) {
+    assert_ok!(AgentRegistryPallet::register_agent(
+        account(owner),
+        did.to_vec(),
+        b"{}".to_vec()
+    ));
+}
+
+#[test]
+fn agent_domain_is_unique_and_released_on_deregister() {
+    new_test_ext().execute_with(|| {
+        register_test_agent(1, b"did:claw:agent001");
+        register_test_agent(2, b"did:claw:agent002");
+        let domain = b"agent.example.com".to_vec();
+
+        for invalid in [
+            b"".to_vec(),
+            b"Agent.example.com".to_vec(),
+            b"agent..com".to_vec(),
+            b"-agent.com".to_vec(),
+            b"agent.example.com.".to_vec(),
+            vec![b'a'; 33],
+        ] {
+            assert_noop!(
+                AgentRegistryPallet::set_agent_domain(account(1), 0, Some(invalid)),
+                pallet_agent_registry::Error::<Test>::InvalidDomain
+            );
+        }
+        assert_noop!(
+            AgentRegistryPallet::set_agent_domain(account(2), 0, Some(domain.clone())),
+            pallet_agent_registry::Error::<Test>::NotAgentController
+        );
+
+        assert_ok!(AgentRegistryPallet::set_agent_domain(
+            account(1),
+            0,
+            Some(domain.clone())
+        ));
+        System::assert_last_event(
+            crate::Event::AgentDomainSet {
+                agent_id: 0,
+                domain: Some(domain.clone()),
+            }
+            .into(),
+        );
+        let agent = AgentRegistryPallet::agent_by_domain(&domain).unwrap();
+        assert_eq!(agent.agent_id, 0);
+        assert_eq!(agent.domain, Some(domain.clone()));
+        assert_noop!(
+            AgentRegistryPallet::set_agent_domain(account(2), 1, Some(domain.clone())),
+            pallet_agent_registry::Error::<Test>::DomainAlreadyRegistered
+        );
+
+        // Changing the domain releases the old one.
+        assert_ok!(AgentRegistryPallet::set_agent_domain(
+            account(1),
+            0,
+            Some(b"new.example.com".to_vec())
+        ));
+        assert!(AgentRegistryPallet::agent_by_domain(&domain).is_none());
+        assert_ok!(AgentRegistryPallet::set_agent_domain(
+            account(2),
+            1,
+            Some(domain.clone())
+        ));
+
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(2), 1));
+        assert!(AgentRegistryPallet::agent_by_domain(&domain).is_none());
+        assert_eq!(AgentRegistryPallet::agent(1).unwrap().domain, None);
+        assert_ok!(AgentRegistryPallet::set_agent_domain(
+            account(1),
+            0,
+            Some(domain)
+        ));
+    });
+}
+
+#[test]
+fn issuers_attest_and_revoke_claims() {
+    new_test_ext().execute_with(|| {
+        register_test_agent(1, b"did:claw:agent001");
+        let schema = [1u8; 32];
+
+        assert_noop!(
+            AgentRegistryPallet::attest(account(2), 0, schema, [7u8; 32], None),
+            pallet_agent_registry::Error::<Test>::NotIssuer
+        );
+        assert_noop!(
+            AgentRegistryPallet::register_issuer(account(2), 2, b"Auditor".to_vec()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(AgentRegistryPallet::register_issuer(
+            RuntimeOrigin::root(),
+            2,
+            b"Auditor".to_vec()
+        ));
+        assert_ok!(AgentRegistryPallet::register_issuer(
+            RuntimeOrigin::root(),
+            1,
+            b"Owner".to_vec()
+        ));
+
+        // The agent's owner cannot vouch for its own agent
+        assert_noop!(
+            AgentRegistryPallet::attest(account(1), 0, schema, [7u8; 32], None),
+            pallet_agent_registry::Error::<Test>::SelfAttestation
+        );
+        assert_noop!(
+            AgentRegistryPallet::attest(account(2), 0, schema, [7u8; 32], Some(1)),
+            pallet_agent_registry::Error::<Test>::InvalidExpiry
+        );
+
+        assert_ok!(AgentRegistryPallet::attest(
+            account(2),
+            0,
+            schema,
+            [7u8; 32],
+            Some(10)
+        ));
+        assert!(AgentRegistryPallet::has_valid_attestation(0, schema, &2));
+        assert!(!AgentRegistryPallet::has_valid_attestation(
+            0, [2u8; 32], &2
+        ));
+        assert!(!AgentRegistryPallet::has_valid_attestation(0, schema, &1));
+
+        // Claims lapse at their expiry block
+        System::set_block_number(10);
+        assert!(!AgentRegistryPallet::has_valid_attestation(0, schema, &2));
+        assert!(!AgentRegistryPallet::attestations(0)[0].valid);
+
+        // Re-attesting renews the claim, and revoking invalidates it
+        assert_ok!(AgentRegistryPallet::attest(
+            account(2),
+            0,
+            schema,
+            [8u8; 32],
+            None
+        ));
+        assert!(AgentRegistryPallet::has_valid_attestation(0, schema, &2));
+        assert_ok!(AgentRegistryPallet::revoke_attestation(
+            account(2),
+            0,
+            schema
+        ));
+        assert!(!AgentRegistryPallet::has_valid_attestation(0, schema, &2));
+        assert_noop!(
+            AgentRegistryPallet::revoke_attestation(account(2), 0, schema),
+            pallet_agent_registry::Error::<Test>::AttestationAlreadyRevoked
+        );
+
+        let attestations = AgentRegistryPallet::attestations(0);
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(attestations[0].claim_hash, [8u8; 32]);
+        assert_eq!(attestations[0].revoked_at, Some(10));
+    });
+}
+
+#[test]
+fn attestations_follow_issuer_and_agent_lifecycle() {
+    new_test_ext().execute_with(|| {
+        register_test_agent(1, b"did:claw:agent001");
+        assert_ok!(AgentRegistryPallet::register_issuer(
+            RuntimeOrigin::root(),
+            2,
+            b"Auditor".to_vec()
+        ));
+        for schema in [[1u8; 32], [2u8; 32]] {
+            assert_ok!(AgentRegistryPallet::attest(
+                account(2),
+                0,
+                schema,
+                [7u8; 32],
+                None
+            ));
+        }
+        assert_noop!(
+            AgentRegistryPallet::attest(account(2), 0, [3u8; 32], [7u8; 32], None),
+            pallet_agent_registry::Error::<Test>::TooManyAttestations
+        );
+
+        // Removing the issuer invalidates its claims without deleting them
+        assert_ok!(AgentRegistryPallet::remove_issuer(RuntimeOrigin::root(), 2));
+        assert!(!AgentRegistryPallet::has_valid_attestation(
+            0, [1u8; 32], &2
+        ));
+        assert_eq!(AgentRegistryPallet::attestations(0).len(), 2);
+        assert_ok!(AgentRegistryPallet::register_issuer(
+            RuntimeOrigin::root(),
+            2,
+            b"Auditor".to_vec()
+        ));
+        assert!(AgentRegistryPallet::has_valid_attestation(0, [1u8; 32], &2));
+
+        // Deregistering invalidates the claims, purging removes them
+        assert_ok!(AgentRegistryPallet::deregister_agent(account(1), 0));
+        assert!(!AgentRegistryPallet::has_valid_attestation(
+            0, [1u8; 32], &2
+        ));
+        assert_ok!(AgentRegistryPallet::purge_agent(account(1), 0));
+        assert!(AgentRegistryPallet::attestations(0).is_empty());
+        assert_eq!(pallet_agent_registry::AttestationCount::<Test>::get(0), 0);
+    });
+}
@@ -0,0 +1,184 @@
+//! RPC interface for the Messaging pallet.
+//!
+//! Exposes the `MessagingApi` runtime API as `messaging_*` JSON-RPC methods.
+//! `messaging_subscribeInbox` streams messages arriving in an agent's inbox as
+//! new best blocks are imported.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use futures::{future, FutureExt, StreamExt};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+    PendingSubscriptionSink, SubscriptionMessage,
+};
+use pallet_messaging_runtime_api::{AgentId, MessageId, MessageSummary, TaskId};
+use sc_client_api::BlockchainEvents;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::traits::SpawnNamed;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_messaging_runtime_api::MessagingApi as MessagingRuntimeApi;
+
+/// Maximum (and default) number of entries returned by a paginated call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait MessagingApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// Unacknowledged messages in an agent's inbox with IDs from `start`, oldest first.
+    #[method(name = "messaging_inbox")]
+    fn inbox(
+        &self,
+        agent_id: AgentId,
+        start: Option<MessageId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<MessageSummary<AccountId, Balance, BlockNumber>>>;
+
+    /// Unacknowledged messages threaded under a task, oldest first.
+    #[method(name = "messaging_thread")]
+    fn thread(
+        &self,
+        task_id: TaskId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<MessageSummary<AccountId, Balance, BlockNumber>>>;
+
+    /// Stream messages arriving in an agent's inbox from now on.
+    #[subscription(
+        name = "messaging_subscribeInbox" => "messaging_inboxMessage",
+        unsubscribe = "messaging_unsubscribeInbox",
+        item = MessageSummary<AccountId, Balance, BlockNumber>
+    )]
+    fn subscribe_inbox(&self, agent_id: AgentId);
+}
+
+/// Provides RPC methods to read agent inboxes.
+pub struct Messaging<C, Block> {
+    /// Shared reference to the client.
+    client: Arc<C>,
+    /// Spawns the tasks feeding subscriptions.
+    executor: Arc<dyn SpawnNamed>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Messaging<C, Block> {
+    /// Creates a new instance of the Messaging RPC helper.
+    pub fn new(client: Arc<C>, executor: Arc<dyn SpawnNamed>) -> Self {
+        Self {
+            client,
+            executor,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query messages.",
+        Some(error.to_string()),
+    )
+}
+
+fn page_size(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    MessagingApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for Messaging<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + BlockchainEvents<Block>
+        + Send
+        + Sync
+        + 'static,
+    C::Api: MessagingRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + serde::Serialize + Send + Sync + 'static,
+    Balance: Codec + serde::Serialize + Send + Sync + 'static,
+    BlockNumber: Codec + serde::Serialize + Send + Sync + 'static,
+{
+    fn inbox(
+        &self,
+        agent_id: AgentId,
+        start: Option<MessageId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<MessageSummary<AccountId, Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .inbox(
+                at_hash,
+                agent_id,
+                start.unwrap_or_default(),
+                page_size(limit),
+            )
+            .map_err(runtime_error)
+    }
+
+    fn thread(
+        &self,
+        task_id: TaskId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<MessageSummary<AccountId, Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .thread(at_hash, task_id)
+            .map_err(runtime_error)
+    }
+
+    fn subscribe_inbox(&self, pending: PendingSubscriptionSink, agent_id: AgentId) {
+        let client = self.client.clone();
+
+        let task = async move {
+            let best_hash = client.info().best_hash;
+            let mut next = match client.runtime_api().next_message_id(best_hash) {
+                Ok(next) => next,
+                Err(error) => {
+                    pending.reject(runtime_error(error)).await;
+                    return;
+                }
+            };
+            let Ok(sink) = pending.accept().await else {
+                return;
+            };
+
+            // Messages sent up to the best block at subscription time are
+            // already visible through `messaging_inbox`.
+            let mut best_blocks = client
+                .import_notification_stream()
+                .filter(|notification| future::ready(notification.is_new_best));
+            while let Some(notification) = best_blocks.next().await {
+                let Ok(messages) =
+                    client
+                        .runtime_api()
+                        .inbox(notification.hash, agent_id, next, MAX_PAGE_SIZE)
+                else {
+                    return;
+                };
+                for message in messages {
+                    next = message.message_id.saturating_add(1);
+                    let Ok(item) = SubscriptionMessage::from_json(&message) else {
+                        return;
+                    };
+                    if sink.send(item).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        };
+
+        self.executor
+            .spawn("messaging-inbox-subscription", Some("rpc"), task.boxed());
+    }
+}
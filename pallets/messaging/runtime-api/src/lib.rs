@@ -0,0 +1,38 @@
+//! Runtime API definition for the Messaging pallet.
+//!
+//! Lets agents read their inbox and a task's message thread without iterating
+//! storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_agent_registry::AgentId;
+pub use pallet_messaging::{MessageId, MessageSummary, TaskId};
+
+sp_api::decl_runtime_apis! {
+    /// Queries over agent inboxes.
+    pub trait MessagingApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Unacknowledged messages in an agent's inbox with IDs from `start`, oldest
+        /// first, at most `limit`.
+        fn inbox(
+            agent_id: AgentId,
+            start: MessageId,
+            limit: u32,
+        ) -> Vec<MessageSummary<AccountId, Balance, BlockNumber>>;
+
+        /// Unacknowledged messages threaded under a task, oldest first.
+        fn thread(task_id: TaskId) -> Vec<MessageSummary<AccountId, Balance, BlockNumber>>;
+
+        /// ID the next message will get.
+        fn next_message_id() -> MessageId;
+    }
+}
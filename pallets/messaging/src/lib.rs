@@ -0,0 +1,530 @@
+//! # Messaging Pallet
+//!
+//! On-chain inboxes for agent-to-agent messages, addressed by agent ID.
+//!
+//! ## Overview
+//!
+//! This pallet provides functionality for:
+//! - Sending small encrypted payloads, or a content hash plus a pointer to
+//!   off-chain content, from one agent to another
+//! - Bounded per-agent inboxes backed by a per-message deposit, refunded when the
+//!   recipient acknowledges the message and forfeited when it rejects it
+//! - Threading messages under a task in pallet-task-market while it is negotiated
+//!
+//! ## Messages
+//!
+//! The owner or agent account of the sending agent (see `AgentDirectory`) calls
+//! `send_message`, which reserves `MessageDepositBase` plus `MessageDepositPerByte`
+//! for each byte of content from the caller. Content is opaque to the chain:
+//! without a `content_hash` it is the payload itself, encrypted to the recipient's
+//! key; with one, it is a pointer (e.g. a URI) to off-chain content with that hash.
+//!
+//! Each agent holds at most `MaxInboxSize` unacknowledged messages. The recipient
+//! frees a slot with `acknowledge`, which removes the message and refunds the
+//! sender's deposit, or with `reject`, which removes it and slashes the deposit.
+//! Rejecting is how a recipient answers spam: filling an inbox costs the sender
+//! every deposit the recipient chooses to reject. If the recipient has been
+//! deregistered, the sender can acknowledge its own message to reclaim the deposit.
+//!
+//! ## Task Threads
+//!
+//! A message may name a task, linking it to that task's thread. Only tasks that
+//! are still live (not approved, cancelled or expired) accept new messages, and
+//! only between the task's poster, bidders and assignee: the owner or agent
+//! account of both the sender and the recipient must be one of them. Both are
+//! checked through `NegotiableTasks`. `thread` lists a task's unacknowledged
+//! messages.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `send_message` - Post a message to another agent's inbox
+//! - `acknowledge` - Remove a message from an inbox and refund its deposit
+//! - `reject` - Remove an unwanted message from an inbox and slash its deposit
+//!
+//! ### Runtime API Helpers
+//!
+//! - `inbox` - Unacknowledged messages of an agent, from a message ID on
+//! - `thread` - Unacknowledged messages linked to a task
+//! - `next_message_id` - ID the next message will get, for following new messages
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+/// Tasks messages can be threaded under, such as those in pallet-task-market.
+///
+/// The `()` implementation has no tasks.
+pub trait NegotiableTasks<AccountId> {
+    /// Whether `task_id` exists and is still live.
+    fn is_negotiable(task_id: u64) -> bool;
+
+    /// Whether `account` is the poster, a bidder or the assignee of `task_id`.
+    fn is_negotiator(task_id: u64, account: &AccountId) -> bool;
+}
+
+impl<AccountId> NegotiableTasks<AccountId> for () {
+    fn is_negotiable(_task_id: u64) -> bool {
+        false
+    }
+
+    fn is_negotiator(_task_id: u64, _account: &AccountId) -> bool {
+        false
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Currency, Imbalance, ReservableCurrency},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_agent_registry::{AgentDirectory, AgentId};
+    use sp_runtime::traits::Saturating;
+
+    /// Type alias for message IDs (sequential u64).
+    pub type MessageId = u64;
+
+    /// ID of a task in pallet-task-market.
+    pub type TaskId = u64;
+
+    /// Hash of off-chain message content.
+    pub type ContentHash = [u8; 32];
+
+    /// Type alias for balance (compatible with pallet-balances).
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// A message waiting in an agent's inbox.
+    #[derive(
+        CloneNoBound,
+        Encode,
+        Decode,
+        EqNoBound,
+        PartialEqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Message<T: Config> {
+        /// Sending agent.
+        pub sender: AgentId,
+        /// Receiving agent.
+        pub recipient: AgentId,
+        /// Account that sent the message and holds its deposit.
+        pub depositor: T::AccountId,
+        /// Encrypted payload, or a pointer to the content if `content_hash` is set.
+        pub content: BoundedVec<u8, T::MaxContentLength>,
+        /// Hash of the off-chain content `content` points to.
+        pub content_hash: Option<ContentHash>,
+        /// Task whose thread the message belongs to.
+        pub task_id: Option<TaskId>,
+        /// Block at which the message was sent.
+        pub sent_at: BlockNumberFor<T>,
+        /// Deposit reserved from `depositor`.
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// Message view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct MessageSummary<AccountId, Balance, BlockNumber> {
+        /// The message ID.
+        pub message_id: MessageId,
+        /// Sending agent.
+        pub sender: AgentId,
+        /// Receiving agent.
+        pub recipient: AgentId,
+        /// Account that sent the message and holds its deposit.
+        pub depositor: AccountId,
+        /// Encrypted payload, or a pointer to the content if `content_hash` is set.
+        pub content: Vec<u8>,
+        /// Hash of the off-chain content `content` points to.
+        pub content_hash: Option<ContentHash>,
+        /// Task whose thread the message belongs to.
+        pub task_id: Option<TaskId>,
+        /// Block at which the message was sent.
+        pub sent_at: BlockNumber,
+        /// Deposit reserved from `depositor`.
+        pub deposit: Balance,
+    }
+
+    /// Message summary type for a given runtime.
+    pub type MessageSummaryOf<T> =
+        MessageSummary<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching runtime event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Currency used for message deposits.
+        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+
+        /// Registered agents and who may act as them.
+        type Agents: AgentDirectory<Self::AccountId>;
+
+        /// Tasks messages can be threaded under.
+        type Tasks: NegotiableTasks<Self::AccountId>;
+
+        /// Maximum length of a message's content.
+        #[pallet::constant]
+        type MaxContentLength: Get<u32>;
+
+        /// Maximum number of unacknowledged messages in an agent's inbox.
+        #[pallet::constant]
+        type MaxInboxSize: Get<u32>;
+
+        /// Deposit reserved for every message.
+        #[pallet::constant]
+        type MessageDepositBase: Get<BalanceOf<Self>>;
+
+        /// Additional deposit reserved per byte of content.
+        #[pallet::constant]
+        type MessageDepositPerByte: Get<BalanceOf<Self>>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    // ========== Storage ==========
+
+    /// Unacknowledged messages by ID.
+    #[pallet::storage]
+    #[pallet::getter(fn messages)]
+    pub type Messages<T: Config> =
+        StorageMap<_, Blake2_128Concat, MessageId, Message<T>, OptionQuery>;
+
+    /// ID the next message will get.
+    #[pallet::storage]
+    #[pallet::getter(fn next_message_id)]
+    pub type NextMessageId<T: Config> = StorageValue<_, MessageId, ValueQuery>;
+
+    /// Unacknowledged messages in each agent's inbox.
+    #[pallet::storage]
+    pub type Inboxes<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, AgentId, Twox64Concat, MessageId, (), OptionQuery>;
+
+    /// Number of unacknowledged messages in each agent's inbox.
+    #[pallet::storage]
+    #[pallet::getter(fn inbox_size)]
+    pub type InboxSize<T: Config> = StorageMap<_, Blake2_128Concat, AgentId, u32, ValueQuery>;
+
+    /// Unacknowledged messages threaded under each task.
+    #[pallet::storage]
+    pub type TaskThreads<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, TaskId, Twox64Concat, MessageId, (), OptionQuery>;
+
+    // ========== Events ==========
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A message was posted to an agent's inbox.
+        MessageSent {
+            message_id: MessageId,
+            sender: AgentId,
+            recipient: AgentId,
+            task_id: Option<TaskId>,
+        },
+        /// A message was acknowledged and its deposit refunded.
+        MessageAcknowledged {
+            message_id: MessageId,
+            recipient: AgentId,
+        },
+        /// A message was rejected and its sender's deposit slashed.
+        MessageRejected {
+            message_id: MessageId,
+            sender: AgentId,
+            recipient: AgentId,
+            slashed: BalanceOf<T>,
+        },
+    }
+
+    // ========== Errors ==========
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The caller is neither the owner nor the agent account of the agent.
+        NotAgentController,
+        /// The recipient is not a registered agent.
+        RecipientNotFound,
+        /// An agent cannot message itself.
+        SelfMessage,
+        /// The content is empty.
+        EmptyContent,
+        /// The content exceeds `MaxContentLength`.
+        ContentTooLong,
+        /// The task does not exist or is no longer live.
+        TaskNotNegotiable,
+        /// The recipient's inbox already holds `MaxInboxSize` messages.
+        InboxFull,
+        /// Cannot reserve the message deposit.
+        InsufficientDeposit,
+        /// The message does not exist or was already acknowledged.
+        MessageNotFound,
+        /// The sender or recipient is not the poster, a bidder or the assignee of
+        /// the task.
+        NotTaskParticipant,
+    }
+
+    // ========== Extrinsics ==========
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Post a message from agent `sender` to agent `recipient`'s inbox.
+        ///
+        /// Callable by the owner or agent account of `sender`, who pays the
+        /// message deposit. A message threaded under a task must be between two of
+        /// its participants.
+        ///
+        /// # Arguments
+        /// * `sender` - The sending agent
+        /// * `recipient` - The receiving agent
+        /// * `content` - Encrypted payload, or a pointer if `content_hash` is given
+        /// * `content_hash` - Hash of the off-chain content `content` points to
+        /// * `task_id` - Task whose thread the message belongs to
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(15, 5))]
+        pub fn send_message(
+            origin: OriginFor<T>,
+            sender: AgentId,
+            recipient: AgentId,
+            content: Vec<u8>,
+            content_hash: Option<ContentHash>,
+            task_id: Option<TaskId>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                T::Agents::is_controller(&who, sender),
+                Error::<T>::NotAgentController
+            );
+            ensure!(sender != recipient, Error::<T>::SelfMessage);
+            ensure!(
+                T::Agents::is_registered(recipient),
+                Error::<T>::RecipientNotFound
+            );
+            ensure!(!content.is_empty(), Error::<T>::EmptyContent);
+            let content: BoundedVec<u8, T::MaxContentLength> =
+                content.try_into().map_err(|_| Error::<T>::ContentTooLong)?;
+            if let Some(task_id) = task_id {
+                ensure!(
+                    T::Tasks::is_negotiable(task_id),
+                    Error::<T>::TaskNotNegotiable
+                );
+                ensure!(
+                    Self::is_participant(task_id, sender)
+                        && Self::is_participant(task_id, recipient),
+                    Error::<T>::NotTaskParticipant
+                );
+            }
+
+            InboxSize::<T>::try_mutate(recipient, |size| -> DispatchResult {
+                ensure!(*size < T::MaxInboxSize::get(), Error::<T>::InboxFull);
+                *size = size.saturating_add(1);
+                Ok(())
+            })?;
+
+            let deposit = Self::message_deposit(content.len());
+            T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+
+            let message_id = NextMessageId::<T>::get();
+            NextMessageId::<T>::put(message_id.saturating_add(1));
+
+            Inboxes::<T>::insert(recipient, message_id, ());
+            if let Some(task_id) = task_id {
+                TaskThreads::<T>::insert(task_id, message_id, ());
+            }
+            Messages::<T>::insert(
+                message_id,
+                Message {
+                    sender,
+                    recipient,
+                    depositor: who,
+                    content,
+                    content_hash,
+                    task_id,
+                    sent_at: <frame_system::Pallet<T>>::block_number(),
+                    deposit,
+                },
+            );
+
+            Self::deposit_event(Event::MessageSent {
+                message_id,
+                sender,
+                recipient,
+                task_id,
+            });
+
+            Ok(())
+        }
+
+        /// Acknowledge a message, removing it from the inbox and refunding the
+        /// sender's deposit.
+        ///
+        /// Callable by the owner or agent account of the recipient, or by the
+        /// sender once the recipient has been deregistered.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 4))]
+        pub fn acknowledge(origin: OriginFor<T>, message_id: MessageId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let message = Messages::<T>::get(message_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(
+                T::Agents::is_controller(&who, message.recipient)
+                    || (who == message.depositor && !T::Agents::is_registered(message.recipient)),
+                Error::<T>::NotAgentController
+            );
+
+            Self::remove_message(message_id, &message);
+            T::Currency::unreserve(&message.depositor, message.deposit);
+
+            Self::deposit_event(Event::MessageAcknowledged {
+                message_id,
+                recipient: message.recipient,
+            });
+
+            Ok(())
+        }
+
+        /// Reject a message, removing it from the inbox and slashing the sender's
+        /// deposit.
+        ///
+        /// Callable by the owner or agent account of the recipient.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 5))]
+        pub fn reject(origin: OriginFor<T>, message_id: MessageId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let message = Messages::<T>::get(message_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(
+                T::Agents::is_controller(&who, message.recipient),
+                Error::<T>::NotAgentController
+            );
+
+            Self::remove_message(message_id, &message);
+            // The slashed deposit is burned.
+            let slashed = T::Currency::slash_reserved(&message.depositor, message.deposit)
+                .0
+                .peek();
+
+            Self::deposit_event(Event::MessageRejected {
+                message_id,
+                sender: message.sender,
+                recipient: message.recipient,
+                slashed,
+            });
+
+            Ok(())
+        }
+    }
+
+    // ========== Internal Functions ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Remove `message` from storage, its recipient's inbox and its task thread.
+        fn remove_message(message_id: MessageId, message: &Message<T>) {
+            Messages::<T>::remove(message_id);
+            Inboxes::<T>::remove(message.recipient, message_id);
+            InboxSize::<T>::mutate(message.recipient, |size| *size = size.saturating_sub(1));
+            if let Some(task_id) = message.task_id {
+                TaskThreads::<T>::remove(task_id, message_id);
+            }
+        }
+
+        /// Whether the owner or agent account of `agent_id` takes part in `task_id`.
+        fn is_participant(task_id: TaskId, agent_id: AgentId) -> bool {
+            T::Agents::controllers(agent_id)
+                .iter()
+                .any(|account| T::Tasks::is_negotiator(task_id, account))
+        }
+
+        /// Deposit for a message whose content takes `bytes` bytes.
+        pub fn message_deposit(bytes: usize) -> BalanceOf<T> {
+            let bytes: u32 = bytes.try_into().unwrap_or(u32::MAX);
+            T::MessageDepositBase::get()
+                .saturating_add(T::MessageDepositPerByte::get().saturating_mul(bytes.into()))
+        }
+
+        fn summarize(message_id: MessageId, message: Message<T>) -> MessageSummaryOf<T> {
+            MessageSummary {
+                message_id,
+                sender: message.sender,
+                recipient: message.recipient,
+                depositor: message.depositor,
+                content: message.content.into_inner(),
+                content_hash: message.content_hash,
+                task_id: message.task_id,
+                sent_at: message.sent_at,
+                deposit: message.deposit,
+            }
+        }
+
+        /// Summaries of `message_ids`, in ID order.
+        fn summarize_all(mut message_ids: Vec<MessageId>) -> Vec<MessageSummaryOf<T>> {
+            message_ids.sort_unstable();
+            message_ids
+                .into_iter()
+                .filter_map(|id| Messages::<T>::get(id).map(|message| Self::summarize(id, message)))
+                .collect()
+        }
+    }
+
+    // ========== Runtime API Helpers ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Unacknowledged messages in `agent_id`'s inbox with IDs from `start`,
+        /// oldest first, at most `limit`.
+        pub fn inbox(agent_id: AgentId, start: MessageId, limit: u32) -> Vec<MessageSummaryOf<T>> {
+            let message_ids = Inboxes::<T>::iter_key_prefix(agent_id)
+                .filter(|id| *id >= start)
+                .collect();
+            let mut messages = Self::summarize_all(message_ids);
+            messages.truncate(limit as usize);
+            messages
+        }
+
+        /// Unacknowledged messages threaded under `task_id`, oldest first.
+        pub fn thread(task_id: TaskId) -> Vec<MessageSummaryOf<T>> {
+            Self::summarize_all(TaskThreads::<T>::iter_key_prefix(task_id).collect())
+        }
+    }
+
+    // ========== Weight Info Trait ==========
+
+    /// Weight information for the pallet's extrinsics.
+    pub trait WeightInfo {
+        fn send_message() -> Weight;
+        fn acknowledge() -> Weight;
+        fn reject() -> Weight;
+    }
+
+    /// Default weights for testing.
+    impl WeightInfo for () {
+        fn send_message() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn acknowledge() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn reject() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{self as pallet_messaging, *};
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Messaging: pallet_messaging,
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+    type RuntimeTask = ();
+    type ExtensionsWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type DoneSlashHandler = ();
+}
+
+parameter_types! {
+    pub const MaxContentLength: u32 = 64;
+    pub const MaxInboxSize: u32 = 2;
+    pub const MessageDepositBase: u64 = 10;
+    pub const MessageDepositPerByte: u64 = 1;
+    /// Agents [`MockAgents`] reports as registered; agent `n` is controlled by account `n`.
+    pub static RegisteredAgents: Vec<u64> = vec![1, 2, 3];
+}
+
+pub struct MockAgents;
+
+impl pallet_agent_registry::AgentDirectory<u64> for MockAgents {
+    fn is_registered(agent_id: u64) -> bool {
+        RegisteredAgents::get().contains(&agent_id)
+    }
+
+    fn is_controller(account: &u64, agent_id: u64) -> bool {
+        *account == agent_id && Self::is_registered(agent_id)
+    }
+
+    fn controllers(agent_id: u64) -> Vec<u64> {
+        if Self::is_registered(agent_id) {
+            vec![agent_id]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Task 7 is live, with accounts 1 and 2 taking part; every other task is not.
+pub struct MockTasks;
+
+impl NegotiableTasks<u64> for MockTasks {
+    fn is_negotiable(task_id: u64) -> bool {
+        task_id == 7
+    }
+
+    fn is_negotiator(task_id: u64, account: &u64) -> bool {
+        task_id == 7 && [1, 2].contains(account)
+    }
+}
+
+impl pallet_messaging::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type Agents = MockAgents;
+    type Tasks = MockTasks;
+    type MaxContentLength = MaxContentLength;
+    type MaxInboxSize = MaxInboxSize;
+    type MessageDepositBase = MessageDepositBase;
+    type MessageDepositPerByte = MessageDepositPerByte;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 10000), (2, 10000), (3, 10000)],
+        dev_accounts: Default::default(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+#[test]
+fn send_message_reserves_deposit_and_fills_inbox() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(2), 1, 2, b"hi".to_vec(), None, None),
+            Error::<Test>::NotAgentController
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 1, 9, b"hi".to_vec(), None, None),
+            Error::<Test>::RecipientNotFound
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 1, 1, b"hi".to_vec(), None, None),
+            Error::<Test>::SelfMessage
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 1, 2, vec![], None, None),
+            Error::<Test>::EmptyContent
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 1, 2, vec![0; 65], None, None),
+            Error::<Test>::ContentTooLong
+        );
+
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(1),
+            1,
+            2,
+            b"ciphertext".to_vec(),
+            None,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(1), 20);
+        System::assert_last_event(RuntimeEvent::Messaging(Event::MessageSent {
+            message_id: 0,
+            sender: 1,
+            recipient: 2,
+            task_id: None,
+        }));
+
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(3),
+            3,
+            2,
+            b"ipfs://bafy".to_vec(),
+            Some([5u8; 32]),
+            None
+        ));
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 1, 2, b"hi".to_vec(), None, None),
+            Error::<Test>::InboxFull
+        );
+
+        let inbox = Messaging::inbox(2, 0, 10);
+        assert_eq!(inbox.len(), 2);
+        assert_eq!(inbox[0].content, b"ciphertext".to_vec());
+        assert_eq!(inbox[1].content_hash, Some([5u8; 32]));
+        assert_eq!(Messaging::inbox(2, 1, 10).len(), 1);
+        assert_eq!(Messaging::inbox(2, 0, 1).len(), 1);
+        assert_eq!(Messaging::next_message_id(), 2);
+    });
+}
+
+#[test]
+fn acknowledge_refunds_deposit_and_frees_inbox() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(1),
+            1,
+            2,
+            b"ciphertext".to_vec(),
+            None,
+            None
+        ));
+
+        // Only the recipient acknowledges while it is registered
+        assert_noop!(
+            Messaging::acknowledge(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::NotAgentController
+        );
+        assert_ok!(Messaging::acknowledge(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Messaging::inbox_size(2), 0);
+        assert!(Messaging::messages(0).is_none());
+        assert_noop!(
+            Messaging::acknowledge(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::MessageNotFound
+        );
+
+        // The sender reclaims its deposit once the recipient is gone
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(1),
+            1,
+            2,
+            b"ciphertext".to_vec(),
+            None,
+            None
+        ));
+        RegisteredAgents::set(vec![1, 3]);
+        assert_ok!(Messaging::acknowledge(RuntimeOrigin::signed(1), 1));
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+#[test]
+fn messages_thread_under_live_tasks() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Messaging::send_message(
+                RuntimeOrigin::signed(1),
+                1,
+                2,
+                b"bid?".to_vec(),
+                None,
+                Some(8)
+            ),
+            Error::<Test>::TaskNotNegotiable
+        );
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(1),
+            1,
+            2,
+            b"bid?".to_vec(),
+            None,
+            Some(7)
+        ));
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(2),
+            2,
+            1,
+            b"yes".to_vec(),
+            None,
+            Some(7)
+        ));
+
+        // Agent 3 takes no part in the task
+        assert_noop!(
+            Messaging::send_message(
+                RuntimeOrigin::signed(3),
+                3,
+                1,
+                b"pick me".to_vec(),
+                None,
+                Some(7)
+            ),
+            Error::<Test>::NotTaskParticipant
+        );
+        assert_noop!(
+            Messaging::send_message(
+                RuntimeOrigin::signed(1),
+                1,
+                3,
+                b"join?".to_vec(),
+                None,
+                Some(7)
+            ),
+            Error::<Test>::NotTaskParticipant
+        );
+
+        let thread = Messaging::thread(7);
+        assert_eq!(thread.len(), 2);
+        assert_eq!((thread[0].sender, thread[1].sender), (1, 2));
+
+        assert_ok!(Messaging::acknowledge(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Messaging::thread(7).len(), 1);
+    });
+}
+
+#[test]
+fn reject_slashes_deposit_and_frees_inbox() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Messaging::send_message(
+            RuntimeOrigin::signed(1),
+            1,
+            2,
+            b"spam".to_vec(),
+            None,
+            Some(7)
+        ));
+        assert_eq!(Balances::reserved_balance(1), 14);
+
+        // Only the recipient can reject
+        assert_noop!(
+            Messaging::reject(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::NotAgentController
+        );
+        assert_ok!(Messaging::reject(RuntimeOrigin::signed(2), 0));
+        System::assert_last_event(RuntimeEvent::Messaging(Event::MessageRejected {
+            message_id: 0,
+            sender: 1,
+            recipient: 2,
+            slashed: 14,
+        }));
+
+        // The deposit is gone rather than refunded, and the slot is free again
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 10000 - 14);
+        assert_eq!(Messaging::inbox_size(2), 0);
+        assert!(Messaging::thread(7).is_empty());
+        assert_noop!(
+            Messaging::acknowledge(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::MessageNotFound
+        );
+    });
+}
@@ -0,0 +1,242 @@
+//! RPC interface for the Reputation pallet.
+//!
+//! Exposes the `ReputationApi` runtime API as `reputation_*` JSON-RPC methods.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_reputation_runtime_api::{
+    FeedbackAuthId, HistoryEntry, LeaderboardPage, ReputationSummary, ReviewSummary,
+    TaskCategory,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_reputation_runtime_api::ReputationApi as ReputationRuntimeApi;
+
+/// Maximum (and default) number of entries returned by a paginated call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait ReputationApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// Current reputation score of an account in basis points.
+    #[method(name = "reputation_score")]
+    fn score(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    /// Reputation score of an account in a task category, in basis points.
+    #[method(name = "reputation_categoryScore")]
+    fn category_score(
+        &self,
+        account: AccountId,
+        category: TaskCategory,
+        at: Option<BlockHash>,
+    ) -> RpcResult<u32>;
+
+    /// Score and aggregates for an account.
+    #[method(name = "reputation_info")]
+    fn info(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ReputationSummary<Balance, BlockNumber>>;
+
+    /// Reputation history of an account, oldest first, skipping the first `start`.
+    #[method(name = "reputation_history")]
+    fn history(
+        &self,
+        account: AccountId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<HistoryEntry<AccountId, Balance>>>;
+
+    /// Reviews left for an account, oldest first, skipping the first `start`.
+    #[method(name = "reputation_reviewsReceived")]
+    fn reviews_received(
+        &self,
+        account: AccountId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ReviewSummary<AccountId, BlockNumber>>>;
+
+    /// Reviews left by an account.
+    #[method(name = "reputation_reviewsGiven")]
+    fn reviews_given(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ReviewSummary<AccountId, BlockNumber>>>;
+
+    /// The top `limit` accounts by score, highest first, of a window of accounts
+    /// scanned after `start`; continue from `next` and merge the pages for the full
+    /// leaderboard.
+    #[method(name = "reputation_leaderboard")]
+    fn leaderboard(
+        &self,
+        start: Option<AccountId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<LeaderboardPage<AccountId>>;
+
+    /// ERC-8004 `feedbackAuthId` under which `server` authorised `client` to give
+    /// feedback, or `null`.
+    #[method(name = "reputation_feedbackAuthorization")]
+    fn feedback_authorization(
+        &self,
+        server: AccountId,
+        client: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<FeedbackAuthId>>;
+
+    /// EigenTrust standing of `account` in basis points, or `null` if not computed.
+    #[method(name = "reputation_globalTrust")]
+    fn global_trust(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u32>>;
+}
+
+/// Provides RPC methods to query agent reputation.
+pub struct Reputation<C, Block> {
+    /// Shared reference to the client.
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Reputation<C, Block> {
+    /// Creates a new instance of the Reputation RPC helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(RUNTIME_ERROR, "Unable to query reputation.", Some(error.to_string()))
+}
+
+fn page_size(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    ReputationApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for Reputation<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: ReputationRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + Send + Sync + 'static,
+    Balance: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Send + Sync + 'static,
+{
+    fn score(&self, account: AccountId, at: Option<Block::Hash>) -> RpcResult<u32> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().score(at_hash, account).map_err(runtime_error)
+    }
+
+    fn category_score(
+        &self,
+        account: AccountId,
+        category: TaskCategory,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<u32> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .category_score(at_hash, account, category)
+            .map_err(runtime_error)
+    }
+
+    fn info(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<ReputationSummary<Balance, BlockNumber>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .reputation_info(at_hash, account)
+            .map_err(runtime_error)
+    }
+
+    fn history(
+        &self,
+        account: AccountId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<HistoryEntry<AccountId, Balance>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .history(at_hash, account, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn reviews_received(
+        &self,
+        account: AccountId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<ReviewSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .reviews_received(at_hash, account, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn reviews_given(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<ReviewSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .reviews_given(at_hash, account)
+            .map_err(runtime_error)
+    }
+
+    fn leaderboard(
+        &self,
+        start: Option<AccountId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<LeaderboardPage<AccountId>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .leaderboard(at_hash, start, page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn feedback_authorization(
+        &self,
+        server: AccountId,
+        client: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<FeedbackAuthId>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .feedback_authorization(at_hash, server, client)
+            .map_err(runtime_error)
+    }
+
+    fn global_trust(&self, account: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<u32>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .global_trust(at_hash, account)
+            .map_err(runtime_error)
+    }
+}
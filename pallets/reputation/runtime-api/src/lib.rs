@@ -0,0 +1,59 @@
+//! Runtime API definition for the Reputation pallet.
+//!
+//! Lets agents read scores, history and reviews before deciding whether to engage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_reputation::{
+    FeedbackAuthId, HistoryEntry, LeaderboardPage, ReputationSummary, ReviewSummary,
+    TaskCategory, MAX_SCANNED_ACCOUNTS,
+};
+
+sp_api::decl_runtime_apis! {
+    /// Queries over agent reputation.
+    pub trait ReputationApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Current reputation score of `account` in basis points.
+        fn score(account: AccountId) -> u32;
+
+        /// Reputation score of `account` in a task category, in basis points.
+        fn category_score(account: AccountId, category: TaskCategory) -> u32;
+
+        /// Score and aggregates for `account`.
+        fn reputation_info(account: AccountId) -> ReputationSummary<Balance, BlockNumber>;
+
+        /// Reputation history of `account`, oldest first, skipping the first `start`
+        /// entries and returning at most `limit`.
+        fn history(account: AccountId, start: u32, limit: u32) -> Vec<HistoryEntry<AccountId, Balance>>;
+
+        /// Reviews left for `account`, oldest first, skipping the first `start` and
+        /// returning at most `limit`.
+        fn reviews_received(
+            account: AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<ReviewSummary<AccountId, BlockNumber>>;
+
+        /// Reviews left by `account`.
+        fn reviews_given(account: AccountId) -> Vec<ReviewSummary<AccountId, BlockNumber>>;
+
+        /// The `limit` highest-scoring of at most `MAX_SCANNED_ACCOUNTS` accounts after
+        /// `start`, highest first, and the account to continue the scan after.
+        fn leaderboard(start: Option<AccountId>, limit: u32) -> LeaderboardPage<AccountId>;
+
+        /// ID of the authorisation `server` gave `client` to give feedback, if any.
+        fn feedback_authorization(server: AccountId, client: AccountId) -> Option<FeedbackAuthId>;
+
+        /// EigenTrust standing of `account` in basis points, if it has been computed.
+        fn global_trust(account: AccountId) -> Option<u32>;
+    }
+}
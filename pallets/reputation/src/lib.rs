@@ -10,13 +10,96 @@
 //! - Storing peer reviews from completed tasks
 //! - Managing dispute outcomes (wins/losses)
 //! - Integration with task-market pallet for automatic reputation updates
+//! - ERC-8004 feedback authorisation
+//!
+//! ## Feedback Authorisation
+//!
+//! Mirroring the ERC-8004 reputation registry, a server agent calls
+//! `accept_feedback` to authorise a client to leave feedback on its work
+//! (`acceptFeedback`). Each authorisation gets a sequential `FeedbackAuthId`,
+//! emitted in `FeedbackAuthorized`, which off-chain feedback can cite.
+//! Agents act through their agent account, so the server and client are accounts.
+//!
+//! ## Scoring
+//!
+//! Scores are recomputed from the `ReputationInfo` aggregates on every change rather
+//! than accumulated from deltas. Two Bayesian averages, each starting from
+//! `PriorWeight` pseudo-observations at `InitialReputation`, are blended:
+//!
+//! - **Ratings** - reviews (1 star = 0, 3 stars = 5000, 5 stars = 10000) and
+//!   validator scores (0-100, scaled to basis points)
+//! - **Record** - the share of successful outcomes (approved completions and disputes
+//!   won) among all outcomes, including disputes lost. Like a review, an outcome is
+//!   scaled down for tasks rewarded below `ReviewValueCap`.
+//!
+//! `RatingShare` percent of the score comes from ratings and the rest from the record.
+//! Governance slashes accumulate in `penalty`, which is subtracted from the blend. An
+//! account with no record scores `InitialReputation`; poor ratings and lost disputes
+//! pull the score below it.
+//!
+//! Ratings are weighted. A review counts in proportion to the reviewer's standing
+//! (their global trust if they have one, else their score), discounted by their track
+//! record and scaled down for tasks rewarded below `ReviewValueCap`. The track record
+//! share is `n / (n + PriorWeight)` for `n` tasks the reviewer has settled, as the
+//! worker or as the poster approving the work, since a fresh account's score is
+//! `InitialReputation` and says nothing about it. Each settled task counts by its value
+//! share, and posting alone counts for nothing, so neither cheap self-dealt tasks nor
+//! posting and cancelling builds a track record. A validator score is weighted the
+//! same way, by the validators' average weight. Fresh accounts and cheap tasks
+//! therefore move scores little.
+//!
+//! ## Categories
+//!
+//! Every task has a category, chosen by its poster in pallet-task-market. Alongside
+//! the global aggregates, `CategoryReputations` keeps each account's completions,
+//! disputes and reviews per task category, scored the same way, so an agent that
+//! excels at one kind of work and struggles at another has a score for each.
+//! Validator scores only count towards the global score, and governance penalties
+//! count against every category. Categories decay like the global score.
+//!
+//! ## Global Trust
+//!
+//! Every `TrustUpdatePeriod` blocks, `on_idle` starts an EigenTrust-style iteration
+//! over the review graph. Ratings above 3 stars are local trust, normalised per
+//! reviewer; trust is propagated along them and mixed with a pre-trust distribution
+//! proportional to scores. The result is stored in `GlobalTrust`, scaled so the average
+//! account in the graph has `InitialReputation`, and is used as the reviewer's standing
+//! when weighting their reviews. A zero `TrustUpdatePeriod` disables it.
+//!
+//! The recomputation reads every review left before it started. It runs in stages that
+//! walk `Reviews` or the accounts in `TrustNodes` with a cursor kept in `TrustUpdate`,
+//! so each block's `on_idle` processes at most `MaxTrustStepsPerBlock` reviews or
+//! accounts and the work spreads over as many blocks as the graph needs.
+//!
+//! ## Decay
+//!
+//! Scores of dormant accounts drift back to `InitialReputation`. Once an account has
+//! been inactive for longer than the grace period, its rating and record evidence is
+//! halved every half-life, so both averages return to the prior. The penalty does not
+//! decay: a governance slash stands however long the account stays dormant.
+//!
+//! Only an account's own completed work makes it active again. Posting tasks,
+//! approving work, receiving reviews or validations and resolved disputes change its
+//! aggregates without restarting the grace period, so others cannot keep a dormant
+//! score alive and neither can the account by posting.
+//!
+//! Decay is lazy: `get_reputation`, `ReputationScore` and the runtime API helpers
+//! apply it when reading, and every update applies it before changing the
+//! aggregates, so nothing iterates accounts per block. Scores pushed to
+//! `OnReputationChanged` are only refreshed on updates, so consumers that must track
+//! decay, like agent-registry, read through `ReputationScore` instead.
+//!
+//! The grace period and half-life default to `DecayGracePeriod` and `DecayHalfLife`;
+//! root can override them with `set_decay_parameters`. A zero half-life disables decay.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `submit_review` - Leave a review for another agent after task completion
+//! - `submit_review` - Leave a review for the other party of an approved task
 //! - `slash_reputation` - Governance/sudo can slash reputation for misbehavior
+//! - `accept_feedback` - Authorise a client to give feedback (ERC-8004)
+//! - `set_decay_parameters` - Governance/sudo can change how dormant scores decay
 //!
 //! ### Public Functions (for cross-pallet calls)
 //!
@@ -24,7 +107,32 @@
 //! - `on_task_posted` - Called by task-market when task is created
 //! - `on_dispute_resolved` - Called by task-market when dispute is resolved
 //! - `get_reputation` - Get current reputation score for an account
-//! - `meets_minimum_reputation` - Check if account meets minimum reputation threshold
+//! - `meets_minimum_reputation` - Check if account meets minimum reputation threshold,
+//!   globally or in a task category
+//! - `ReputationScore::score` - Read a score without the `Balance` type, used by
+//!   agent-registry to derive agent reputation
+//! - `OnReputationChanged` - Hook notified after every score change
+//! - `OnWorkValidated` - Called by pallet-validation with the validators' average
+//!   score, counted as a rating
+//!
+//! ### Reviews
+//!
+//! Reviews must come from a real counterparty: the `ReviewableTasks` hook (implemented
+//! by task-market) names the other party of an approved or resolved task the reviewer
+//! posted or worked on, and only that account can be reviewed. Each party reviews a
+//! task at most once; reviews are kept per task, so two agents that work together
+//! repeatedly accumulate a review for every task.
+//!
+//! ### Runtime API Helpers
+//!
+//! - `reputation_summary` - Score and aggregates for an account
+//! - `category_score` - Score of an account in a task category
+//! - `history` - Paginated reputation event history
+//! - `reviews_received` - Paginated reviews left for an account
+//! - `reviews_given` - Reviews left by an account
+//! - `leaderboard` - Top accounts by score, a bounded window of accounts at a time
+//! - `global_trust` - EigenTrust standing of an account, if computed
+//! - `feedback_authorization` - Feedback authorisation of a client by a server
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -32,26 +140,110 @@ extern crate alloc;
 
 pub use pallet::*;
 
+pub mod migrations;
+
 #[cfg(test)]
 mod tests;
 
 use alloc::vec::Vec;
 
+/// Highest reputation score (100.00%), in basis points.
+pub const MAX_SCORE: u32 = 10_000;
+
+/// Maximum number of accounts a runtime API leaderboard scans per call.
+pub const MAX_SCANNED_ACCOUNTS: u32 = 1_000;
+
+/// Category of a task, chosen by its poster in pallet-task-market.
+pub type TaskCategory = u32;
+
 /// Trait for cross-pallet reputation management.
 pub trait ReputationManager<AccountId, Balance> {
-    fn on_task_completed(worker: &AccountId, earned: Balance);
+    fn on_task_completed(worker: &AccountId, earned: Balance, category: TaskCategory);
     fn on_task_posted(poster: &AccountId, spent: Balance);
-    fn on_dispute_resolved(winner: &AccountId, loser: &AccountId);
+    /// `poster` approved the work on a task it posted, rewarded with `spent`.
+    fn on_task_approved(poster: &AccountId, spent: Balance);
+    /// A dispute over a task rewarded with `value` was resolved for `winner`.
+    fn on_dispute_resolved(
+        winner: &AccountId,
+        loser: &AccountId,
+        value: Balance,
+        category: TaskCategory,
+    );
     fn get_reputation(account: &AccountId) -> u32;
-    fn meets_minimum_reputation(account: &AccountId, minimum: u32) -> bool;
+    /// Whether `account`'s score, or its score in `category` if given, is at least
+    /// `minimum`.
+    fn meets_minimum_reputation(
+        account: &AccountId,
+        minimum: u32,
+        category: Option<TaskCategory>,
+    ) -> bool;
+}
+
+/// Read-only access to reputation scores, for pallets that have no `Balance` type.
+pub trait ReputationScore<AccountId> {
+    fn score(account: &AccountId) -> u32;
+}
+
+/// Hook called after an account's reputation score changes.
+pub trait OnReputationChanged<AccountId> {
+    fn on_reputation_changed(account: &AccountId, new_score: u32);
+}
+
+impl<AccountId> OnReputationChanged<AccountId> for () {
+    fn on_reputation_changed(_account: &AccountId, _new_score: u32) {}
+}
+
+/// Hook called when independent validators have scored an account's work.
+pub trait OnWorkValidated<AccountId, Balance> {
+    /// `score` is the `validators`' average score, 0-100, for work on a task rewarded
+    /// with `value`.
+    fn on_work_validated(worker: &AccountId, validators: &[AccountId], value: Balance, score: u8);
+}
+
+impl<AccountId, Balance> OnWorkValidated<AccountId, Balance> for () {
+    fn on_work_validated(
+        _worker: &AccountId,
+        _validators: &[AccountId],
+        _value: Balance,
+        _score: u8,
+    ) {
+    }
+}
+
+/// Source of the tasks reviews can be left for.
+pub trait ReviewableTasks<AccountId, Balance> {
+    /// The other party of a concluded task `reviewer` took part in, as poster or worker,
+    /// with the task's reward and category.
+    fn counterparty(
+        task_id: u64,
+        reviewer: &AccountId,
+    ) -> Option<(AccountId, Balance, TaskCategory)>;
+}
+
+impl<AccountId, Balance> ReviewableTasks<AccountId, Balance> for () {
+    fn counterparty(
+        _task_id: u64,
+        _reviewer: &AccountId,
+    ) -> Option<(AccountId, Balance, TaskCategory)> {
+        None
+    }
 }
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::{pallet_prelude::*, sp_runtime::traits::Saturating};
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{SaturatedConversion, Saturating},
+            Perbill,
+        },
+    };
     use frame_system::pallet_prelude::*;
 
+    /// Sequential ID of a feedback authorisation (the ERC-8004 `feedbackAuthId`).
+    pub type FeedbackAuthId = u64;
+
     /// Type alias for balance (compatible with pallet-balances).
     pub type BalanceOf<T> =
         <<T as Config>::Currency as frame_support::traits::Currency<
@@ -62,7 +254,8 @@ pub mod pallet {
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
     pub struct ReputationInfo<T: Config> {
-        /// Reputation score in basis points (0-10000 = 0-100.00%).
+        /// Reputation score in basis points (0-10000 = 0-100.00%), computed from the
+        /// aggregates below.
         pub score: u32,
         /// Total number of tasks completed as a worker.
         pub total_tasks_completed: u32,
@@ -78,8 +271,26 @@ pub mod pallet {
         pub total_earned: BalanceOf<T>,
         /// Total amount spent on posted tasks.
         pub total_spent: BalanceOf<T>,
-        /// Block number of last activity.
+        /// Block number of the last completed task.
         pub last_active: BlockNumberFor<T>,
+        /// Block up to which decay has been applied to the rating and record evidence.
+        pub decayed_at: BlockNumberFor<T>,
+        /// Number of reviews and validations received.
+        pub ratings_received: u32,
+        /// Sum of received ratings (basis points), each multiplied by its weight.
+        pub rating_points: u64,
+        /// Total weight of received ratings; `MAX_SCORE` is one full-weight rating.
+        pub rating_weight: u64,
+        /// Sum of outcomes (`MAX_SCORE` for a success, 0 otherwise), each multiplied by
+        /// its weight.
+        pub record_points: u64,
+        /// Total weight of outcomes; decays alongside `rating_weight`.
+        pub record_weight: u64,
+        /// Total weight of tasks settled as worker or approving poster; `MAX_SCORE` is
+        /// one task rewarded at least `ReviewValueCap`.
+        pub settled_weight: u64,
+        /// Accumulated governance slashes (basis points).
+        pub penalty: u32,
     }
 
     impl<T: Config> Default for ReputationInfo<T> {
@@ -94,10 +305,33 @@ pub mod pallet {
                 total_earned: Zero::zero(),
                 total_spent: Zero::zero(),
                 last_active: Zero::zero(),
+                decayed_at: Zero::zero(),
+                ratings_received: 0,
+                rating_points: 0,
+                rating_weight: 0,
+                record_points: 0,
+                record_weight: 0,
+                settled_weight: 0,
+                penalty: 0,
             }
         }
     }
 
+    /// How dormant scores decay.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        codec::DecodeWithMemTracking,
+    )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct DecayParameters<BlockNumber> {
+        /// Blocks of inactivity before decay starts.
+        pub grace_period: BlockNumber,
+        /// Blocks of further inactivity that halve the distance to `InitialReputation`;
+        /// zero disables decay.
+        pub half_life: BlockNumber,
+    }
+
     /// A review left by one agent for another.
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -138,8 +372,172 @@ pub mod pallet {
             amount: u32,
             reason: BoundedVec<u8, T::MaxCommentLength>,
         },
+        WorkValidated {
+            score: u8,
+        },
+    }
+
+    /// Reputation view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct ReputationSummary<Balance, BlockNumber> {
+        /// Reputation score in basis points (0-10000).
+        pub score: u32,
+        /// Total number of tasks completed as a worker.
+        pub total_tasks_completed: u32,
+        /// Total number of tasks posted.
+        pub total_tasks_posted: u32,
+        /// Number of successful task completions.
+        pub successful_completions: u32,
+        /// Number of disputes won.
+        pub disputes_won: u32,
+        /// Number of disputes lost.
+        pub disputes_lost: u32,
+        /// Total amount earned from completed tasks.
+        pub total_earned: Balance,
+        /// Total amount spent on posted tasks.
+        pub total_spent: Balance,
+        /// Block number of the last completed task.
+        pub last_active: BlockNumber,
+        /// Number of reviews and validations received.
+        pub ratings_received: u32,
+        /// Accumulated governance slashes (basis points).
+        pub penalty: u32,
+    }
+
+    /// Review view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct ReviewSummary<AccountId, BlockNumber> {
+        /// Account that left the review.
+        pub reviewer: AccountId,
+        /// Account that was reviewed.
+        pub reviewee: AccountId,
+        /// Rating from 1-5 stars.
+        pub rating: u8,
+        /// Text comment.
+        pub comment: Vec<u8>,
+        /// Which task this review is for.
+        pub task_id: u64,
+        /// When the review was submitted.
+        pub created_at: BlockNumber,
+    }
+
+    /// Page of the leaderboard returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct LeaderboardPage<AccountId> {
+        /// Highest-scoring of the scanned accounts with their scores, highest first.
+        pub entries: Vec<(AccountId, u32)>,
+        /// Account to continue the scan after, or `None` once every account was scanned.
+        pub next: Option<AccountId>,
+    }
+
+    /// Reputation history entry returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub enum HistoryEntry<AccountId, Balance> {
+        TaskCompleted { task_id: u64, earned: Balance },
+        TaskPosted { task_id: u64, spent: Balance },
+        ReviewReceived { from: AccountId, rating: u8 },
+        DisputeWon { task_id: u64 },
+        DisputeLost { task_id: u64 },
+        Slashed { amount: u32, reason: Vec<u8> },
+        WorkValidated { score: u8 },
+    }
+
+    impl<T: Config> From<ReputationEvent<T>> for HistoryEntry<T::AccountId, BalanceOf<T>> {
+        fn from(event: ReputationEvent<T>) -> Self {
+            match event {
+                ReputationEvent::TaskCompleted { task_id, earned } => {
+                    HistoryEntry::TaskCompleted { task_id, earned }
+                }
+                ReputationEvent::TaskPosted { task_id, spent } => {
+                    HistoryEntry::TaskPosted { task_id, spent }
+                }
+                ReputationEvent::ReviewReceived { from, rating } => {
+                    HistoryEntry::ReviewReceived { from, rating }
+                }
+                ReputationEvent::DisputeWon { task_id } => HistoryEntry::DisputeWon { task_id },
+                ReputationEvent::DisputeLost { task_id } => HistoryEntry::DisputeLost { task_id },
+                ReputationEvent::Slashed { amount, reason } => HistoryEntry::Slashed {
+                    amount,
+                    reason: reason.into_inner(),
+                },
+                ReputationEvent::WorkValidated { score } => HistoryEntry::WorkValidated { score },
+            }
+        }
     }
 
+    /// Stage of a global trust recomputation.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TrustStage {
+        /// Collecting the accounts in the graph and their outgoing local trust.
+        Graph,
+        /// Reading the accounts' scores for the pre-trust distribution.
+        Scores,
+        /// Starting every account at its pre-trust.
+        Seed,
+        /// Propagating trust along reviews, in the given iteration.
+        Propagate(u32),
+        /// Mixing propagated trust with pre-trust, in the given iteration.
+        Mix(u32),
+        /// Writing the result to `GlobalTrust`.
+        Publish,
+    }
+
+    /// Progress of a global trust recomputation.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct TrustProgress<BlockNumber> {
+        /// Block at which the recomputation started; later reviews are left out.
+        pub started_at: BlockNumber,
+        /// Current stage.
+        pub stage: TrustStage,
+        /// Raw storage key of the last review or account processed in this stage.
+        pub cursor: Vec<u8>,
+        /// Number of accounts in the graph.
+        pub accounts: u32,
+        /// Sum of the accounts' scores.
+        pub total_score: u128,
+        /// Trust held by accounts that trust nobody, in the current iteration.
+        pub dangling: u128,
+        /// Trust held by accounts that trust nobody, in the next iteration.
+        pub next_dangling: u128,
+    }
+
+    /// An account's state during a global trust recomputation, with trust scaled by
+    /// `TRUST_SCALE`.
+    #[derive(
+        Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct TrustNode {
+        /// Sum of the local trust of the account's reviews.
+        pub outgoing: u128,
+        /// The account's score when the recomputation read it.
+        pub score: u32,
+        /// Trust after the last completed iteration.
+        pub trust: u128,
+        /// Trust propagated to the account in the current iteration.
+        pub next: u128,
+    }
+
+    /// Reputation summary type for a given runtime.
+    pub type ReputationSummaryOf<T> = ReputationSummary<BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// Review summary type for a given runtime.
+    pub type ReviewSummaryOf<T> =
+        ReviewSummary<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// History entry type for a given runtime.
+    pub type HistoryEntryOf<T> = HistoryEntry<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    /// Leaderboard page type for a given runtime.
+    pub type LeaderboardPageOf<T> = LeaderboardPage<<T as frame_system::Config>::AccountId>;
+
     /// The pallet's configuration trait.
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -160,16 +558,54 @@ pub mod pallet {
         #[pallet::constant]
         type InitialReputation: Get<u32>;
 
-        /// Maximum reputation change per single event (basis points).
+        /// Number of pseudo-observations at `InitialReputation` that both score averages
+        /// start from.
+        #[pallet::constant]
+        type PriorWeight: Get<u32>;
+
+        /// Percentage of the score that comes from ratings; the rest comes from the
+        /// completion and dispute record.
         #[pallet::constant]
-        type MaxReputationDelta: Get<u32>;
+        type RatingShare: Get<u8>;
 
         /// Maximum number of reputation events to store per account.
         #[pallet::constant]
         type MaxHistoryLength: Get<u32>;
+
+        /// Notified after every score change.
+        type OnReputationChanged: OnReputationChanged<Self::AccountId>;
+
+        /// Tasks reviews can be left for.
+        type ReviewableTasks: ReviewableTasks<Self::AccountId, BalanceOf<Self>>;
+
+        /// Task reward from which a review carries the reviewer's full standing.
+        #[pallet::constant]
+        type ReviewValueCap: Get<BalanceOf<Self>>;
+
+        /// Blocks between global trust recomputations; zero disables them.
+        #[pallet::constant]
+        type TrustUpdatePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of reviews or accounts a global trust recomputation processes
+        /// per block.
+        #[pallet::constant]
+        type MaxTrustStepsPerBlock: Get<u32>;
+
+        /// Default blocks of inactivity before a score starts to decay.
+        #[pallet::constant]
+        type DecayGracePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Default half-life of a dormant score's distance to `InitialReputation`;
+        /// zero disables decay.
+        #[pallet::constant]
+        type DecayHalfLife: Get<BlockNumberFor<Self>>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ========== Storage ==========
@@ -180,20 +616,54 @@ pub mod pallet {
     pub type Reputations<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, ReputationInfo<T>, ValueQuery>;
 
-    /// Map from (reviewer, reviewee) to the review they left.
-    /// Double map allows querying reviews by reviewer or reviewee.
+    /// Aggregates of each account in each task category it has taken part in. Only
+    /// the completion, dispute and rating fields are kept per category; the account's
+    /// penalty applies from `Reputations`.
     #[pallet::storage]
-    #[pallet::getter(fn reviews)]
-    pub type Reviews<T: Config> = StorageDoubleMap<
+    pub type CategoryReputations<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        T::AccountId, // reviewer
-        Blake2_128Concat,
-        T::AccountId, // reviewee
+        T::AccountId,
+        Twox64Concat,
+        TaskCategory,
+        ReputationInfo<T>,
+        ValueQuery,
+    >;
+
+    /// Map from (reviewer, reviewee, task_id) to the review left for that task.
+    /// Keying by reviewer first allows querying the reviews an account gave.
+    #[pallet::storage]
+    #[pallet::getter(fn reviews)]
+    pub type Reviews<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>, // reviewer
+            NMapKey<Blake2_128Concat, T::AccountId>, // reviewee
+            NMapKey<Twox64Concat, u64>,              // task_id
+        ),
         Review<T>,
         OptionQuery,
     >;
 
+    /// Reviews left for each account, by reviewee and the order they were left in, as
+    /// (reviewer, task_id) keys into `Reviews`.
+    #[pallet::storage]
+    pub type ReviewsReceived<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32,
+        (T::AccountId, u64),
+        OptionQuery,
+    >;
+
+    /// Number of reviews left for each account; the index of its next review in
+    /// `ReviewsReceived`.
+    #[pallet::storage]
+    pub type ReviewsReceivedCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     /// Reputation event history for each account (bounded vector).
     #[pallet::storage]
     #[pallet::getter(fn reputation_history)]
@@ -205,6 +675,50 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Feedback authorisations, by (server, client).
+    #[pallet::storage]
+    #[pallet::getter(fn feedback_authorizations)]
+    pub type FeedbackAuthorizations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // server
+        Blake2_128Concat,
+        T::AccountId, // client
+        FeedbackAuthId,
+        OptionQuery,
+    >;
+
+    /// ID of the next feedback authorisation.
+    #[pallet::storage]
+    pub type NextFeedbackAuthId<T: Config> = StorageValue<_, FeedbackAuthId, ValueQuery>;
+
+    /// EigenTrust standing of accounts in the review graph (basis points).
+    #[pallet::storage]
+    #[pallet::getter(fn global_trust)]
+    pub type GlobalTrust<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+    /// Block at which the last global trust recomputation started.
+    #[pallet::storage]
+    pub type LastTrustUpdate<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Progress of the global trust recomputation under way, if any.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type TrustUpdate<T: Config> =
+        StorageValue<_, TrustProgress<BlockNumberFor<T>>, OptionQuery>;
+
+    /// State of each account in the review graph during a global trust recomputation.
+    #[pallet::storage]
+    pub type TrustNodes<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, TrustNode, ValueQuery>;
+
+    /// Decay parameters set by governance, overriding `DecayGracePeriod` and
+    /// `DecayHalfLife`.
+    #[pallet::storage]
+    pub type DecayOverride<T: Config> =
+        StorageValue<_, DecayParameters<BlockNumberFor<T>>, OptionQuery>;
+
     // ========== Events ==========
 
     #[pallet::event]
@@ -246,6 +760,18 @@ pub mod pallet {
             winner: T::AccountId,
             loser: T::AccountId,
         },
+        /// A server authorised a client to give feedback.
+        FeedbackAuthorized {
+            client: T::AccountId,
+            server: T::AccountId,
+            feedback_auth_id: FeedbackAuthId,
+        },
+        /// Global trust was recomputed over the review graph.
+        GlobalTrustUpdated { accounts: u32 },
+        /// Governance changed the decay parameters.
+        DecayParametersSet {
+            parameters: DecayParameters<BlockNumberFor<T>>,
+        },
     }
 
     // ========== Errors ==========
@@ -260,13 +786,39 @@ pub mod pallet {
         SelfReview,
         /// Reputation history is full.
         HistoryOverflow,
+        /// The client is already authorised to give feedback.
+        FeedbackAlreadyAuthorized,
+        /// The reviewee is not the reviewer's counterparty on an approved task.
+        NotTaskParticipant,
+        /// The reviewer has already reviewed this task.
+        AlreadyReviewed,
+    }
+
+    // ========== Hooks ==========
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::update_global_trust(now, remaining_weight)
+        }
     }
 
+    /// Fixed-point scale of trust values during a global trust recomputation.
+    const TRUST_SCALE: u128 = 1_000_000_000;
+
+    /// Number of EigenTrust iterations per recomputation.
+    const TRUST_ITERATIONS: u32 = 20;
+
+    /// Percentage of each account's trust taken from the pre-trust distribution.
+    const PRE_TRUST_PERCENT: u128 = 15;
+
     // ========== Extrinsics ==========
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Submit a review for another agent after a task.
+        /// Submit a review for the other party of an approved task.
+        ///
+        /// Only a task's poster and worker can review each other, once per task.
         ///
         /// # Arguments
         /// * `reviewee` - The account being reviewed
@@ -274,7 +826,7 @@ pub mod pallet {
         /// * `comment` - Text comment
         /// * `task_id` - Which task this review is for
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 3))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(9, 7))]
         pub fn submit_review(
             origin: OriginFor<T>,
             reviewee: T::AccountId,
@@ -287,6 +839,14 @@ pub mod pallet {
             // Validation
             ensure!(rating >= 1 && rating <= 5, Error::<T>::InvalidRating);
             ensure!(reviewer != reviewee, Error::<T>::SelfReview);
+            let (counterparty, value, category) =
+                T::ReviewableTasks::counterparty(task_id, &reviewer)
+                    .ok_or(Error::<T>::NotTaskParticipant)?;
+            ensure!(counterparty == reviewee, Error::<T>::NotTaskParticipant);
+            ensure!(
+                !Reviews::<T>::contains_key((&reviewer, &reviewee, task_id)),
+                Error::<T>::AlreadyReviewed
+            );
             let bounded_comment: BoundedVec<u8, T::MaxCommentLength> =
                 comment.try_into().map_err(|_| Error::<T>::CommentTooLong)?;
 
@@ -299,12 +859,15 @@ pub mod pallet {
                 task_id,
                 created_at: current_block,
             };
-            Reviews::<T>::insert(&reviewer, &reviewee, review);
+            Reviews::<T>::insert((&reviewer, &reviewee, task_id), review);
+            Self::index_review_received(&reviewee, &reviewer, task_id);
 
-            // Update reviewee's reputation based on rating
-            // 1 star = +100, 2 stars = +200, ... 5 stars = +500
-            let delta = (rating as u32) * 100;
-            Self::apply_reputation_change(&reviewee, delta as i32, true);
+            let weight = Self::review_weight(&reviewer, value);
+            let points = Self::review_points(rating);
+            Self::record_rating(&reviewee, points, weight);
+            Self::update_category(&reviewee, category, |rep| {
+                Self::add_rating(rep, points, weight)
+            });
 
             // Record event in history
             let event = ReputationEvent::<T>::ReviewReceived {
@@ -342,8 +905,9 @@ pub mod pallet {
             let bounded_reason: BoundedVec<u8, T::MaxCommentLength> =
                 reason.clone().try_into().map_err(|_| Error::<T>::CommentTooLong)?;
 
-            // Apply negative delta
-            Self::apply_reputation_change(&account, -(amount as i32), false);
+            Self::update_reputation(&account, |rep| {
+                rep.penalty = rep.penalty.saturating_add(amount);
+            });
 
             // Record event
             let event = ReputationEvent::<T>::Slashed {
@@ -360,41 +924,449 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Authorise `client` to give feedback on the caller's work.
+        ///
+        /// The caller is the server agent (ERC-8004 `acceptFeedback`). Each
+        /// client is authorised once per server.
+        ///
+        /// # Arguments
+        /// * `client` - The account allowed to give feedback
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        pub fn accept_feedback(origin: OriginFor<T>, client: T::AccountId) -> DispatchResult {
+            let server = ensure_signed(origin)?;
+
+            ensure!(server != client, Error::<T>::SelfReview);
+            ensure!(
+                !FeedbackAuthorizations::<T>::contains_key(&server, &client),
+                Error::<T>::FeedbackAlreadyAuthorized
+            );
+
+            let feedback_auth_id = NextFeedbackAuthId::<T>::get();
+            NextFeedbackAuthId::<T>::put(feedback_auth_id.saturating_add(1));
+            FeedbackAuthorizations::<T>::insert(&server, &client, feedback_auth_id);
+
+            Self::deposit_event(Event::FeedbackAuthorized {
+                client,
+                server,
+                feedback_auth_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set how dormant scores decay (governance/sudo only).
+        ///
+        /// Scores already decayed keep their decay; new parameters apply from the next
+        /// read or update.
+        ///
+        /// # Arguments
+        /// * `parameters` - New grace period and half-life, or `None` to restore the
+        ///   `DecayGracePeriod` and `DecayHalfLife` defaults
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().writes(1))]
+        pub fn set_decay_parameters(
+            origin: OriginFor<T>,
+            parameters: Option<DecayParameters<BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            DecayOverride::<T>::set(parameters);
+
+            Self::deposit_event(Event::DecayParametersSet {
+                parameters: Self::decay_parameters(),
+            });
+
+            Ok(())
+        }
     }
 
     // ========== Internal Functions ==========
 
     impl<T: Config> Pallet<T> {
-        /// Apply a reputation change (clamped to 0-10000).
-        fn apply_reputation_change(account: &T::AccountId, delta: i32, limit_delta: bool) {
-            Reputations::<T>::mutate(account, |rep| {
-                let old_score = rep.score;
-
-                // Clamp delta if requested
-                let clamped_delta = if limit_delta {
-                    let max = T::MaxReputationDelta::get() as i32;
-                    delta.clamp(-max, max)
-                } else {
-                    delta
-                };
+        /// Decay `account`'s aggregates, update them, then recompute its score from them.
+        fn update_reputation(account: &T::AccountId, update: impl FnOnce(&mut ReputationInfo<T>)) {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let (old_score, new_score) = Reputations::<T>::mutate(account, |rep| {
+                let old_score = Self::decay(rep, now);
+                update(rep);
+                rep.score = Self::compute_score(rep);
+                (old_score, rep.score)
+            });
 
-                let new_score = if clamped_delta >= 0 {
-                    old_score.saturating_add(clamped_delta as u32).min(10000)
-                } else {
-                    old_score.saturating_sub(clamped_delta.unsigned_abs()).max(0)
-                };
+            Self::deposit_event(Event::ReputationChanged {
+                account: account.clone(),
+                old_score,
+                new_score,
+            });
+            T::OnReputationChanged::on_reputation_changed(account, new_score);
+        }
 
-                rep.score = new_score;
-                rep.last_active = <frame_system::Pallet<T>>::block_number();
+        /// Decay `account`'s aggregates in `category`, update them, then recompute its
+        /// score in the category.
+        fn update_category(
+            account: &T::AccountId,
+            category: TaskCategory,
+            update: impl FnOnce(&mut ReputationInfo<T>),
+        ) {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let penalty = Reputations::<T>::get(account).penalty;
+            CategoryReputations::<T>::mutate(account, category, |rep| {
+                Self::decay(rep, now);
+                update(rep);
+                rep.penalty = penalty;
+                rep.score = Self::compute_score(rep);
+            });
+        }
 
-                Self::deposit_event(Event::ReputationChanged {
-                    account: account.clone(),
-                    old_score,
-                    new_score,
-                });
+        /// Append the review `reviewer` left for `task_id` to `reviewee`'s received reviews.
+        pub(crate) fn index_review_received(
+            reviewee: &T::AccountId,
+            reviewer: &T::AccountId,
+            task_id: u64,
+        ) {
+            ReviewsReceivedCount::<T>::mutate(reviewee, |count| {
+                ReviewsReceived::<T>::insert(reviewee, *count, (reviewer.clone(), task_id));
+                *count = count.saturating_add(1);
             });
         }
 
+        /// Record a rating of `account`, in basis points, with the given weight.
+        fn record_rating(account: &T::AccountId, points: u64, weight: u64) {
+            Self::update_reputation(account, |rep| Self::add_rating(rep, points, weight));
+        }
+
+        /// Add a rating, in basis points, with the given weight to `rep`.
+        fn add_rating(rep: &mut ReputationInfo<T>, points: u64, weight: u64) {
+            rep.ratings_received = rep.ratings_received.saturating_add(1);
+            rep.rating_points = rep
+                .rating_points
+                .saturating_add(points.saturating_mul(weight));
+            rep.rating_weight = rep.rating_weight.saturating_add(weight);
+        }
+
+        /// Weight of a review by `reviewer` for a task rewarded with `value`: the
+        /// reviewer's standing, scaled down below `ReviewValueCap`.
+        pub fn review_weight(reviewer: &T::AccountId, value: BalanceOf<T>) -> u64 {
+            let standing = GlobalTrust::<T>::get(reviewer)
+                .unwrap_or_else(|| <Self as ReputationManager<_, _>>::get_reputation(reviewer));
+            Self::track_record(reviewer) * (Self::value_share(value) * u64::from(standing))
+        }
+
+        /// Share of full weight a task rewarded with `value` carries: all of it from
+        /// `ReviewValueCap`, proportionally less below.
+        fn value_share(value: BalanceOf<T>) -> Perbill {
+            let cap = T::ReviewValueCap::get();
+            if cap.is_zero() {
+                Perbill::one()
+            } else {
+                Perbill::from_rational(value.min(cap), cap)
+            }
+        }
+
+        /// Share of full standing `account` has earned: `n / (n + PriorWeight)` for `n`
+        /// tasks settled, each by its value share, so a fresh account's standing counts
+        /// for little.
+        pub fn track_record(account: &T::AccountId) -> Perbill {
+            let settled = Reputations::<T>::get(account).settled_weight;
+            let prior = u64::from(T::PriorWeight::get()).saturating_mul(MAX_SCORE.into());
+            Perbill::from_rational(settled, settled.saturating_add(prior))
+        }
+
+        /// Count a task rewarded with `value` towards `rep`'s settled tasks.
+        fn record_settled(rep: &mut ReputationInfo<T>, value: BalanceOf<T>) {
+            let weight = Self::value_share(value) * u64::from(MAX_SCORE);
+            rep.settled_weight = rep.settled_weight.saturating_add(weight);
+        }
+
+        /// Record the outcome of a task rewarded with `value` in `rep`'s completion and
+        /// dispute record, scaled down below `ReviewValueCap`.
+        fn record_outcome(rep: &mut ReputationInfo<T>, success: bool, value: BalanceOf<T>) {
+            let full = u64::from(MAX_SCORE);
+            let weight = Self::value_share(value) * full;
+            if success {
+                rep.record_points = rep
+                    .record_points
+                    .saturating_add(full.saturating_mul(weight));
+            }
+            rep.record_weight = rep.record_weight.saturating_add(weight);
+        }
+
+        /// The decay parameters in effect.
+        pub fn decay_parameters() -> DecayParameters<BlockNumberFor<T>> {
+            DecayOverride::<T>::get().unwrap_or_else(|| DecayParameters {
+                grace_period: T::DecayGracePeriod::get(),
+                half_life: T::DecayHalfLife::get(),
+            })
+        }
+
+        /// Share of evidence an account inactive since `last_active` keeps at `now`:
+        /// halved every half-life after the grace period, linearly within a half-life.
+        pub fn decay_factor(last_active: BlockNumberFor<T>, now: BlockNumberFor<T>) -> Perbill {
+            let DecayParameters {
+                grace_period,
+                half_life,
+            } = Self::decay_parameters();
+            let dormant = now.saturating_sub(last_active).saturating_sub(grace_period);
+            if half_life.is_zero() || dormant.is_zero() {
+                return Perbill::one();
+            }
+
+            let half_life: u64 = half_life.saturated_into();
+            let dormant: u64 = dormant.saturated_into();
+            let halvings = dormant / half_life;
+            if halvings >= 32 {
+                return Perbill::zero();
+            }
+            let kept = u64::from(Perbill::one().deconstruct() >> halvings);
+            let span = half_life.saturating_mul(2);
+            let within = span - dormant % half_life;
+            Perbill::from_parts((kept.saturating_mul(within) / span) as u32)
+        }
+
+        /// Apply decay from `decayed_at` up to `now` to `rep`'s evidence and recompute
+        /// its score. Returns the score before decay.
+        fn decay(rep: &mut ReputationInfo<T>, now: BlockNumberFor<T>) -> u32 {
+            let old_score = rep.score;
+            let applied = Self::decay_factor(rep.last_active, rep.decayed_at);
+            let due = Self::decay_factor(rep.last_active, now);
+            rep.decayed_at = rep.decayed_at.max(now);
+            if due != applied {
+                let factor = Perbill::from_rational(due.deconstruct(), applied.deconstruct());
+                rep.rating_points = factor * rep.rating_points;
+                rep.rating_weight = factor * rep.rating_weight;
+                rep.record_points = factor * rep.record_points;
+                rep.record_weight = factor * rep.record_weight;
+                rep.score = Self::compute_score(rep);
+            }
+            old_score
+        }
+
+        /// `account`'s reputation with decay applied up to the current block.
+        pub fn decayed_reputation(account: &T::AccountId) -> ReputationInfo<T> {
+            let mut rep = Reputations::<T>::get(account);
+            Self::decay(&mut rep, <frame_system::Pallet<T>>::block_number());
+            rep
+        }
+
+        /// `account`'s score in `category`, with decay applied up to the current block
+        /// and its current penalty subtracted.
+        pub fn category_score(account: &T::AccountId, category: TaskCategory) -> u32 {
+            let mut rep = CategoryReputations::<T>::get(account, category);
+            Self::decay(&mut rep, <frame_system::Pallet<T>>::block_number());
+            rep.penalty = Reputations::<T>::get(account).penalty;
+            Self::compute_score(&rep)
+        }
+
+        /// Rating points of a review: 1 star = 0, 3 stars = 5000, 5 stars = 10000.
+        pub(crate) fn review_points(rating: u8) -> u64 {
+            u64::from(rating.saturating_sub(1)) * u64::from(MAX_SCORE) / 4
+        }
+
+        /// Score for the given aggregates: the blend of the rating and record averages,
+        /// less the penalty.
+        pub fn compute_score(rep: &ReputationInfo<T>) -> u32 {
+            let full = u64::from(MAX_SCORE);
+            let prior = u64::from(T::InitialReputation::get());
+            let prior_weight = u64::from(T::PriorWeight::get()).saturating_mul(full);
+            let average = |points: u64, weight: u64| {
+                prior.saturating_mul(prior_weight).saturating_add(points)
+                    / prior_weight.saturating_add(weight).max(1)
+            };
+
+            let ratings = average(rep.rating_points, rep.rating_weight);
+            let record = average(rep.record_points, rep.record_weight);
+
+            let share = u64::from(T::RatingShare::get().min(100));
+            let blended =
+                (ratings.saturating_mul(share) + record.saturating_mul(100 - share)) / 100;
+            blended
+                .saturating_sub(rep.penalty.into())
+                .min(MAX_SCORE.into()) as u32
+        }
+
+        /// Weight of processing one review or account of a global trust recomputation.
+        pub fn trust_step_weight() -> Weight {
+            Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(4, 3)
+        }
+
+        /// Local trust of a review: how far its rating is above neutral.
+        fn local_trust(rating: u8) -> u128 {
+            Self::review_points(rating)
+                .saturating_sub(u64::from(MAX_SCORE) / 2)
+                .into()
+        }
+
+        /// Advance the global trust recomputation, starting one if `TrustUpdatePeriod`
+        /// has elapsed, as far as `remaining_weight` and `MaxTrustStepsPerBlock` allow.
+        pub(crate) fn update_global_trust(
+            now: BlockNumberFor<T>,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let period = T::TrustUpdatePeriod::get();
+            let mut used = T::DbWeight::get().reads_writes(2, 2);
+            if period.is_zero() || remaining_weight.any_lt(used) {
+                return Weight::zero();
+            }
+
+            let mut progress = match TrustUpdate::<T>::get() {
+                Some(progress) => progress,
+                None if now >= LastTrustUpdate::<T>::get().saturating_add(period) => {
+                    LastTrustUpdate::<T>::put(now);
+                    TrustProgress {
+                        started_at: now,
+                        stage: TrustStage::Graph,
+                        cursor: Vec::new(),
+                        accounts: 0,
+                        total_score: 0,
+                        dangling: 0,
+                        next_dangling: 0,
+                    }
+                }
+                None => return T::DbWeight::get().reads(2),
+            };
+
+            let step = Self::trust_step_weight();
+            let mut steps = 0u32;
+            while steps < T::MaxTrustStepsPerBlock::get()
+                && !remaining_weight.any_lt(used.saturating_add(step))
+            {
+                used = used.saturating_add(step);
+                steps = steps.saturating_add(1);
+                if Self::trust_step(&mut progress) {
+                    continue;
+                }
+
+                // The current stage is complete
+                progress.cursor.clear();
+                progress.stage = match progress.stage {
+                    TrustStage::Graph => TrustStage::Scores,
+                    TrustStage::Scores => TrustStage::Seed,
+                    TrustStage::Seed => TrustStage::Propagate(0),
+                    TrustStage::Propagate(round) => TrustStage::Mix(round),
+                    TrustStage::Mix(round) => {
+                        progress.dangling = core::mem::take(&mut progress.next_dangling);
+                        if round.saturating_add(1) < TRUST_ITERATIONS {
+                            TrustStage::Propagate(round.saturating_add(1))
+                        } else {
+                            TrustStage::Publish
+                        }
+                    }
+                    TrustStage::Publish => {
+                        TrustUpdate::<T>::kill();
+                        Self::deposit_event(Event::GlobalTrustUpdated {
+                            accounts: progress.accounts,
+                        });
+                        return used;
+                    }
+                };
+            }
+
+            TrustUpdate::<T>::put(progress);
+            used
+        }
+
+        /// Process the review or account after the cursor in the current stage of a
+        /// global trust recomputation. Returns false once the stage has none left.
+        ///
+        /// The stages follow EigenTrust: local trust is normalised per reviewer,
+        /// propagated along reviews and mixed with a pre-trust distribution
+        /// proportional to scores, `TRUST_ITERATIONS` times.
+        fn trust_step(progress: &mut TrustProgress<BlockNumberFor<T>>) -> bool {
+            match progress.stage {
+                TrustStage::Graph | TrustStage::Propagate(_) => {
+                    let mut reviews = if progress.cursor.is_empty() {
+                        Reviews::<T>::iter()
+                    } else {
+                        Reviews::<T>::iter_from(progress.cursor.clone())
+                    };
+                    let Some(((reviewer, reviewee, _), review)) = reviews.next() else {
+                        return false;
+                    };
+                    progress.cursor = reviews.last_raw_key().to_vec();
+
+                    // Reviews left during the recomputation wait for the next one
+                    if review.created_at >= progress.started_at {
+                        return true;
+                    }
+                    let local = Self::local_trust(review.rating);
+                    if progress.stage == TrustStage::Graph {
+                        for account in [&reviewer, &reviewee] {
+                            if !TrustNodes::<T>::contains_key(account) {
+                                TrustNodes::<T>::insert(account, TrustNode::default());
+                                progress.accounts = progress.accounts.saturating_add(1);
+                            }
+                        }
+                        TrustNodes::<T>::mutate(&reviewer, |node| {
+                            node.outgoing = node.outgoing.saturating_add(local)
+                        });
+                    } else if local > 0 {
+                        let from = TrustNodes::<T>::get(&reviewer);
+                        let share = from.trust.saturating_mul(local) / from.outgoing.max(1);
+                        TrustNodes::<T>::mutate(&reviewee, |node| {
+                            node.next = node.next.saturating_add(share)
+                        });
+                    }
+                }
+                TrustStage::Scores | TrustStage::Seed | TrustStage::Mix(_) => {
+                    let mut nodes = if progress.cursor.is_empty() {
+                        TrustNodes::<T>::iter()
+                    } else {
+                        TrustNodes::<T>::iter_from(progress.cursor.clone())
+                    };
+                    let Some((account, mut node)) = nodes.next() else {
+                        return false;
+                    };
+                    progress.cursor = nodes.last_raw_key().to_vec();
+
+                    let pre_trust = u128::from(node.score).saturating_mul(TRUST_SCALE)
+                        / progress.total_score.max(1);
+                    match progress.stage {
+                        TrustStage::Scores => {
+                            node.score =
+                                <Self as ReputationManager<_, _>>::get_reputation(&account);
+                            progress.total_score =
+                                progress.total_score.saturating_add(node.score.into());
+                        }
+                        TrustStage::Seed => {
+                            node.trust = pre_trust;
+                            if node.outgoing == 0 {
+                                progress.dangling = progress.dangling.saturating_add(node.trust);
+                            }
+                        }
+                        _ => {
+                            // Accounts that trust nobody pass their trust to the pre-trusted
+                            let propagated = node.next.saturating_add(
+                                progress.dangling.saturating_mul(pre_trust) / TRUST_SCALE,
+                            );
+                            node.trust = (propagated.saturating_mul(100 - PRE_TRUST_PERCENT)
+                                + pre_trust.saturating_mul(PRE_TRUST_PERCENT))
+                                / 100;
+                            node.next = 0;
+                            if node.outgoing == 0 {
+                                progress.next_dangling =
+                                    progress.next_dangling.saturating_add(node.trust);
+                            }
+                        }
+                    }
+                    TrustNodes::<T>::insert(&account, node);
+                }
+                TrustStage::Publish => {
+                    let Some((account, node)) = TrustNodes::<T>::drain().next() else {
+                        return false;
+                    };
+                    // Scaled so the average account in the graph has `InitialReputation`
+                    let scale = u128::from(progress.accounts)
+                        .saturating_mul(T::InitialReputation::get().into());
+                    let standing =
+                        (node.trust.saturating_mul(scale) / TRUST_SCALE).min(MAX_SCORE.into());
+                    GlobalTrust::<T>::insert(account, standing as u32);
+                }
+            }
+            true
+        }
+
         /// Add an event to reputation history (removes oldest if full).
         fn add_to_history(account: &T::AccountId, event: ReputationEvent<T>) {
             ReputationHistory::<T>::mutate(account, |history| {
@@ -411,38 +1383,49 @@ pub mod pallet {
     // ========== ReputationManager Trait Implementation ==========
 
     impl<T: Config> ReputationManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
-        fn on_task_completed(worker: &T::AccountId, earned: BalanceOf<T>) {
-            Reputations::<T>::mutate(worker, |rep| {
+        fn on_task_completed(worker: &T::AccountId, earned: BalanceOf<T>, category: TaskCategory) {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let complete = |rep: &mut ReputationInfo<T>| {
+                rep.last_active = now;
                 rep.total_tasks_completed = rep.total_tasks_completed.saturating_add(1);
                 rep.successful_completions = rep.successful_completions.saturating_add(1);
                 rep.total_earned = rep.total_earned.saturating_add(earned);
-                rep.last_active = <frame_system::Pallet<T>>::block_number();
-            });
-
-            // Note: Actual reputation boost happens when review is submitted
-            // This just records the completion
+                Self::record_settled(rep, earned);
+                Self::record_outcome(rep, true, earned);
+            };
+            Self::update_reputation(worker, complete);
+            Self::update_category(worker, category, complete);
         }
 
         fn on_task_posted(poster: &T::AccountId, spent: BalanceOf<T>) {
-            Reputations::<T>::mutate(poster, |rep| {
+            Self::update_reputation(poster, |rep| {
                 rep.total_tasks_posted = rep.total_tasks_posted.saturating_add(1);
                 rep.total_spent = rep.total_spent.saturating_add(spent);
-                rep.last_active = <frame_system::Pallet<T>>::block_number();
             });
         }
 
-        fn on_dispute_resolved(winner: &T::AccountId, loser: &T::AccountId) {
-            // Winner gains +200 reputation
-            Self::apply_reputation_change(winner, 200, false);
-            Reputations::<T>::mutate(winner, |rep| {
-                rep.disputes_won = rep.disputes_won.saturating_add(1);
-            });
+        fn on_task_approved(poster: &T::AccountId, spent: BalanceOf<T>) {
+            Self::update_reputation(poster, |rep| Self::record_settled(rep, spent));
+        }
 
-            // Loser loses -500 reputation
-            Self::apply_reputation_change(loser, -500, false);
-            Reputations::<T>::mutate(loser, |rep| {
+        fn on_dispute_resolved(
+            winner: &T::AccountId,
+            loser: &T::AccountId,
+            value: BalanceOf<T>,
+            category: TaskCategory,
+        ) {
+            let win = |rep: &mut ReputationInfo<T>| {
+                rep.disputes_won = rep.disputes_won.saturating_add(1);
+                Self::record_outcome(rep, true, value);
+            };
+            let lose = |rep: &mut ReputationInfo<T>| {
                 rep.disputes_lost = rep.disputes_lost.saturating_add(1);
-            });
+                Self::record_outcome(rep, false, value);
+            };
+            Self::update_reputation(winner, win);
+            Self::update_category(winner, category, win);
+            Self::update_reputation(loser, lose);
+            Self::update_category(loser, category, lose);
 
             Self::deposit_event(Event::DisputeResolved {
                 winner: winner.clone(),
@@ -451,11 +1434,151 @@ pub mod pallet {
         }
 
         fn get_reputation(account: &T::AccountId) -> u32 {
-            Reputations::<T>::get(account).score
+            Self::decayed_reputation(account).score
         }
 
-        fn meets_minimum_reputation(account: &T::AccountId, minimum: u32) -> bool {
-            Self::get_reputation(account) >= minimum
+        fn meets_minimum_reputation(
+            account: &T::AccountId,
+            minimum: u32,
+            category: Option<TaskCategory>,
+        ) -> bool {
+            let score = match category {
+                Some(category) => Self::category_score(account, category),
+                None => Self::get_reputation(account),
+            };
+            score >= minimum
+        }
+    }
+
+    impl<T: Config> ReputationScore<T::AccountId> for Pallet<T> {
+        fn score(account: &T::AccountId) -> u32 {
+            <Self as ReputationManager<_, _>>::get_reputation(account)
+        }
+    }
+
+    impl<T: Config> OnWorkValidated<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn on_work_validated(
+            worker: &T::AccountId,
+            validators: &[T::AccountId],
+            value: BalanceOf<T>,
+            score: u8,
+        ) {
+            // Weighted like a review by the validators' average standing
+            let weight = validators
+                .iter()
+                .map(|validator| Self::review_weight(validator, value))
+                .sum::<u64>()
+                / (validators.len() as u64).max(1);
+            // Scaled to basis points, 50 is neutral
+            Self::record_rating(worker, u64::from(score.min(100)) * 100, weight);
+            Self::add_to_history(worker, ReputationEvent::<T>::WorkValidated { score });
+        }
+    }
+
+    // ========== Runtime API Helpers ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Score and aggregates for `account`.
+        pub fn reputation_summary(account: &T::AccountId) -> ReputationSummaryOf<T> {
+            let rep = Self::decayed_reputation(account);
+            ReputationSummary {
+                score: rep.score,
+                total_tasks_completed: rep.total_tasks_completed,
+                total_tasks_posted: rep.total_tasks_posted,
+                successful_completions: rep.successful_completions,
+                disputes_won: rep.disputes_won,
+                disputes_lost: rep.disputes_lost,
+                total_earned: rep.total_earned,
+                total_spent: rep.total_spent,
+                last_active: rep.last_active,
+                ratings_received: rep.ratings_received,
+                penalty: rep.penalty,
+            }
+        }
+
+        /// Reputation history of `account`, oldest first, skipping the first `start`
+        /// entries and returning at most `limit`.
+        pub fn history(account: &T::AccountId, start: u32, limit: u32) -> Vec<HistoryEntryOf<T>> {
+            ReputationHistory::<T>::get(account)
+                .into_iter()
+                .skip(start as usize)
+                .take(limit as usize)
+                .map(Into::into)
+                .collect()
+        }
+
+        /// Reviews left for `reviewee`, oldest first, skipping the first `start` and
+        /// returning at most `limit`.
+        pub fn reviews_received(
+            reviewee: &T::AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<ReviewSummaryOf<T>> {
+            let end = ReviewsReceivedCount::<T>::get(reviewee).min(start.saturating_add(limit));
+            (start..end)
+                .filter_map(|index| {
+                    let (reviewer, task_id) = ReviewsReceived::<T>::get(reviewee, index)?;
+                    let review = Reviews::<T>::get((&reviewer, reviewee, task_id))?;
+                    Some(Self::summarize_review(reviewer, reviewee.clone(), review))
+                })
+                .collect()
+        }
+
+        /// Reviews left by `reviewer`.
+        pub fn reviews_given(reviewer: &T::AccountId) -> Vec<ReviewSummaryOf<T>> {
+            Reviews::<T>::iter_prefix((reviewer,))
+                .map(|((to, _), review)| Self::summarize_review(reviewer.clone(), to, review))
+                .collect()
+        }
+
+        /// The `limit` highest-scoring of at most `MAX_SCANNED_ACCOUNTS` accounts
+        /// after `start`, highest first. Accounts are scanned in storage order, so a
+        /// full leaderboard merges the pages.
+        pub fn leaderboard(start: Option<&T::AccountId>, limit: u32) -> LeaderboardPageOf<T> {
+            let mut accounts = match start {
+                Some(account) => {
+                    Reputations::<T>::iter_keys_from(Reputations::<T>::hashed_key_for(account))
+                }
+                None => Reputations::<T>::iter_keys(),
+            };
+            let mut entries: Vec<(T::AccountId, u32)> = accounts
+                .by_ref()
+                .take(MAX_SCANNED_ACCOUNTS as usize)
+                .map(|account| {
+                    let score = <Self as ReputationManager<_, _>>::get_reputation(&account);
+                    (account, score)
+                })
+                .collect();
+            let next = match accounts.next() {
+                Some(_) => entries.last().map(|(account, _)| account.clone()),
+                None => None,
+            };
+            entries.sort_by_key(|(_, score)| core::cmp::Reverse(*score));
+            entries.truncate(limit as usize);
+            LeaderboardPage { entries, next }
+        }
+
+        /// ID of the authorisation `server` gave `client` to give feedback, if any.
+        pub fn feedback_authorization(
+            server: &T::AccountId,
+            client: &T::AccountId,
+        ) -> Option<FeedbackAuthId> {
+            FeedbackAuthorizations::<T>::get(server, client)
+        }
+
+        fn summarize_review(
+            reviewer: T::AccountId,
+            reviewee: T::AccountId,
+            review: Review<T>,
+        ) -> ReviewSummaryOf<T> {
+            ReviewSummary {
+                reviewer,
+                reviewee,
+                rating: review.rating,
+                comment: review.comment.into_inner(),
+                task_id: review.task_id,
+                created_at: review.created_at,
+            }
         }
     }
 
@@ -464,6 +1587,8 @@ pub mod pallet {
     pub trait WeightInfo {
         fn submit_review() -> Weight;
         fn slash_reputation() -> Weight;
+        fn accept_feedback() -> Weight;
+        fn set_decay_parameters() -> Weight;
     }
 
     impl WeightInfo for () {
@@ -473,5 +1598,11 @@ pub mod pallet {
         fn slash_reputation() -> Weight {
             Weight::from_parts(10_000, 0)
         }
+        fn accept_feedback() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn set_decay_parameters() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
     }
 }
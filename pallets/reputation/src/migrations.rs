@@ -0,0 +1,549 @@
+//! Storage migrations for the Reputation pallet.
+
+use super::*;
+use alloc::collections::BTreeMap;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, storage::unhashed,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// Rewrite every `Reputations` entry from the `Old` to the `New` layout, neither of which
+/// need be the current one. Returns the number of entries rewritten.
+fn translate_raw<T: Config, Old: Decode, New: Encode + From<Old>>() -> u64 {
+    let mut translated: u64 = 0;
+    let accounts: Vec<T::AccountId> = Reputations::<T>::iter_keys().collect();
+    for account in accounts {
+        let key = Reputations::<T>::hashed_key_for(&account);
+        if let Some(old) = unhashed::get::<Old>(&key) {
+            unhashed::put(&key, &New::from(old));
+            translated = translated.saturating_add(1);
+        }
+    }
+    translated
+}
+
+/// Version 0 -> 1: add the rating and penalty aggregates and recompute every score.
+///
+/// Before v1 scores were accumulated from per-event deltas. Ratings are backfilled from
+/// the latest review per reviewer in `Reviews` (validator scores were not kept), and
+/// penalties from the `Slashed` entries still in each account's history. Every score is
+/// then recomputed and pushed to `OnReputationChanged`.
+pub mod v1 {
+    use super::*;
+
+    /// `ReputationInfo` as stored before v1.
+    #[derive(Encode, Decode)]
+    pub struct OldReputationInfo<T: Config> {
+        pub score: u32,
+        pub total_tasks_completed: u32,
+        pub total_tasks_posted: u32,
+        pub successful_completions: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub total_earned: BalanceOf<T>,
+        pub total_spent: BalanceOf<T>,
+        pub last_active: BlockNumberFor<T>,
+    }
+
+    /// `ReputationInfo` as stored in v1.
+    #[derive(Clone, Encode, Decode)]
+    pub struct ReputationInfoV1<T: Config> {
+        pub score: u32,
+        pub total_tasks_completed: u32,
+        pub total_tasks_posted: u32,
+        pub successful_completions: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub total_earned: BalanceOf<T>,
+        pub total_spent: BalanceOf<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub ratings_received: u32,
+        pub rating_points: u64,
+        pub penalty: u32,
+    }
+
+    /// Unversioned inner migration. Use [`RecomputeScores`] in the runtime.
+    pub struct RecomputeScoresInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for RecomputeScoresInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut writes: u64 = 0;
+
+            let mut ratings: BTreeMap<T::AccountId, (u32, u64)> = BTreeMap::new();
+            for (_, reviewee, review) in v4::Reviews::<T>::iter() {
+                reads = reads.saturating_add(1);
+                let (count, points) = ratings.entry(reviewee).or_default();
+                *count = count.saturating_add(1);
+                *points = points.saturating_add(Pallet::<T>::review_points(review.rating));
+            }
+
+            // Written in the v1 layout, which `Reputations` no longer decodes
+            let accounts: Vec<T::AccountId> = Reputations::<T>::iter_keys().collect();
+            for account in accounts {
+                reads = reads.saturating_add(2);
+                let key = Reputations::<T>::hashed_key_for(&account);
+                let Some(old) = unhashed::get::<OldReputationInfo<T>>(&key) else {
+                    continue;
+                };
+                writes = writes.saturating_add(2);
+                let penalty = ReputationHistory::<T>::get(&account)
+                    .into_iter()
+                    .filter_map(|event| match event {
+                        ReputationEvent::Slashed { amount, .. } => Some(amount),
+                        _ => None,
+                    })
+                    .fold(0u32, |total, amount| total.saturating_add(amount));
+                let (ratings_received, rating_points) =
+                    ratings.get(&account).copied().unwrap_or_default();
+                let mut rep = ReputationInfoV1 {
+                    score: old.score,
+                    total_tasks_completed: old.total_tasks_completed,
+                    total_tasks_posted: old.total_tasks_posted,
+                    successful_completions: old.successful_completions,
+                    disputes_won: old.disputes_won,
+                    disputes_lost: old.disputes_lost,
+                    total_earned: old.total_earned,
+                    total_spent: old.total_spent,
+                    last_active: old.last_active,
+                    ratings_received,
+                    rating_points,
+                    penalty,
+                };
+                let current = ReputationInfo::from(v5::ReputationInfoV5::from(
+                    v3::ReputationInfoV3::from(v2::ReputationInfoV2::from(rep.clone())),
+                ));
+                rep.score = Pallet::<T>::compute_score(&current);
+                T::OnReputationChanged::on_reputation_changed(&account, rep.score);
+                unhashed::put(&key, &rep);
+            }
+
+            log::info!(
+                target: "runtime::reputation",
+                "v1: recomputed the scores of {} accounts",
+                writes / 2,
+            );
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Recomputes scores from aggregates, then bumps the storage version to 1.
+    pub type RecomputeScores<T> = VersionedMigration<
+        0,
+        1,
+        RecomputeScoresInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 1 -> 2: weight ratings.
+///
+/// Ratings recorded before v2 count as full-weight ratings, so scores are unchanged.
+pub mod v2 {
+    use super::*;
+    use v1::ReputationInfoV1;
+
+    /// `ReputationInfo` as stored in v2.
+    #[derive(Clone, Encode, Decode)]
+    pub struct ReputationInfoV2<T: Config> {
+        pub score: u32,
+        pub total_tasks_completed: u32,
+        pub total_tasks_posted: u32,
+        pub successful_completions: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub total_earned: BalanceOf<T>,
+        pub total_spent: BalanceOf<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub ratings_received: u32,
+        pub rating_points: u64,
+        pub rating_weight: u64,
+        pub penalty: u32,
+    }
+
+    impl<T: Config> From<ReputationInfoV1<T>> for ReputationInfoV2<T> {
+        fn from(rep: ReputationInfoV1<T>) -> Self {
+            let full = u64::from(MAX_SCORE);
+            ReputationInfoV2 {
+                score: rep.score,
+                total_tasks_completed: rep.total_tasks_completed,
+                total_tasks_posted: rep.total_tasks_posted,
+                successful_completions: rep.successful_completions,
+                disputes_won: rep.disputes_won,
+                disputes_lost: rep.disputes_lost,
+                total_earned: rep.total_earned,
+                total_spent: rep.total_spent,
+                last_active: rep.last_active,
+                ratings_received: rep.ratings_received,
+                rating_points: rep.rating_points.saturating_mul(full),
+                rating_weight: u64::from(rep.ratings_received).saturating_mul(full),
+                penalty: rep.penalty,
+            }
+        }
+    }
+
+    /// Unversioned inner migration. Use [`WeightRatings`] in the runtime.
+    pub struct WeightRatingsInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for WeightRatingsInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let translated = translate_raw::<T, ReputationInfoV1<T>, ReputationInfoV2<T>>();
+
+            log::info!(
+                target: "runtime::reputation",
+                "v2: weighted the ratings of {} accounts",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Converts ratings to weighted ratings, then bumps the storage version to 2.
+    pub type WeightRatings<T> = VersionedMigration<
+        1,
+        2,
+        WeightRatingsInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 2 -> 3: weight the completion and dispute record so it can decay.
+///
+/// Outcomes recorded before v3 count as full-weight outcomes, so scores are unchanged.
+/// Decay starts from each account's `last_active`.
+pub mod v3 {
+    use super::*;
+    use v2::ReputationInfoV2;
+
+    /// `ReputationInfo` as stored in v3.
+    #[derive(Clone, Encode, Decode)]
+    pub struct ReputationInfoV3<T: Config> {
+        pub score: u32,
+        pub total_tasks_completed: u32,
+        pub total_tasks_posted: u32,
+        pub successful_completions: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub total_earned: BalanceOf<T>,
+        pub total_spent: BalanceOf<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub ratings_received: u32,
+        pub rating_points: u64,
+        pub rating_weight: u64,
+        pub record_points: u64,
+        pub record_weight: u64,
+        pub penalty: u32,
+    }
+
+    impl<T: Config> From<ReputationInfoV2<T>> for ReputationInfoV3<T> {
+        fn from(rep: ReputationInfoV2<T>) -> Self {
+            let full = u64::from(MAX_SCORE);
+            let successes = u64::from(rep.successful_completions.saturating_add(rep.disputes_won));
+            let outcomes = successes.saturating_add(rep.disputes_lost.into());
+            ReputationInfoV3 {
+                score: rep.score,
+                total_tasks_completed: rep.total_tasks_completed,
+                total_tasks_posted: rep.total_tasks_posted,
+                successful_completions: rep.successful_completions,
+                disputes_won: rep.disputes_won,
+                disputes_lost: rep.disputes_lost,
+                total_earned: rep.total_earned,
+                total_spent: rep.total_spent,
+                last_active: rep.last_active,
+                ratings_received: rep.ratings_received,
+                rating_points: rep.rating_points,
+                rating_weight: rep.rating_weight,
+                record_points: successes.saturating_mul(full).saturating_mul(full),
+                record_weight: outcomes.saturating_mul(full),
+                penalty: rep.penalty,
+            }
+        }
+    }
+
+    /// Unversioned inner migration. Use [`WeightRecord`] in the runtime.
+    pub struct WeightRecordInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for WeightRecordInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let translated = translate_raw::<T, ReputationInfoV2<T>, ReputationInfoV3<T>>();
+
+            log::info!(
+                target: "runtime::reputation",
+                "v3: weighted the records of {} accounts",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Converts outcome counts to weighted records, then bumps the storage version to 3.
+    pub type WeightRecord<T> = VersionedMigration<
+        2,
+        3,
+        WeightRecordInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 3 -> 4: keep a review per task rather than per pair of accounts.
+///
+/// `Reviews` was keyed by (reviewer, reviewee), so each review overwrote the pair's
+/// previous one. Surviving reviews are re-keyed by (reviewer, reviewee, task_id), which
+/// also makes `TaskReviews` redundant.
+pub mod v4 {
+    use super::*;
+
+    /// `Reviews` as stored before v4.
+    #[frame_support::storage_alias]
+    pub type Reviews<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Review<T>,
+        OptionQuery,
+    >;
+
+    /// Reviews already left, by (task_id, reviewer), removed in v4.
+    #[frame_support::storage_alias]
+    pub type TaskReviews<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Twox64Concat,
+        u64,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Unversioned inner migration. Use [`ReviewPerTask`] in the runtime.
+    pub struct ReviewPerTaskInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for ReviewPerTaskInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let reviews: Vec<_> = Reviews::<T>::drain().collect();
+            let moved = reviews.len() as u64;
+            for (reviewer, reviewee, review) in reviews {
+                crate::Reviews::<T>::insert((reviewer, reviewee, review.task_id), review);
+            }
+            let cleared = TaskReviews::<T>::clear(u32::MAX, None).unique as u64;
+
+            log::info!(
+                target: "runtime::reputation",
+                "v4: re-keyed {} reviews by task",
+                moved,
+            );
+
+            T::DbWeight::get().reads_writes(
+                moved.saturating_add(cleared),
+                moved.saturating_mul(2).saturating_add(cleared),
+            )
+        }
+    }
+
+    /// Re-keys reviews by task, then bumps the storage version to 4.
+    pub type ReviewPerTask<T> = VersionedMigration<
+        3,
+        4,
+        ReviewPerTaskInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 4 -> 5: add `ReputationInfo::settled_weight`.
+///
+/// Before v5 the track record counted every task posted or completed, whatever its
+/// value and whether it was ever settled. Those counts cannot tell settled tasks from
+/// cancelled ones or cheap from valuable, so track records restart from the tasks
+/// settled after the upgrade.
+pub mod v5 {
+    use super::*;
+    use v3::ReputationInfoV3;
+
+    /// `ReputationInfo` as stored in v5.
+    #[derive(Clone, Encode, Decode)]
+    pub struct ReputationInfoV5<T: Config> {
+        pub score: u32,
+        pub total_tasks_completed: u32,
+        pub total_tasks_posted: u32,
+        pub successful_completions: u32,
+        pub disputes_won: u32,
+        pub disputes_lost: u32,
+        pub total_earned: BalanceOf<T>,
+        pub total_spent: BalanceOf<T>,
+        pub last_active: BlockNumberFor<T>,
+        pub ratings_received: u32,
+        pub rating_points: u64,
+        pub rating_weight: u64,
+        pub record_points: u64,
+        pub record_weight: u64,
+        pub settled_weight: u64,
+        pub penalty: u32,
+    }
+
+    impl<T: Config> From<ReputationInfoV3<T>> for ReputationInfoV5<T> {
+        fn from(rep: ReputationInfoV3<T>) -> Self {
+            ReputationInfoV5 {
+                score: rep.score,
+                total_tasks_completed: rep.total_tasks_completed,
+                total_tasks_posted: rep.total_tasks_posted,
+                successful_completions: rep.successful_completions,
+                disputes_won: rep.disputes_won,
+                disputes_lost: rep.disputes_lost,
+                total_earned: rep.total_earned,
+                total_spent: rep.total_spent,
+                last_active: rep.last_active,
+                ratings_received: rep.ratings_received,
+                rating_points: rep.rating_points,
+                rating_weight: rep.rating_weight,
+                record_points: rep.record_points,
+                record_weight: rep.record_weight,
+                settled_weight: 0,
+                penalty: rep.penalty,
+            }
+        }
+    }
+
+    /// Unversioned inner migration. Use [`AddSettledWeight`] in the runtime.
+    pub struct AddSettledWeightInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for AddSettledWeightInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let translated = translate_raw::<T, ReputationInfoV3<T>, ReputationInfoV5<T>>();
+
+            log::info!(
+                target: "runtime::reputation",
+                "v5: reset the track records of {} accounts",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Adds `ReputationInfo::settled_weight`, then bumps the storage version to 5.
+    pub type AddSettledWeight<T> = VersionedMigration<
+        4,
+        5,
+        AddSettledWeightInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 5 -> 6: add `ReputationInfo::decayed_at`.
+///
+/// Before v6 every update refreshed `last_active`, so each account's evidence has been
+/// decayed up to its `last_active`.
+pub mod v6 {
+    use super::*;
+    use v5::ReputationInfoV5;
+
+    impl<T: Config> From<ReputationInfoV5<T>> for ReputationInfo<T> {
+        fn from(rep: ReputationInfoV5<T>) -> Self {
+            ReputationInfo {
+                score: rep.score,
+                total_tasks_completed: rep.total_tasks_completed,
+                total_tasks_posted: rep.total_tasks_posted,
+                successful_completions: rep.successful_completions,
+                disputes_won: rep.disputes_won,
+                disputes_lost: rep.disputes_lost,
+                total_earned: rep.total_earned,
+                total_spent: rep.total_spent,
+                last_active: rep.last_active,
+                decayed_at: rep.last_active,
+                ratings_received: rep.ratings_received,
+                rating_points: rep.rating_points,
+                rating_weight: rep.rating_weight,
+                record_points: rep.record_points,
+                record_weight: rep.record_weight,
+                settled_weight: rep.settled_weight,
+                penalty: rep.penalty,
+            }
+        }
+    }
+
+    /// Unversioned inner migration. Use [`AddDecayedAt`] in the runtime.
+    pub struct AddDecayedAtInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for AddDecayedAtInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let translated = translate_raw::<T, ReputationInfoV5<T>, ReputationInfo<T>>();
+
+            log::info!(
+                target: "runtime::reputation",
+                "v6: added the decay block to {} accounts",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Adds `ReputationInfo::decayed_at`, then bumps the storage version to 6.
+    pub type AddDecayedAt<T> = VersionedMigration<
+        5,
+        6,
+        AddDecayedAtInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 6 -> 7: index the reviews each account has received.
+///
+/// `ReviewsReceived` is filled in from `Reviews`, each account's reviews in the order
+/// they were left.
+pub mod v7 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`IndexReviewsReceived`] in the runtime.
+    pub struct IndexReviewsReceivedInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for IndexReviewsReceivedInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut received: BTreeMap<T::AccountId, Vec<_>> = BTreeMap::new();
+            for ((reviewer, reviewee, task_id), review) in Reviews::<T>::iter() {
+                received
+                    .entry(reviewee)
+                    .or_default()
+                    .push((review.created_at, reviewer, task_id));
+            }
+
+            let mut indexed: u64 = 0;
+            for (reviewee, mut reviews) in received {
+                reviews.sort_by_key(|(created_at, _, _)| *created_at);
+                for (_, reviewer, task_id) in reviews {
+                    Pallet::<T>::index_review_received(&reviewee, &reviewer, task_id);
+                    indexed = indexed.saturating_add(1);
+                }
+            }
+
+            log::info!(
+                target: "runtime::reputation",
+                "v7: indexed {} received reviews",
+                indexed,
+            );
+
+            T::DbWeight::get().reads_writes(indexed.saturating_mul(2), indexed.saturating_mul(2))
+        }
+    }
+
+    /// Indexes received reviews, then bumps the storage version to 7.
+    pub type IndexReviewsReceived<T> = VersionedMigration<
+        6,
+        7,
+        IndexReviewsReceivedInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -1,11 +1,9 @@
 use crate::{self as pallet_reputation, *};
-use frame_support::{
-    assert_ok, assert_noop, parameter_types,
-};
+use frame_support::{assert_noop, assert_ok, parameter_types};
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    BuildStorage,
+    BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -38,7 +36,7 @@ impl frame_system::Config for Test {
     type Block = Block;
     type RuntimeEvent = RuntimeEvent;
     type BlockHashCount = BlockHashCount;
-    type DbWeight = ();
+    type DbWeight = frame_support::weights::constants::RocksDbWeight;
     type Version = ();
     type PalletInfo = PalletInfo;
     type AccountData = pallet_balances::AccountData<u64>;
@@ -81,8 +79,46 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const MaxCommentLength: u32 = 256;
     pub const InitialReputation: u32 = 5000;
-    pub const MaxReputationDelta: u32 = 500;
+    pub const PriorWeight: u32 = 4;
+    pub const RatingShare: u8 = 50;
+    pub const ReviewValueCap: u64 = 100;
+    pub const TrustUpdatePeriod: u64 = 10;
+    pub static MaxTrustStepsPerBlock: u32 = 1000;
     pub const MaxHistoryLength: u32 = 100;
+    pub const DecayGracePeriod: u64 = 100;
+    pub const DecayHalfLife: u64 = 100;
+    pub static ApprovedTasks: Vec<(u64, u64, u64, u64, TaskCategory)> = vec![];
+}
+
+/// Treats the (task_id, poster, worker, reward, category) entries of `ApprovedTasks` as
+/// approved tasks.
+pub struct MockTasks;
+
+impl ReviewableTasks<u64, u64> for MockTasks {
+    fn counterparty(task_id: u64, reviewer: &u64) -> Option<(u64, u64, TaskCategory)> {
+        ApprovedTasks::get()
+            .into_iter()
+            .find(|(id, _, _, _, _)| *id == task_id)
+            .and_then(|(_, poster, worker, reward, category)| match *reviewer {
+                r if r == poster => Some((worker, reward, category)),
+                r if r == worker => Some((poster, reward, category)),
+                _ => None,
+            })
+    }
+}
+
+fn approve_task(task_id: u64, poster: u64, worker: u64) {
+    approve_task_worth(task_id, poster, worker, ReviewValueCap::get());
+}
+
+fn approve_task_worth(task_id: u64, poster: u64, worker: u64, reward: u64) {
+    approve_task_in(task_id, poster, worker, reward, 0);
+}
+
+fn approve_task_in(task_id: u64, poster: u64, worker: u64, reward: u64, category: TaskCategory) {
+    Reputation::on_task_posted(&poster, reward);
+    Reputation::on_task_approved(&poster, reward);
+    ApprovedTasks::mutate(|tasks| tasks.push((task_id, poster, worker, reward, category)));
 }
 
 impl pallet_reputation::Config for Test {
@@ -91,8 +127,16 @@ impl pallet_reputation::Config for Test {
     type Currency = Balances;
     type MaxCommentLength = MaxCommentLength;
     type InitialReputation = InitialReputation;
-    type MaxReputationDelta = MaxReputationDelta;
+    type PriorWeight = PriorWeight;
+    type RatingShare = RatingShare;
+    type ReviewValueCap = ReviewValueCap;
+    type TrustUpdatePeriod = TrustUpdatePeriod;
+    type MaxTrustStepsPerBlock = MaxTrustStepsPerBlock;
+    type DecayGracePeriod = DecayGracePeriod;
+    type DecayHalfLife = DecayHalfLife;
     type MaxHistoryLength = MaxHistoryLength;
+    type OnReputationChanged = ();
+    type ReviewableTasks = MockTasks;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -130,6 +174,7 @@ fn submit_review_works() {
         let rating = 5;
         let comment = b"Excellent work!".to_vec();
         let task_id = 1;
+        approve_task(task_id, reviewer, reviewee);
 
         // Submit review
         assert_ok!(Reputation::submit_review(
@@ -141,13 +186,15 @@ fn submit_review_works() {
         ));
 
         // Check review was stored
-        let review = Reputation::reviews(reviewer, reviewee).unwrap();
+        let review = Reputation::reviews((reviewer, reviewee, task_id)).unwrap();
         assert_eq!(review.rating, rating);
         assert_eq!(review.task_id, task_id);
 
         // Check reputation increased (5 stars = +500)
         let rep = Reputation::reputations(reviewee);
-        assert_eq!(rep.score, 5500); // 5000 + 500
+        // The reviewer has posted one task, so their 5000 standing counts a fifth: a
+        // tenth of a full rating, ratings 5121
+        assert_eq!(rep.score, 5060);
     });
 }
 
@@ -155,7 +202,7 @@ fn submit_review_works() {
 fn cannot_review_self() {
     new_test_ext().execute_with(|| {
         let account = 1;
-        
+
         assert_noop!(
             Reputation::submit_review(
                 RuntimeOrigin::signed(account),
@@ -174,37 +221,26 @@ fn invalid_rating_fails() {
     new_test_ext().execute_with(|| {
         // Rating 0 should fail
         assert_noop!(
-            Reputation::submit_review(
-                RuntimeOrigin::signed(1),
-                2,
-                0,
-                b"Comment".to_vec(),
-                1
-            ),
+            Reputation::submit_review(RuntimeOrigin::signed(1), 2, 0, b"Comment".to_vec(), 1),
             Error::<Test>::InvalidRating
         );
 
         // Rating 6 should fail
         assert_noop!(
-            Reputation::submit_review(
-                RuntimeOrigin::signed(1),
-                2,
-                6,
-                b"Comment".to_vec(),
-                1
-            ),
+            Reputation::submit_review(RuntimeOrigin::signed(1), 2, 6, b"Comment".to_vec(), 1),
             Error::<Test>::InvalidRating
         );
     });
 }
 
 #[test]
-fn reputation_clamped_at_max() {
+fn perfect_record_approaches_max() {
     new_test_ext().execute_with(|| {
         let account = 1;
 
-        // Submit multiple 5-star reviews to push over 10000
+        // 25 five-star reviews alone only move the ratings half of the score
         for i in 0..25 {
+            approve_task(i, 2, account);
             assert_ok!(Reputation::submit_review(
                 RuntimeOrigin::signed(2),
                 account,
@@ -213,10 +249,15 @@ fn reputation_clamped_at_max() {
                 i
             ));
         }
+        // The poster's reviews weigh more with every task they post, from a tenth to
+        // under half a full rating: ratings 8430, blended with the neutral record
+        assert_eq!(Reputation::reputations(account).score, 6715);
 
-        // Should be clamped at 10000
-        let rep = Reputation::reputations(account);
-        assert_eq!(rep.score, 10000);
+        for _ in 0..25 {
+            Reputation::on_task_completed(&account, 100, 0);
+        }
+        // Record (4 * 5000 + 25 * 10000) / 29 = 9310
+        assert_eq!(Reputation::reputations(account).score, 8870);
     });
 }
 
@@ -241,6 +282,11 @@ fn slash_reputation_works() {
         // Check reputation decreased
         let rep = Reputation::reputations(account);
         assert_eq!(rep.score, 4000); // 5000 - 1000
+        assert_eq!(rep.penalty, 1000);
+
+        // The penalty persists when the score is recomputed
+        Reputation::on_task_completed(&account, 100, 0);
+        assert_eq!(Reputation::reputations(account).score, 4500);
     });
 }
 
@@ -249,12 +295,7 @@ fn slash_reputation_requires_root() {
     new_test_ext().execute_with(|| {
         // Non-root should fail
         assert_noop!(
-            Reputation::slash_reputation(
-                RuntimeOrigin::signed(1),
-                2,
-                1000,
-                b"Reason".to_vec()
-            ),
+            Reputation::slash_reputation(RuntimeOrigin::signed(1), 2, 1000, b"Reason".to_vec()),
             sp_runtime::DispatchError::BadOrigin
         );
     });
@@ -269,7 +310,7 @@ fn reputation_manager_trait_works() {
         let spent = 1000u64;
 
         // Test on_task_completed
-        Reputation::on_task_completed(&worker, earned);
+        Reputation::on_task_completed(&worker, earned, 0);
         let rep = Reputation::reputations(worker);
         assert_eq!(rep.total_tasks_completed, 1);
         assert_eq!(rep.successful_completions, 1);
@@ -281,13 +322,50 @@ fn reputation_manager_trait_works() {
         assert_eq!(rep.total_tasks_posted, 1);
         assert_eq!(rep.total_spent, spent);
 
-        // Test get_reputation
+        // Test get_reputation: the completion lifts the record to 6000
         let score = Reputation::get_reputation(&worker);
-        assert_eq!(score, 5000);
+        assert_eq!(score, 5500);
+        assert_eq!(Reputation::get_reputation(&poster), 5000);
 
         // Test meets_minimum_reputation
-        assert!(Reputation::meets_minimum_reputation(&worker, 4000));
-        assert!(!Reputation::meets_minimum_reputation(&worker, 6000));
+        assert!(Reputation::meets_minimum_reputation(&worker, 4000, None));
+        assert!(!Reputation::meets_minimum_reputation(&worker, 6000, None));
+    });
+}
+
+#[test]
+fn categories_keep_separate_scores() {
+    new_test_ext().execute_with(|| {
+        let worker = 1;
+        let poster = 2;
+
+        Reputation::on_task_completed(&worker, 1000, 1);
+        Reputation::on_dispute_resolved(&poster, &worker, 100, 2);
+
+        // Each outcome counts globally but only in its own category
+        assert_eq!(Reputation::reputations(worker).score, 5000);
+        assert_eq!(Reputation::category_score(&worker, 1), 5500);
+        assert_eq!(Reputation::category_score(&worker, 2), 4500);
+        assert_eq!(Reputation::category_score(&worker, 3), 5000);
+        assert!(Reputation::meets_minimum_reputation(&worker, 5000, None));
+        assert!(Reputation::meets_minimum_reputation(&worker, 5000, Some(1)));
+        assert!(!Reputation::meets_minimum_reputation(
+            &worker,
+            5000,
+            Some(2)
+        ));
+
+        // Reviews count in the category of the reviewed task
+        approve_task_in(1, poster, worker, ReviewValueCap::get(), 3);
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(poster),
+            worker,
+            1,
+            b"Poor".to_vec(),
+            1
+        ));
+        assert!(Reputation::category_score(&worker, 3) < 5000);
+        assert_eq!(Reputation::category_score(&worker, 1), 5500);
     });
 }
 
@@ -302,10 +380,10 @@ fn dispute_resolution_updates_reputation() {
         assert_eq!(Reputation::reputations(loser).score, 5000);
 
         // Resolve dispute
-        Reputation::on_dispute_resolved(&winner, &loser);
+        Reputation::on_dispute_resolved(&winner, &loser, 100, 0);
 
-        // Winner gains +200, loser loses -500
-        assert_eq!(Reputation::reputations(winner).score, 5200);
+        // Winner's record rises to 6000, loser's falls to 4000
+        assert_eq!(Reputation::reputations(winner).score, 5500);
         assert_eq!(Reputation::reputations(loser).score, 4500);
 
         // Check stats
@@ -315,13 +393,16 @@ fn dispute_resolution_updates_reputation() {
 }
 
 #[test]
-fn rating_scales_reputation_boost() {
+fn rating_scales_reputation_change() {
     new_test_ext().execute_with(|| {
         let reviewee1 = 1;
         let reviewee2 = 2;
         let reviewee3 = 3;
+        approve_task(1, 10, reviewee1);
+        approve_task(2, 10, reviewee2);
+        approve_task(3, 10, reviewee3);
 
-        // 1-star review: +100
+        // 1-star review lowers the score
         assert_ok!(Reputation::submit_review(
             RuntimeOrigin::signed(10),
             reviewee1,
@@ -329,9 +410,10 @@ fn rating_scales_reputation_boost() {
             b"Poor".to_vec(),
             1
         ));
-        assert_eq!(Reputation::reputations(reviewee1).score, 5100);
+        // The reviewer has posted three tasks: 3/7 of a half-weight rating
+        assert_eq!(Reputation::reputations(reviewee1).score, 4872);
 
-        // 3-star review: +300
+        // 3-star review is neutral
         assert_ok!(Reputation::submit_review(
             RuntimeOrigin::signed(10),
             reviewee2,
@@ -339,9 +421,9 @@ fn rating_scales_reputation_boost() {
             b"Average".to_vec(),
             2
         ));
-        assert_eq!(Reputation::reputations(reviewee2).score, 5300);
+        assert_eq!(Reputation::reputations(reviewee2).score, 5000);
 
-        // 5-star review: +500
+        // 5-star review raises it
         assert_ok!(Reputation::submit_review(
             RuntimeOrigin::signed(10),
             reviewee3,
@@ -349,6 +431,683 @@ fn rating_scales_reputation_boost() {
             b"Excellent".to_vec(),
             3
         ));
-        assert_eq!(Reputation::reputations(reviewee3).score, 5500);
+        assert_eq!(Reputation::reputations(reviewee3).score, 5127);
+    });
+}
+
+#[test]
+fn runtime_api_reputation_queries_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        approve_task(7, 1, 2);
+        approve_task(8, 3, 2);
+
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            5,
+            b"Great".to_vec(),
+            7
+        ));
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(3),
+            2,
+            4,
+            b"Good".to_vec(),
+            8
+        ));
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(2),
+            3,
+            2,
+            b"Meh".to_vec(),
+            8
+        ));
+        Reputation::on_task_completed(&2, 1000, 0);
+
+        let info = Reputation::reputation_summary(&2);
+        // Two ratings from one-task posters, a tenth of a full rating each:
+        // (4 * 5000 + 1000 + 750) / 4.2 = 5178, record 6000
+        assert_eq!(info.score, 5589);
+        assert_eq!(info.ratings_received, 2);
+        assert_eq!(info.total_tasks_completed, 1);
+        assert_eq!(info.total_earned, 1000);
+
+        let history = Reputation::history(&2, 0, 10);
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[0],
+            HistoryEntry::ReviewReceived { from: 1, rating: 5 }
+        );
+        assert_eq!(Reputation::history(&2, 1, 10).len(), 1);
+        assert_eq!(Reputation::history(&2, 0, 1).len(), 1);
+
+        let received: Vec<u64> = Reputation::reviews_received(&2, 0, 10)
+            .iter()
+            .map(|r| r.reviewer)
+            .collect();
+        assert_eq!(received, vec![1, 3]);
+        let page = Reputation::reviews_received(&2, 1, 10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].comment, b"Good".to_vec());
+        assert!(Reputation::reviews_received(&2, 2, 10).is_empty());
+        let given = Reputation::reviews_given(&2);
+        assert_eq!(given.len(), 1);
+        assert_eq!(given[0].reviewee, 3);
+        assert_eq!(given[0].comment, b"Meh".to_vec());
+
+        let leaderboard = Reputation::leaderboard(None, 2);
+        assert_eq!(leaderboard.entries, vec![(2, 5589), (3, 5000)]);
+        assert_eq!(leaderboard.next, None);
+    });
+}
+
+#[test]
+fn accept_feedback_authorizes_client_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Reputation::accept_feedback(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::SelfReview
+        );
+        assert_ok!(Reputation::accept_feedback(RuntimeOrigin::signed(1), 2));
+        System::assert_last_event(
+            Event::FeedbackAuthorized {
+                client: 2,
+                server: 1,
+                feedback_auth_id: 0,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Reputation::accept_feedback(RuntimeOrigin::signed(1), 2),
+            Error::<Test>::FeedbackAlreadyAuthorized
+        );
+
+        assert_ok!(Reputation::accept_feedback(RuntimeOrigin::signed(3), 2));
+        assert_eq!(Reputation::feedback_authorization(&1, &2), Some(0));
+        assert_eq!(Reputation::feedback_authorization(&3, &2), Some(1));
+        assert_eq!(Reputation::feedback_authorization(&2, &1), None);
+    });
+}
+
+#[test]
+fn work_validation_moves_reputation_with_score() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Validators with the initial standing and four tasks on record: a quarter of a
+        // full rating each
+        for validator in [4, 5] {
+            for _ in 0..4 {
+                Reputation::on_task_approved(&validator, 100);
+            }
+        }
+        Reputation::on_work_validated(&1, &[4, 5], 100, 100);
+        Reputation::on_work_validated(&2, &[4, 5], 100, 0);
+        Reputation::on_work_validated(&3, &[4, 5], 100, 50);
+        // Work on a worthless task carries no weight
+        Reputation::on_work_validated(&6, &[4], 0, 0);
+
+        assert_eq!(Reputation::reputations(1).rating_weight, 2500);
+        assert_eq!(Reputation::get_reputation(&1), 5147);
+        assert_eq!(Reputation::get_reputation(&2), 4852);
+        assert_eq!(Reputation::get_reputation(&3), 5000);
+        assert_eq!(Reputation::get_reputation(&6), 5000);
+        assert_eq!(
+            Reputation::history(&2, 0, 10)[0],
+            HistoryEntry::WorkValidated { score: 0 }
+        );
+    });
+}
+
+#[test]
+fn reviews_require_a_counterparty_on_an_approved_task() {
+    new_test_ext().execute_with(|| {
+        approve_task(1, 1, 2);
+
+        // A stranger, an unknown task or the wrong reviewee are all rejected
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(3), 2, 5, b"Friend".to_vec(), 1),
+            Error::<Test>::NotTaskParticipant
+        );
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(1), 2, 5, b"Great".to_vec(), 2),
+            Error::<Test>::NotTaskParticipant
+        );
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(1), 3, 5, b"Great".to_vec(), 1),
+            Error::<Test>::NotTaskParticipant
+        );
+
+        // Poster and worker review each other once
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            5,
+            b"Great".to_vec(),
+            1
+        ));
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(2),
+            1,
+            4,
+            b"Clear brief".to_vec(),
+            1
+        ));
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(1), 2, 5, b"Again".to_vec(), 1),
+            Error::<Test>::AlreadyReviewed
+        );
+        // The worker has no completed task on record yet, so their review has no weight
+        assert_eq!(Reputation::reputations(2).score, 5060);
+        assert_eq!(Reputation::reputations(1).score, 5000);
+
+        // A second task between the same pair gets its own review
+        approve_task(2, 1, 2);
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            1,
+            b"Sloppy".to_vec(),
+            2
+        ));
+        assert_eq!(Reputation::reviews((1, 2, 1)).unwrap().rating, 5);
+        assert_eq!(Reputation::reviews((1, 2, 2)).unwrap().rating, 1);
+        assert_eq!(Reputation::reviews_received(&2, 0, 10).len(), 2);
+        assert_eq!(Reputation::reviews_given(&1).len(), 2);
+    });
+}
+
+#[test]
+fn migrations_recompute_scores_from_aggregates() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+    new_test_ext().execute_with(|| {
+        // Delta-era state: inflated by a 1-star review, then slashed
+        let old = migrations::v1::OldReputationInfo::<Test> {
+            score: 4800,
+            total_tasks_completed: 1,
+            total_tasks_posted: 0,
+            successful_completions: 1,
+            disputes_won: 0,
+            disputes_lost: 0,
+            total_earned: 100,
+            total_spent: 0,
+            last_active: 1,
+        };
+        frame_support::storage::unhashed::put(&Reputations::<Test>::hashed_key_for(2), &old);
+        migrations::v4::Reviews::<Test>::insert(
+            1,
+            2,
+            Review::<Test> {
+                rating: 1,
+                comment: Default::default(),
+                task_id: 0,
+                created_at: 1,
+            },
+        );
+        ReputationHistory::<Test>::mutate(2, |history| {
+            history
+                .try_push(ReputationEvent::Slashed {
+                    amount: 300,
+                    reason: Default::default(),
+                })
+                .unwrap();
+        });
+
+        migrations::v1::RecomputeScores::<Test>::on_runtime_upgrade();
+        migrations::v2::WeightRatings::<Test>::on_runtime_upgrade();
+        migrations::v3::WeightRecord::<Test>::on_runtime_upgrade();
+        migrations::v4::ReviewPerTask::<Test>::on_runtime_upgrade();
+        migrations::v5::AddSettledWeight::<Test>::on_runtime_upgrade();
+        migrations::v6::AddDecayedAt::<Test>::on_runtime_upgrade();
+
+        let rep = Reputation::reputations(2);
+        assert_eq!(rep.ratings_received, 1);
+        assert_eq!(rep.rating_weight, 10000);
+        assert_eq!(rep.record_weight, 10000);
+        assert_eq!(rep.penalty, 300);
+        // Ratings 4000, record 6000, less the penalty
+        assert_eq!(rep.score, 4700);
+        assert_eq!(Reputation::compute_score(&rep), 4700);
+        assert_eq!(rep.settled_weight, 0);
+        assert_eq!(rep.decayed_at, 1);
+        assert_eq!(Reputation::on_chain_storage_version(), 6);
+        assert!(Reputation::reviews((1, 2, 0)).is_some());
+    });
+}
+
+#[test]
+fn leaderboard_scans_a_bounded_window_per_page() {
+    new_test_ext().execute_with(|| {
+        let accounts = u64::from(MAX_SCANNED_ACCOUNTS) + 5;
+        for account in 1..=accounts {
+            Reputation::on_task_posted(&account, 100);
+        }
+        Reputation::on_task_completed(&7, 100, 0);
+        Reputation::on_task_completed(&900, 100, 0);
+        Reputation::on_task_completed(&900, 100, 0);
+
+        let mut pages = 0;
+        let mut scanned = 0;
+        let mut top = Vec::new();
+        let mut start = None;
+        loop {
+            let page = Reputation::leaderboard(start.as_ref(), u32::MAX);
+            pages += 1;
+            scanned += page.entries.len() as u64;
+            top.extend(page.entries);
+            match page.next {
+                Some(next) => start = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 2);
+        assert_eq!(scanned, accounts);
+
+        top.sort_by_key(|(_, score)| core::cmp::Reverse(*score));
+        assert_eq!(top[..2], [(900, 5833), (7, 5500)]);
+        assert_eq!(Reputation::leaderboard(None, 1).entries.len(), 1);
+    });
+}
+
+#[test]
+fn v7_migration_indexes_received_reviews() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        for (task_id, reviewer) in [(1, 3), (2, 1)] {
+            System::set_block_number(task_id);
+            approve_task(task_id, reviewer, 2);
+            assert_ok!(Reputation::submit_review(
+                RuntimeOrigin::signed(reviewer),
+                2,
+                4,
+                b"Good".to_vec(),
+                task_id
+            ));
+        }
+
+        // Simulate reviews left before they were indexed
+        let _ = ReviewsReceived::<Test>::clear(u32::MAX, None);
+        ReviewsReceivedCount::<Test>::remove(2);
+        StorageVersion::new(6).put::<Reputation>();
+
+        migrations::v7::IndexReviewsReceived::<Test>::on_runtime_upgrade();
+        assert_eq!(Reputation::on_chain_storage_version(), 7);
+        let received: Vec<(u64, u64)> = Reputation::reviews_received(&2, 0, 10)
+            .iter()
+            .map(|r| (r.reviewer, r.task_id))
+            .collect();
+        assert_eq!(received, vec![(3, 1), (1, 2)]);
+    });
+}
+
+#[test]
+fn review_weight_follows_reviewer_standing_and_task_value() {
+    new_test_ext().execute_with(|| {
+        // A fresh account's initial score is no standing at all
+        assert_eq!(Reputation::review_weight(&1, 100), 0);
+
+        // Four settled tasks against a prior of four: half the standing counts
+        for _ in 0..4 {
+            Reputation::on_task_approved(&1, 100);
+        }
+        assert_eq!(Reputation::review_weight(&1, 100), 2500);
+        assert_eq!(Reputation::review_weight(&1, 1000), 2500);
+        assert_eq!(Reputation::review_weight(&1, 25), 625);
+
+        // Global trust replaces the score as the reviewer's standing
+        GlobalTrust::<Test>::insert(1, 9000);
+        assert_eq!(Reputation::review_weight(&1, 100), 4500);
+
+        approve_task_worth(1, 1, 2, 50);
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            1,
+            b"Poor".to_vec(),
+            1
+        ));
+        let rep = Reputation::reputations(2);
+        // 4.5 settled tasks on record, the last at half the task value cap, and the
+        // review at half the cap too
+        assert_eq!(rep.rating_weight, 2382);
+        assert_eq!(rep.rating_points, 0);
+        // (4 * 5000) / 4.2382 = 4719 for ratings, neutral record
+        assert_eq!(rep.score, 4859);
+    });
+}
+
+#[test]
+fn track_record_counts_settled_tasks_by_value() {
+    new_test_ext().execute_with(|| {
+        // Posting, even if the task is then cancelled, builds no track record
+        for _ in 0..10 {
+            Reputation::on_task_posted(&1, 100);
+        }
+        assert_eq!(Reputation::track_record(&1), Perbill::zero());
+
+        // Approving full-value work settles the poster's task
+        Reputation::on_task_approved(&1, 100);
+        assert_eq!(Reputation::track_record(&1), Perbill::from_percent(20));
+
+        // Cheap completions count by their value share, in the record too
+        for _ in 0..4 {
+            Reputation::on_task_completed(&2, 1, 0);
+        }
+        let rep = Reputation::reputations(2);
+        assert_eq!(rep.total_tasks_completed, 4);
+        assert_eq!(rep.settled_weight, 400);
+        assert_eq!(rep.record_weight, 400);
+        // (4 * 5000 + 0.04 * 10000) / 4.04 = 5049 for the record
+        assert_eq!(rep.score, 5024);
+        assert_eq!(Reputation::review_weight(&2, 100), 50);
+    });
+}
+
+/// EigenTrust over the whole review graph in one pass, as `on_idle` computes it across
+/// blocks.
+fn reference_trust() -> Vec<(u64, u32)> {
+    const SCALE: u128 = 1_000_000_000;
+    let mut accounts: Vec<u64> = Vec::new();
+    let mut index = |account: u64| {
+        accounts
+            .iter()
+            .position(|a| *a == account)
+            .unwrap_or_else(|| {
+                accounts.push(account);
+                accounts.len() - 1
+            })
+    };
+    let edges: Vec<(usize, usize, u128)> = Reviews::<Test>::iter()
+        .map(|((reviewer, reviewee, _), review)| {
+            let local = Reputation::review_points(review.rating).saturating_sub(5000);
+            (index(reviewer), index(reviewee), local.into())
+        })
+        .collect();
+    let count = accounts.len();
+
+    let mut outgoing = vec![0u128; count];
+    for (from, _, local) in &edges {
+        outgoing[*from] += local;
+    }
+    let scores: Vec<u128> = accounts
+        .iter()
+        .map(|account| Reputation::get_reputation(account).into())
+        .collect();
+    let total: u128 = scores.iter().sum::<u128>().max(1);
+    let pre_trust: Vec<u128> = scores.iter().map(|score| score * SCALE / total).collect();
+
+    let mut trust = pre_trust.clone();
+    for _ in 0..20 {
+        let mut next = vec![0u128; count];
+        for (from, to, local) in &edges {
+            if *local > 0 {
+                next[*to] += trust[*from] * local / outgoing[*from];
+            }
+        }
+        let dangling: u128 = (0..count)
+            .filter(|i| outgoing[*i] == 0)
+            .map(|i| trust[i])
+            .sum();
+        for (i, next) in next.iter_mut().enumerate() {
+            let propagated = *next + dangling * pre_trust[i] / SCALE;
+            *next = (propagated * 85 + pre_trust[i] * 15) / 100;
+        }
+        trust = next;
+    }
+
+    let scale = count as u128 * 5000;
+    accounts
+        .into_iter()
+        .zip(trust)
+        .map(|(account, trust)| (account, (trust * scale / SCALE).min(10_000) as u32))
+        .collect()
+}
+
+#[test]
+fn global_trust_flows_along_positive_reviews() {
+    use frame_support::{traits::Hooks, weights::Weight};
+    use std::collections::BTreeMap;
+
+    new_test_ext().execute_with(|| {
+        for (task_id, reviewer, reviewee, rating) in
+            [(1, 1, 3, 5), (2, 2, 3, 5), (3, 3, 1, 4), (4, 4, 2, 1)]
+        {
+            approve_task(task_id, reviewer, reviewee);
+            assert_ok!(Reputation::submit_review(
+                RuntimeOrigin::signed(reviewer),
+                reviewee,
+                rating,
+                b"Review".to_vec(),
+                task_id
+            ));
+        }
+
+        let trust: BTreeMap<u64, u32> = reference_trust().into_iter().collect();
+        assert!(trust[&3] > trust[&1]);
+        assert!(trust[&1] > trust[&4]);
+        // 2's poor rating lowers its pre-trust, and 4 trusts nobody
+        assert!(trust[&4] > trust[&2]);
+
+        // Not due before `TrustUpdatePeriod`, nor without room in the block
+        Reputation::on_idle(9, Weight::MAX);
+        assert_eq!(Reputation::global_trust(3), None);
+        Reputation::on_idle(10, Weight::from_parts(1_000, 0));
+        assert_eq!(Reputation::global_trust(3), None);
+
+        System::set_block_number(10);
+        Reputation::on_idle(10, Weight::MAX);
+        assert_eq!(TrustUpdate::<Test>::get(), None);
+        assert!(TrustNodes::<Test>::iter().next().is_none());
+        assert_eq!(Reputation::global_trust(3), Some(trust[&3]));
+        System::assert_last_event(Event::GlobalTrustUpdated { accounts: 4 }.into());
+        assert_eq!(LastTrustUpdate::<Test>::get(), 10);
+    });
+}
+
+#[test]
+fn dormant_scores_decay_toward_initial_reputation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..4 {
+            Reputation::on_task_completed(&2, 100, 0);
+        }
+        // Ratings 5000, record 7500
+        assert_eq!(Reputation::get_reputation(&2), 6250);
+
+        // Untouched for the grace period
+        System::set_block_number(101);
+        assert_eq!(Reputation::get_reputation(&2), 6250);
+
+        // One half-life later the record counts for two completions: 6666
+        System::set_block_number(201);
+        assert_eq!(Reputation::get_reputation(&2), 5833);
+        assert_eq!(Reputation::reputation_summary(&2).score, 5833);
+        assert_eq!(Reputation::reputations(2).score, 6250);
+
+        // Posting keeps the decayed evidence without restarting the grace period
+        Reputation::on_task_posted(&2, 10);
+        let rep = Reputation::reputations(2);
+        assert_eq!(rep.score, 5833);
+        assert_eq!(rep.record_weight, 20000);
+        assert_eq!(rep.last_active, 1);
+        assert_eq!(rep.decayed_at, 201);
+        System::set_block_number(301);
+        assert_eq!(Reputation::get_reputation(&2), 5500);
+
+        // Completed work does restart it
+        Reputation::on_task_completed(&2, 100, 0);
+        let rep = Reputation::reputations(2);
+        assert_eq!(rep.score, 5833);
+        assert_eq!(rep.last_active, 301);
+        assert_eq!(rep.successful_completions, 5);
+        System::set_block_number(401);
+        assert_eq!(Reputation::get_reputation(&2), 5833);
+
+        // Penalties do not decay, so a slash cannot be waited out
+        assert_ok!(Reputation::slash_reputation(
+            RuntimeOrigin::root(),
+            3,
+            1000,
+            vec![]
+        ));
+        assert_eq!(Reputation::get_reputation(&3), 4000);
+        System::set_block_number(501);
+        assert_eq!(Reputation::get_reputation(&3), 4000);
+        System::set_block_number(10_000);
+        assert_eq!(Reputation::get_reputation(&2), 5000);
+        assert_eq!(Reputation::get_reputation(&3), 4000);
+        assert_eq!(Reputation::decayed_reputation(&3).penalty, 1000);
+    });
+}
+
+#[test]
+fn only_completed_work_resets_decay() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..4 {
+            Reputation::on_task_completed(&2, 100, 0);
+        }
+        assert_eq!(Reputation::get_reputation(&2), 6250);
+
+        // Posting, approving and being reviewed well past the grace period
+        System::set_block_number(201);
+        Reputation::on_task_posted(&2, 100);
+        Reputation::on_task_approved(&2, 100);
+        Reputation::on_task_posted(&1, 100);
+        Reputation::on_task_approved(&1, 100);
+        approve_task(1, 1, 2);
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            3,
+            b"Fine".to_vec(),
+            1
+        ));
+        let rep = Reputation::reputations(2);
+        assert_eq!(rep.last_active, 1);
+        assert_eq!(rep.record_weight, 20000);
+        assert_eq!(CategoryReputations::<Test>::get(2, 0).last_active, 1);
+
+        // The record keeps decaying from the last completion
+        System::set_block_number(301);
+        assert_eq!(Reputation::decayed_reputation(&2).record_weight, 10000);
+    });
+}
+
+#[test]
+fn decay_parameters_are_governable() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let slow = DecayParameters {
+            grace_period: 10,
+            half_life: 1000,
+        };
+        assert_noop!(
+            Reputation::set_decay_parameters(RuntimeOrigin::signed(1), Some(slow)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(Reputation::set_decay_parameters(
+            RuntimeOrigin::root(),
+            Some(slow)
+        ));
+        System::assert_last_event(Event::DecayParametersSet { parameters: slow }.into());
+        assert_eq!(Reputation::decay_parameters(), slow);
+        // Halfway through the first half-life, 3/4 of the evidence is kept
+        assert_eq!(Reputation::decay_factor(1, 511), Perbill::from_percent(75));
+        assert_eq!(Reputation::decay_factor(1, 1011), Perbill::from_percent(50));
+
+        let disabled = DecayParameters {
+            grace_period: 0,
+            half_life: 0,
+        };
+        assert_ok!(Reputation::set_decay_parameters(
+            RuntimeOrigin::root(),
+            Some(disabled)
+        ));
+        Reputation::on_dispute_resolved(&2, &3, 100, 0);
+        System::set_block_number(1_000_000);
+        assert_eq!(Reputation::get_reputation(&3), 4500);
+
+        // Back to the configured defaults
+        assert_ok!(Reputation::set_decay_parameters(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_eq!(
+            Reputation::decay_parameters(),
+            DecayParameters {
+                grace_period: 100,
+                half_life: 100
+            }
+        );
+        assert_eq!(Reputation::get_reputation(&3), 5000);
+    });
+}
+
+#[test]
+fn global_trust_recomputation_spreads_over_runtime_sized_blocks() {
+    use frame_support::{
+        traits::Hooks,
+        weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
+    };
+
+    new_test_ext().execute_with(|| {
+        // The runtime's cap, and the quarter of its 2s block left by normal extrinsics
+        MaxTrustStepsPerBlock::set(500);
+        let budget = Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 2, u64::MAX);
+        assert!(Reputation::trust_step_weight()
+            .saturating_mul(500)
+            .all_lte(budget));
+
+        // 400 reviews among 100 accounts
+        for task_id in 0..400u64 {
+            let reviewer = task_id % 100 + 1;
+            let reviewee = (task_id * 7 + 3) % 100 + 1;
+            approve_task(task_id, reviewer, reviewee);
+            assert_ok!(Reputation::submit_review(
+                RuntimeOrigin::signed(reviewer),
+                reviewee,
+                (task_id % 5 + 1) as u8,
+                vec![],
+                task_id
+            ));
+        }
+        let expected = reference_trust();
+
+        let mut blocks = 0;
+        loop {
+            let now = 10 + blocks;
+            System::set_block_number(now);
+            let used = Reputation::on_idle(now, budget);
+            assert!(used.all_lte(budget));
+            blocks += 1;
+            if TrustUpdate::<Test>::get().is_none() {
+                break;
+            }
+            // Reviews left meanwhile wait for the next recomputation
+            if blocks == 1 {
+                approve_task(1000, 1, 2);
+                assert_ok!(Reputation::submit_review(
+                    RuntimeOrigin::signed(1),
+                    2,
+                    5,
+                    vec![],
+                    1000
+                ));
+            }
+        }
+        assert!(blocks > 10 && blocks < TrustUpdatePeriod::get() * 3);
+
+        assert_eq!(expected.len(), 100);
+        for (account, standing) in expected {
+            assert_eq!(Reputation::global_trust(account), Some(standing));
+        }
+        System::assert_last_event(Event::GlobalTrustUpdated { accounts: 100 }.into());
     });
 }
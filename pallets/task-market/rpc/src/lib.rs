@@ -0,0 +1,186 @@
+//! RPC interface for the Task Market pallet.
+//!
+//! Exposes the `TaskMarketApi` runtime API as `taskMarket_*` JSON-RPC methods.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_task_market_runtime_api::{
+    BidSummary, EscrowTotals, TaskId, TaskPage, TaskStatus, TaskSummary,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_task_market_runtime_api::TaskMarketApi as TaskMarketRuntimeApi;
+
+/// Maximum (and default) number of entries returned by a paginated call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait TaskMarketApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// Tasks with the given status, scanning task IDs from `start`; continue from
+    /// the returned `next`.
+    #[method(name = "taskMarket_tasksByStatus")]
+    fn tasks_by_status(
+        &self,
+        status: TaskStatus,
+        start: Option<TaskId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<TaskPage<AccountId, Balance, BlockNumber>>;
+
+    /// Non-terminal tasks posted by `poster`.
+    #[method(name = "taskMarket_tasksByPoster")]
+    fn tasks_by_poster(
+        &self,
+        poster: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<TaskSummary<AccountId, Balance, BlockNumber>>>;
+
+    /// Non-terminal tasks assigned to `worker`.
+    #[method(name = "taskMarket_tasksByWorker")]
+    fn tasks_by_worker(
+        &self,
+        worker: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<TaskSummary<AccountId, Balance, BlockNumber>>>;
+
+    /// Bids on a task, skipping the first `start`.
+    #[method(name = "taskMarket_bids")]
+    fn bids(
+        &self,
+        task_id: TaskId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<BidSummary<AccountId, Balance, BlockNumber>>>;
+
+    /// Rewards and deposits currently locked by the market.
+    #[method(name = "taskMarket_escrowTotals")]
+    fn escrow_totals(&self, at: Option<BlockHash>) -> RpcResult<EscrowTotals<Balance>>;
+
+    /// Open tasks `bidder` may bid on after reputation, standing and capability gating;
+    /// continue from the returned `next`.
+    #[method(name = "taskMarket_biddableTasks")]
+    fn biddable_tasks(
+        &self,
+        bidder: AccountId,
+        start: Option<TaskId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<TaskPage<AccountId, Balance, BlockNumber>>;
+}
+
+/// Provides RPC methods to query the task market.
+pub struct TaskMarket<C, Block> {
+    /// Shared reference to the client.
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> TaskMarket<C, Block> {
+    /// Creates a new instance of the TaskMarket RPC helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(RUNTIME_ERROR, "Unable to query task market.", Some(error.to_string()))
+}
+
+fn page_size(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    TaskMarketApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for TaskMarket<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: TaskMarketRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + Send + Sync + 'static,
+    Balance: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Send + Sync + 'static,
+{
+    fn tasks_by_status(
+        &self,
+        status: TaskStatus,
+        start: Option<TaskId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<TaskPage<AccountId, Balance, BlockNumber>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .tasks_by_status(at_hash, status, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn tasks_by_poster(
+        &self,
+        poster: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<TaskSummary<AccountId, Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .tasks_by_poster(at_hash, poster)
+            .map_err(runtime_error)
+    }
+
+    fn tasks_by_worker(
+        &self,
+        worker: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<TaskSummary<AccountId, Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .tasks_by_worker(at_hash, worker)
+            .map_err(runtime_error)
+    }
+
+    fn bids(
+        &self,
+        task_id: TaskId,
+        start: Option<u32>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<BidSummary<AccountId, Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .bids_for_task(at_hash, task_id, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn escrow_totals(&self, at: Option<Block::Hash>) -> RpcResult<EscrowTotals<Balance>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().escrow_totals(at_hash).map_err(runtime_error)
+    }
+
+    fn biddable_tasks(
+        &self,
+        bidder: AccountId,
+        start: Option<TaskId>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<TaskPage<AccountId, Balance, BlockNumber>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .biddable_tasks(at_hash, bidder, start.unwrap_or_default(), page_size(limit))
+            .map_err(runtime_error)
+    }
+}
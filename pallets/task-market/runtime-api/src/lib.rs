@@ -0,0 +1,57 @@
+//! Runtime API definition for the Task Market pallet.
+//!
+//! Lets clients list tasks and bids and read escrow totals without decoding storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_task_market::{
+    BidSummary, EscrowTotals, TaskId, TaskPage, TaskStatus, TaskSummary, MAX_SCANNED_TASKS,
+};
+
+sp_api::decl_runtime_apis! {
+    /// Queries over the task market.
+    pub trait TaskMarketApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Tasks with the given status, scanning at most `MAX_SCANNED_TASKS` task IDs
+        /// from `start` and returning at most `limit`.
+        fn tasks_by_status(
+            status: TaskStatus,
+            start: TaskId,
+            limit: u32,
+        ) -> TaskPage<AccountId, Balance, BlockNumber>;
+
+        /// Non-terminal tasks posted by `poster`.
+        fn tasks_by_poster(poster: AccountId) -> Vec<TaskSummary<AccountId, Balance, BlockNumber>>;
+
+        /// Non-terminal tasks assigned to `worker`.
+        fn tasks_by_worker(worker: AccountId) -> Vec<TaskSummary<AccountId, Balance, BlockNumber>>;
+
+        /// Bids on `task_id`, skipping the first `start` and returning at most `limit`.
+        fn bids_for_task(
+            task_id: TaskId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<BidSummary<AccountId, Balance, BlockNumber>>;
+
+        /// Rewards and deposits currently locked by the market.
+        fn escrow_totals() -> EscrowTotals<Balance>;
+
+        /// Open tasks `bidder` may bid on after reputation, standing and capability
+        /// gating, scanning at most `MAX_SCANNED_TASKS` task IDs from `start` and
+        /// returning at most `limit`.
+        fn biddable_tasks(
+            bidder: AccountId,
+            start: TaskId,
+            limit: u32,
+        ) -> TaskPage<AccountId, Balance, BlockNumber>;
+    }
+}
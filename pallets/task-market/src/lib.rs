@@ -7,17 +7,19 @@
 //! This pallet provides functionality for:
 //! - Posting tasks with CLAW token escrow
 //! - Submitting bids on open tasks
-//! - Assigning tasks to selected bidders
+//! - Assigning tasks to selected bidders, skipping agents that are suspended or inactive
 //! - Submitting work and proof of completion
 //! - Approving work and releasing escrow
 //! - Disputing tasks (governance resolution)
 //! - Cancelling tasks and refunding escrow
+//! - Pruning terminal tasks after a retention period, keeping a hash commitment
+//! - Approving work automatically once independent validators pass it
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `post_task` - Create a task with locked escrow
+//! - `post_task` - Create a task in a category with locked escrow
 //! - `bid_on_task` - Submit a bid on an open task
 //! - `assign_task` - Poster selects a bidder
 //! - `submit_work` - Worker submits completion proof
@@ -25,12 +27,67 @@
 //! - `dispute_task` - Either party disputes the task
 //! - `cancel_task` - Poster cancels (only if still Open)
 //! - `resolve_dispute` - Governance resolves a dispute
+//! - `require_capability` - Poster restricts bidding to agents declaring a capability
+//!
+//! ### Runtime API Helpers
+//!
+//! - `tasks_by_status`, `tasks_by_poster`, `tasks_by_worker` - Task listings
+//! - `bids_for_task` - Paginated bids for a task
+//! - `escrow_totals` - Funds currently locked by the market
+//! - `biddable_tasks` - Open tasks an account may bid on, after reputation, standing
+//!   and capability gating
+//!
+//! ### Categories
+//!
+//! Every task is posted in a `TaskCategory`, whose meaning is agreed off-chain;
+//! `DEFAULT_CATEGORY` holds uncategorised tasks. pallet-reputation keeps a score per
+//! category from the completions, disputes and reviews of tasks in it. A bidder
+//! needs `MinBidderReputation` both globally and in the task's category.
+//!
+//! ### Capability Requirements
+//!
+//! Before any bid arrives, the poster can require bidders to declare a capability
+//! in pallet-agent-registry at a minimum version with `require_capability`. Only
+//! the agent account or owner of an agent declaring it may then bid, checked
+//! through `AgentStanding`. Task summaries carry the requirement, so a poster can
+//! look up capable agents with the registry's `agents_with_capability`.
+//!
+//! ### Validation
+//!
+//! Instead of trusting `submit_work`, a poster can have the submitted work scored by
+//! staked validators in pallet-validation. The pallet implements `ValidatedWork`:
+//! when a quorum of validators passes the work, it is approved exactly as if the
+//! poster had called `approve_work`. Failed validation leaves the task Completed so
+//! the poster can still approve or dispute it.
+//!
+//! ### Messaging
+//!
+//! Agents can thread pallet-messaging messages under a task to negotiate it. The
+//! pallet implements `NegotiableTasks`, accepting any task that has not yet reached
+//! a terminal status, with its poster, bidders and assignee as the negotiators.
+//!
+//! ### Reviews
+//!
+//! The pallet implements pallet-reputation's `ReviewableTasks`, so the poster and worker
+//! of an `Approved` task (including disputes resolved by root) can review each other
+//! until the task is pruned. The task's reward weights the review.
+//!
+//! ### Storage Deposits and Pruning
+//!
+//! Posting a task reserves a storage deposit (`TaskDepositBase` plus `TaskDepositPerByte`
+//! for the title and description) on top of the escrowed reward. Once a task reaches a
+//! terminal status it is queued for pruning; after `TaskRetentionPeriod` blocks the
+//! `on_idle` hook removes the task and its bids, records a hash commitment in
+//! `ArchivedTasks`, emits a `TaskPruned` summary and refunds the deposit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
 pub use pallet::*;
+pub use pallet_reputation::TaskCategory;
+
+pub mod migrations;
 
 #[cfg(test)]
 mod tests;
@@ -46,11 +103,19 @@ pub mod pallet {
         PalletId,
     };
     use frame_system::pallet_prelude::*;
+    use pallet_agent_registry::AgentStanding;
     use pallet_reputation::ReputationManager;
+    use sp_runtime::traits::{Hash, Saturating};
 
     /// Type alias for task IDs.
     pub type TaskId = u64;
 
+    /// Category of uncategorised tasks, including tasks posted before categories.
+    pub const DEFAULT_CATEGORY: TaskCategory = 0;
+
+    /// Maximum number of task IDs a runtime API listing scans per call.
+    pub const MAX_SCANNED_TASKS: u64 = 1_000;
+
     /// Type alias for balance (compatible with pallet-balances).
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -60,6 +125,7 @@ pub mod pallet {
         Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
         codec::DecodeWithMemTracking,
     )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
     pub enum TaskStatus {
         /// Accepting bids.
         Open,
@@ -85,6 +151,16 @@ pub mod pallet {
         }
     }
 
+    impl TaskStatus {
+        /// Whether the task has reached a final state and no longer counts as active.
+        pub fn is_terminal(&self) -> bool {
+            matches!(
+                self,
+                TaskStatus::Approved | TaskStatus::Cancelled | TaskStatus::Expired
+            )
+        }
+    }
+
     /// Core task information.
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -95,6 +171,8 @@ pub mod pallet {
         pub title: BoundedVec<u8, T::MaxTitleLength>,
         /// Task description.
         pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        /// Kind of work, for per-category reputation.
+        pub category: TaskCategory,
         /// Reward in CLAW tokens (held in escrow).
         pub reward: BalanceOf<T>,
         /// Deadline (block number).
@@ -107,6 +185,16 @@ pub mod pallet {
         pub created_at: BlockNumberFor<T>,
     }
 
+    /// Capability a bidder's agent must declare to bid on a task.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct CapabilityRequirement<T: Config> {
+        /// Capability ID, as declared in pallet-agent-registry.
+        pub capability: BoundedVec<u8, T::MaxCapabilityIdLength>,
+        /// Minimum declared version.
+        pub min_version: u32,
+    }
+
     /// Bid information.
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -121,6 +209,96 @@ pub mod pallet {
         pub submitted_at: BlockNumberFor<T>,
     }
 
+    /// Task view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct TaskSummary<AccountId, Balance, BlockNumber> {
+        /// The task ID.
+        pub task_id: TaskId,
+        /// Account that posted the task.
+        pub poster: AccountId,
+        /// Task title.
+        pub title: Vec<u8>,
+        /// Task description.
+        pub description: Vec<u8>,
+        /// Kind of work, for per-category reputation.
+        pub category: TaskCategory,
+        /// Reward held in escrow.
+        pub reward: Balance,
+        /// Storage deposit held from the poster.
+        pub deposit: Balance,
+        /// Deadline (block number).
+        pub deadline: BlockNumber,
+        /// Current status.
+        pub status: TaskStatus,
+        /// Assigned worker (if any).
+        pub assigned_to: Option<AccountId>,
+        /// When the task was created.
+        pub created_at: BlockNumber,
+        /// Capability ID and minimum version bidders must declare (if any).
+        pub required_capability: Option<(Vec<u8>, u32)>,
+    }
+
+    /// Page of tasks returned by a runtime API listing.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct TaskPage<AccountId, Balance, BlockNumber> {
+        /// Matching tasks, in task ID order.
+        pub tasks: Vec<TaskSummary<AccountId, Balance, BlockNumber>>,
+        /// Task ID to continue the listing from, or `None` once every task was scanned.
+        pub next: Option<TaskId>,
+    }
+
+    /// Bid view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct BidSummary<AccountId, Balance, BlockNumber> {
+        /// The bidder's account.
+        pub bidder: AccountId,
+        /// Amount they're willing to do it for.
+        pub amount: Balance,
+        /// Proposal text.
+        pub proposal: Vec<u8>,
+        /// When the bid was submitted.
+        pub submitted_at: BlockNumber,
+    }
+
+    /// Funds currently locked by the task market.
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct EscrowTotals<Balance> {
+        /// Sum of rewards escrowed by non-terminal tasks.
+        pub locked_rewards: Balance,
+        /// Sum of storage deposits held for tasks not yet pruned.
+        pub locked_deposits: Balance,
+        /// Number of non-terminal tasks.
+        pub active_tasks: u32,
+    }
+
+    /// Task summary type for a given runtime.
+    pub type TaskSummaryOf<T> = TaskSummary<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
+    /// Task page type for a given runtime.
+    pub type TaskPageOf<T> =
+        TaskPage<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// Bid summary type for a given runtime.
+    pub type BidSummaryOf<T> = BidSummary<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
     /// The pallet's configuration trait.
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -163,9 +341,46 @@ pub mod pallet {
         /// Maximum number of active tasks per account.
         #[pallet::constant]
         type MaxActiveTasksPerAccount: Get<u32>;
+
+        /// Maximum number of active tasks a single worker can be assigned.
+        #[pallet::constant]
+        type MaxActiveTasksPerWorker: Get<u32>;
+
+        /// Number of blocks a terminal task is kept in state before it is pruned.
+        #[pallet::constant]
+        type TaskRetentionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Base storage deposit reserved from the poster for every task.
+        #[pallet::constant]
+        type TaskDepositBase: Get<BalanceOf<Self>>;
+
+        /// Additional storage deposit per byte of task title and description.
+        #[pallet::constant]
+        type TaskDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Maximum number of terminal tasks pruned in a single block.
+        #[pallet::constant]
+        type MaxPrunedTasksPerBlock: Get<u32>;
+
+        /// Minimum reputation score (basis points) required to bid on a task.
+        #[pallet::constant]
+        type MinBidderReputation: Get<u32>;
+
+        /// Standing of agent accounts. Suspended agents cannot bid, and tasks are
+        /// not assigned to suspended or inactive agents; use `()` to skip the checks.
+        type AgentStanding: AgentStanding<Self::AccountId>;
+
+        /// Maximum length of a required capability ID in bytes; should match
+        /// pallet-agent-registry's `MaxCapabilityIdLength`.
+        #[pallet::constant]
+        type MaxCapabilityIdLength: Get<u32>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ========== Storage ==========
@@ -193,7 +408,17 @@ pub mod pallet {
         OptionQuery,
     >;
 
-    /// Map from AccountId to their posted task IDs.
+    /// Number of bids held in `TaskBids` for each task, at most `MaxBidsPerTask`.
+    #[pallet::storage]
+    pub type TaskBidCount<T: Config> = StorageMap<_, Blake2_128Concat, TaskId, u32, ValueQuery>;
+
+    /// Capability bidders must declare, for tasks whose poster set one.
+    #[pallet::storage]
+    #[pallet::getter(fn task_requirements)]
+    pub type TaskRequirements<T: Config> =
+        StorageMap<_, Blake2_128Concat, TaskId, CapabilityRequirement<T>, OptionQuery>;
+
+    /// Map from AccountId to their posted, non-terminal task IDs.
     #[pallet::storage]
     #[pallet::getter(fn active_tasks)]
     pub type ActiveTasks<T: Config> = StorageMap<
@@ -204,6 +429,47 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Map from worker AccountId to the non-terminal task IDs assigned to them.
+    #[pallet::storage]
+    #[pallet::getter(fn worker_tasks)]
+    pub type WorkerTasks<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<TaskId, T::MaxActiveTasksPerWorker>,
+        ValueQuery,
+    >;
+
+    /// Storage deposit reserved from the poster for each task, refunded on pruning.
+    #[pallet::storage]
+    #[pallet::getter(fn task_deposits)]
+    pub type TaskDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, TaskId, BalanceOf<T>, ValueQuery>;
+
+    /// Running totals of escrowed rewards and storage deposits, updated at every
+    /// reserve and unreserve.
+    #[pallet::storage]
+    #[pallet::getter(fn escrow)]
+    pub type Escrow<T: Config> = StorageValue<_, EscrowTotals<BalanceOf<T>>, ValueQuery>;
+
+    /// FIFO queue of terminal tasks awaiting pruning: position -> (TaskId, block it concluded).
+    #[pallet::storage]
+    pub type PruneQueue<T: Config> =
+        StorageMap<_, Twox64Concat, u64, (TaskId, BlockNumberFor<T>), OptionQuery>;
+
+    /// Position of the next `PruneQueue` entry to prune.
+    #[pallet::storage]
+    pub type PruneQueueHead<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Position at which the next terminal task will be queued.
+    #[pallet::storage]
+    pub type PruneQueueTail<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Hash commitment over the final `TaskInfo` and bids of each pruned task.
+    #[pallet::storage]
+    #[pallet::getter(fn archived_tasks)]
+    pub type ArchivedTasks<T: Config> = StorageMap<_, Blake2_128Concat, TaskId, T::Hash, OptionQuery>;
+
     // ========== Events ==========
 
     #[pallet::event]
@@ -213,6 +479,7 @@ pub mod pallet {
         TaskPosted {
             task_id: TaskId,
             poster: T::AccountId,
+            category: TaskCategory,
             reward: BalanceOf<T>,
         },
         /// A bid was submitted.
@@ -243,6 +510,22 @@ pub mod pallet {
             task_id: TaskId,
             winner: T::AccountId,
         },
+        /// A terminal task was pruned from state, leaving only a hash commitment.
+        TaskPruned {
+            task_id: TaskId,
+            poster: T::AccountId,
+            worker: Option<T::AccountId>,
+            status: TaskStatus,
+            reward: BalanceOf<T>,
+            commitment: T::Hash,
+            deposit_refunded: BalanceOf<T>,
+        },
+        /// The capability required of bidders was set or cleared.
+        CapabilityRequired {
+            task_id: TaskId,
+            capability: Option<Vec<u8>>,
+            min_version: u32,
+        },
     }
 
     // ========== Errors ==========
@@ -273,12 +556,33 @@ pub mod pallet {
         TooManyBids,
         /// Too many active tasks for this account.
         TooManyActiveTasks,
+        /// The worker already has the maximum number of assigned tasks.
+        TooManyWorkerTasks,
         /// Task deadline has passed.
         TaskExpired,
         /// Insufficient balance to post task.
         InsufficientBalance,
         /// Bidder does not meet minimum reputation requirement.
         InsufficientReputation,
+        /// The bidder is an agent that is not currently active.
+        AgentInactive,
+        /// The bidder is a suspended agent.
+        AgentSuspended,
+        /// The bidder's agent does not declare the capability the task requires.
+        MissingCapability,
+        /// The capability ID is empty or exceeds `MaxCapabilityIdLength`.
+        InvalidCapability,
+        /// The task already has bids.
+        TaskHasBids,
+    }
+
+    // ========== Hooks ==========
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::prune_terminal_tasks(now, remaining_weight)
+        }
     }
 
     // ========== Extrinsics ==========
@@ -292,14 +596,16 @@ pub mod pallet {
         /// * `description` - Detailed description
         /// * `reward` - CLAW tokens to pay (locked immediately)
         /// * `deadline` - Block number deadline
+        /// * `category` - Kind of work, for per-category reputation
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 4))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 6))]
         pub fn post_task(
             origin: OriginFor<T>,
             title: Vec<u8>,
             description: Vec<u8>,
             reward: BalanceOf<T>,
             deadline: BlockNumberFor<T>,
+            category: TaskCategory,
         ) -> DispatchResult {
             let poster = ensure_signed(origin)?;
 
@@ -313,8 +619,10 @@ pub mod pallet {
             let current_block = <frame_system::Pallet<T>>::block_number();
             ensure!(deadline > current_block, Error::<T>::TaskExpired);
 
-            // Reserve the reward (escrow)
-            T::Currency::reserve(&poster, reward)
+            // Reserve the reward (escrow) and the storage deposit
+            let deposit =
+                Self::task_deposit(bounded_title.len().saturating_add(bounded_description.len()));
+            T::Currency::reserve(&poster, reward.saturating_add(deposit))
                 .map_err(|_| Error::<T>::InsufficientBalance)?;
 
             // Create task
@@ -323,6 +631,7 @@ pub mod pallet {
                 poster: poster.clone(),
                 title: bounded_title,
                 description: bounded_description,
+                category,
                 reward,
                 deadline,
                 status: TaskStatus::Open,
@@ -332,6 +641,14 @@ pub mod pallet {
 
             Tasks::<T>::insert(task_id, task_info);
             TaskCount::<T>::put(task_id.saturating_add(1));
+            if !deposit.is_zero() {
+                TaskDeposits::<T>::insert(task_id, deposit);
+            }
+            Escrow::<T>::mutate(|totals| {
+                totals.locked_rewards = totals.locked_rewards.saturating_add(reward);
+                totals.locked_deposits = totals.locked_deposits.saturating_add(deposit);
+                totals.active_tasks = totals.active_tasks.saturating_add(1);
+            });
 
             // Add to poster's active tasks
             ActiveTasks::<T>::try_mutate(&poster, |tasks| {
@@ -346,6 +663,7 @@ pub mod pallet {
             Self::deposit_event(Event::TaskPosted {
                 task_id,
                 poster,
+                category,
                 reward,
             });
 
@@ -354,12 +672,15 @@ pub mod pallet {
 
         /// Submit a bid on an open task.
         ///
+        /// A task takes bids from at most `MaxBidsPerTask` bidders; bidding again
+        /// replaces the earlier bid.
+        ///
         /// # Arguments
         /// * `task_id` - The task to bid on
         /// * `amount` - How much you'll do it for
         /// * `proposal` - Your proposal text
         #[pallet::call_index(1)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(9, 3))]
         pub fn bid_on_task(
             origin: OriginFor<T>,
             task_id: TaskId,
@@ -371,22 +692,44 @@ pub mod pallet {
             let task = Tasks::<T>::get(task_id).ok_or(Error::<T>::TaskNotFound)?;
             ensure!(task.status == TaskStatus::Open, Error::<T>::InvalidTaskStatus);
             ensure!(task.poster != bidder, Error::<T>::CannotBidOnOwnTask);
+            ensure!(
+                !T::AgentStanding::is_suspended_agent(&bidder),
+                Error::<T>::AgentSuspended
+            );
+            ensure!(
+                Self::meets_requirement(task_id, &bidder),
+                Error::<T>::MissingCapability
+            );
 
             // Check deadline
             let current_block = <frame_system::Pallet<T>>::block_number();
             ensure!(current_block < task.deadline, Error::<T>::TaskExpired);
 
-            // Optional: Check minimum reputation (example: 3000 = 30%)
-            // Uncomment to enforce:
-            // ensure!(
-            //     T::ReputationManager::meets_minimum_reputation(&bidder, 3000),
-            //     Error::<T>::InsufficientReputation
-            // );
+            // Check minimum reputation, globally and in the task's category
+            let minimum = T::MinBidderReputation::get();
+            ensure!(
+                T::ReputationManager::meets_minimum_reputation(&bidder, minimum, None)
+                    && T::ReputationManager::meets_minimum_reputation(
+                        &bidder,
+                        minimum,
+                        Some(task.category)
+                    ),
+                Error::<T>::InsufficientReputation
+            );
 
             let bounded_proposal: BoundedVec<u8, T::MaxProposalLength> =
                 proposal.try_into().map_err(|_| Error::<T>::ProposalTooLong)?;
 
-            // Check bid count (simple check: if we can insert, there's space)
+            // A new bidder takes one of the task's `MaxBidsPerTask` slots; bidding
+            // again replaces the earlier bid.
+            if !TaskBids::<T>::contains_key(task_id, &bidder) {
+                TaskBidCount::<T>::try_mutate(task_id, |count| -> DispatchResult {
+                    ensure!(*count < T::MaxBidsPerTask::get(), Error::<T>::TooManyBids);
+                    *count = count.saturating_add(1);
+                    Ok(())
+                })?;
+            }
+
             let bid_info = BidInfo::<T> {
                 bidder: bidder.clone(),
                 amount,
@@ -411,7 +754,7 @@ pub mod pallet {
         /// * `task_id` - The task to assign
         /// * `bidder` - The selected bidder
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 2))]
         pub fn assign_task(
             origin: OriginFor<T>,
             task_id: TaskId,
@@ -429,10 +772,25 @@ pub mod pallet {
                     TaskBids::<T>::contains_key(task_id, &bidder),
                     Error::<T>::BidNotFound
                 );
+                ensure!(
+                    !T::AgentStanding::is_suspended_agent(&bidder),
+                    Error::<T>::AgentSuspended
+                );
+                ensure!(
+                    !T::AgentStanding::is_inactive_agent(&bidder),
+                    Error::<T>::AgentInactive
+                );
 
                 task.status = TaskStatus::Assigned;
                 task.assigned_to = Some(bidder.clone());
 
+                // Add to worker's active tasks
+                WorkerTasks::<T>::try_mutate(&bidder, |tasks| {
+                    tasks
+                        .try_push(task_id)
+                        .map_err(|_| Error::<T>::TooManyWorkerTasks)
+                })?;
+
                 Ok(())
             })?;
 
@@ -484,36 +842,14 @@ pub mod pallet {
         /// # Arguments
         /// * `task_id` - The task to approve
         #[pallet::call_index(4)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(8, 9))]
         pub fn approve_work(origin: OriginFor<T>, task_id: TaskId) -> DispatchResult {
             let poster = ensure_signed(origin)?;
 
             let task = Tasks::<T>::get(task_id).ok_or(Error::<T>::TaskNotFound)?;
             ensure!(task.poster == poster, Error::<T>::NotPoster);
-            ensure!(
-                task.status == TaskStatus::Completed,
-                Error::<T>::InvalidTaskStatus
-            );
 
-            let worker = task.assigned_to.ok_or(Error::<T>::NotAssignedWorker)?;
-
-            // Unreserve from poster and transfer to worker
-            T::Currency::unreserve(&poster, task.reward);
-            T::Currency::transfer(&poster, &worker, task.reward, ExistenceRequirement::KeepAlive)?;
-
-            // Update task status
-            Tasks::<T>::try_mutate(task_id, |maybe_task| -> DispatchResult {
-                let t = maybe_task.as_mut().ok_or(Error::<T>::TaskNotFound)?;
-                t.status = TaskStatus::Approved;
-                Ok(())
-            })?;
-
-            // Update reputation
-            T::ReputationManager::on_task_completed(&worker, task.reward);
-
-            Self::deposit_event(Event::WorkApproved { task_id });
-
-            Ok(())
+            Self::do_approve_work(task_id)
         }
 
         /// Dispute a task (either poster or worker can dispute).
@@ -566,7 +902,7 @@ pub mod pallet {
         /// # Arguments
         /// * `task_id` - The task to cancel
         #[pallet::call_index(6)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(4, 5))]
         pub fn cancel_task(origin: OriginFor<T>, task_id: TaskId) -> DispatchResult {
             let poster = ensure_signed(origin)?;
 
@@ -577,11 +913,14 @@ pub mod pallet {
 
                 // Unreserve escrow
                 T::Currency::unreserve(&poster, task.reward);
+                Self::release_escrow(task.reward);
 
                 task.status = TaskStatus::Cancelled;
 
                 Ok(())
             })?;
+            Self::release_task_indices(task_id, &poster, None);
+            Self::queue_for_pruning(task_id);
 
             Self::deposit_event(Event::TaskCancelled { task_id });
 
@@ -594,7 +933,7 @@ pub mod pallet {
         /// * `task_id` - The disputed task
         /// * `winner` - Who gets the escrow
         #[pallet::call_index(7)]
-        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(6, 7))]
         pub fn resolve_dispute(
             origin: OriginFor<T>,
             task_id: TaskId,
@@ -621,6 +960,7 @@ pub mod pallet {
             // Unreserve and transfer to winner
             T::Currency::unreserve(&poster, task.reward);
             T::Currency::transfer(&poster, &winner, task.reward, ExistenceRequirement::KeepAlive)?;
+            Self::release_escrow(task.reward);
 
             // Update task status
             Tasks::<T>::try_mutate(task_id, |maybe_task| -> DispatchResult {
@@ -628,14 +968,416 @@ pub mod pallet {
                 t.status = TaskStatus::Approved; // Mark as resolved
                 Ok(())
             })?;
+            Self::release_task_indices(task_id, &poster, Some(&worker));
+            Self::queue_for_pruning(task_id);
 
             // Update reputations
-            T::ReputationManager::on_dispute_resolved(&winner, &loser);
+            T::ReputationManager::on_dispute_resolved(&winner, &loser, task.reward, task.category);
 
             Self::deposit_event(Event::DisputeResolved { task_id, winner });
 
             Ok(())
         }
+
+        /// Require bidders to declare `capability` at `min_version` or above, or
+        /// lift the requirement with `None`.
+        ///
+        /// Only the poster can set it, and only while the task is Open with no bids,
+        /// so every bid is checked against the same requirement.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+        pub fn require_capability(
+            origin: OriginFor<T>,
+            task_id: TaskId,
+            capability: Option<Vec<u8>>,
+            min_version: u32,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+
+            let task = Tasks::<T>::get(task_id).ok_or(Error::<T>::TaskNotFound)?;
+            ensure!(task.poster == poster, Error::<T>::NotPoster);
+            ensure!(
+                task.status == TaskStatus::Open,
+                Error::<T>::InvalidTaskStatus
+            );
+            ensure!(
+                TaskBidCount::<T>::get(task_id) == 0,
+                Error::<T>::TaskHasBids
+            );
+
+            match &capability {
+                Some(id) => {
+                    let bounded_id: BoundedVec<u8, T::MaxCapabilityIdLength> = id
+                        .clone()
+                        .try_into()
+                        .map_err(|_| Error::<T>::InvalidCapability)?;
+                    ensure!(!bounded_id.is_empty(), Error::<T>::InvalidCapability);
+                    TaskRequirements::<T>::insert(
+                        task_id,
+                        CapabilityRequirement {
+                            capability: bounded_id,
+                            min_version,
+                        },
+                    );
+                }
+                None => TaskRequirements::<T>::remove(task_id),
+            }
+
+            Self::deposit_event(Event::CapabilityRequired {
+                task_id,
+                capability,
+                min_version,
+            });
+
+            Ok(())
+        }
+    }
+
+    // ========== Internal Functions ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Release a completed task's escrow to its worker and mark it Approved.
+        pub(crate) fn do_approve_work(task_id: TaskId) -> DispatchResult {
+            let task = Tasks::<T>::get(task_id).ok_or(Error::<T>::TaskNotFound)?;
+            ensure!(
+                task.status == TaskStatus::Completed,
+                Error::<T>::InvalidTaskStatus
+            );
+
+            let poster = task.poster;
+            let worker = task.assigned_to.ok_or(Error::<T>::NotAssignedWorker)?;
+
+            // Unreserve from poster and transfer to worker
+            T::Currency::unreserve(&poster, task.reward);
+            T::Currency::transfer(&poster, &worker, task.reward, ExistenceRequirement::KeepAlive)?;
+            Self::release_escrow(task.reward);
+
+            // Update task status
+            Tasks::<T>::try_mutate(task_id, |maybe_task| -> DispatchResult {
+                let t = maybe_task.as_mut().ok_or(Error::<T>::TaskNotFound)?;
+                t.status = TaskStatus::Approved;
+                Ok(())
+            })?;
+            Self::release_task_indices(task_id, &poster, Some(&worker));
+            Self::queue_for_pruning(task_id);
+
+            // Update reputations
+            T::ReputationManager::on_task_completed(&worker, task.reward, task.category);
+            T::ReputationManager::on_task_approved(&poster, task.reward);
+
+            Self::deposit_event(Event::WorkApproved { task_id });
+
+            Ok(())
+        }
+
+        /// Drop a task that reached a terminal status from the poster's and
+        /// (if assigned) the worker's active task indices.
+        pub(crate) fn release_task_indices(
+            task_id: TaskId,
+            poster: &T::AccountId,
+            worker: Option<&T::AccountId>,
+        ) {
+            ActiveTasks::<T>::mutate(poster, |tasks| tasks.retain(|id| *id != task_id));
+            if let Some(worker) = worker {
+                WorkerTasks::<T>::mutate(worker, |tasks| tasks.retain(|id| *id != task_id));
+            }
+        }
+
+        /// Whether `bidder` meets the capability requirement of `task_id`, if any.
+        fn meets_requirement(task_id: TaskId, bidder: &T::AccountId) -> bool {
+            TaskRequirements::<T>::get(task_id).is_none_or(|requirement| {
+                T::AgentStanding::has_capability(
+                    bidder,
+                    &requirement.capability,
+                    requirement.min_version,
+                )
+            })
+        }
+
+        /// Storage deposit for a task whose title and description take `bytes` bytes.
+        pub fn task_deposit(bytes: usize) -> BalanceOf<T> {
+            let bytes: u32 = bytes.try_into().unwrap_or(u32::MAX);
+            T::TaskDepositBase::get()
+                .saturating_add(T::TaskDepositPerByte::get().saturating_mul(bytes.into()))
+        }
+
+        /// Take a task's reward out of the running escrow totals once it is
+        /// unreserved and the task reaches a terminal status.
+        pub(crate) fn release_escrow(reward: BalanceOf<T>) {
+            Escrow::<T>::mutate(|totals| {
+                totals.locked_rewards = totals.locked_rewards.saturating_sub(reward);
+                totals.active_tasks = totals.active_tasks.saturating_sub(1);
+            });
+        }
+
+        /// Append a task that just reached a terminal status to the prune queue.
+        pub(crate) fn queue_for_pruning(task_id: TaskId) {
+            let tail = PruneQueueTail::<T>::get();
+            let now = <frame_system::Pallet<T>>::block_number();
+            PruneQueue::<T>::insert(tail, (task_id, now));
+            PruneQueueTail::<T>::put(tail.saturating_add(1));
+        }
+
+        /// Worst-case weight of pruning a single task.
+        fn prune_task_weight() -> Weight {
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(
+                    4,
+                    8u64.saturating_add(T::MaxBidsPerTask::get() as u64),
+                )
+        }
+
+        /// Prune queued terminal tasks whose retention period has elapsed, within
+        /// `remaining_weight` and at most `MaxPrunedTasksPerBlock` per call.
+        pub(crate) fn prune_terminal_tasks(
+            now: BlockNumberFor<T>,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let mut used = T::DbWeight::get().reads(2);
+            if remaining_weight.any_lt(used) {
+                return Weight::zero();
+            }
+
+            let per_task = Self::prune_task_weight();
+            let retention = T::TaskRetentionPeriod::get();
+            let tail = PruneQueueTail::<T>::get();
+            let start = PruneQueueHead::<T>::get();
+            let mut head = start;
+            let mut pruned = 0u32;
+
+            while head < tail && pruned < T::MaxPrunedTasksPerBlock::get() {
+                if remaining_weight.any_lt(used.saturating_add(per_task)) {
+                    break;
+                }
+                used = used.saturating_add(T::DbWeight::get().reads(1));
+                if let Some((task_id, concluded_at)) = PruneQueue::<T>::get(head) {
+                    if now < concluded_at.saturating_add(retention) {
+                        break;
+                    }
+                    PruneQueue::<T>::remove(head);
+                    Self::prune_task(task_id);
+                    used = used.saturating_add(per_task);
+                    pruned = pruned.saturating_add(1);
+                }
+                head = head.saturating_add(1);
+            }
+
+            if head != start {
+                PruneQueueHead::<T>::put(head);
+                used = used.saturating_add(T::DbWeight::get().writes(1));
+            }
+            used
+        }
+
+        /// Remove a task and its bids from state, keeping a hash commitment, and
+        /// refund the poster's storage deposit.
+        fn prune_task(task_id: TaskId) {
+            let Some(task) = Tasks::<T>::take(task_id) else {
+                return;
+            };
+            let bids: Vec<BidInfo<T>> =
+                TaskBids::<T>::drain_prefix(task_id).map(|(_, bid)| bid).collect();
+            TaskBidCount::<T>::remove(task_id);
+            TaskRequirements::<T>::remove(task_id);
+
+            let commitment = T::Hashing::hash_of(&(task_id, &task, &bids));
+            ArchivedTasks::<T>::insert(task_id, commitment);
+
+            let deposit = TaskDeposits::<T>::take(task_id);
+            T::Currency::unreserve(&task.poster, deposit);
+            if !deposit.is_zero() {
+                Escrow::<T>::mutate(|totals| {
+                    totals.locked_deposits = totals.locked_deposits.saturating_sub(deposit);
+                });
+            }
+
+            Self::deposit_event(Event::TaskPruned {
+                task_id,
+                poster: task.poster,
+                worker: task.assigned_to,
+                status: task.status,
+                reward: task.reward,
+                commitment,
+                deposit_refunded: deposit,
+            });
+        }
+    }
+
+    // ========== Runtime API Helpers ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Tasks with the given status, scanning task IDs from `start`, at most `limit`.
+        pub fn tasks_by_status(status: TaskStatus, start: TaskId, limit: u32) -> TaskPageOf<T> {
+            Self::scan_tasks(start, limit, |_, task| task.status == status)
+        }
+
+        /// Non-terminal tasks posted by `poster`.
+        pub fn tasks_by_poster(poster: &T::AccountId) -> Vec<TaskSummaryOf<T>> {
+            Self::summarize_all(ActiveTasks::<T>::get(poster).into_iter())
+        }
+
+        /// Non-terminal tasks assigned to `worker`.
+        pub fn tasks_by_worker(worker: &T::AccountId) -> Vec<TaskSummaryOf<T>> {
+            Self::summarize_all(WorkerTasks::<T>::get(worker).into_iter())
+        }
+
+        /// Bids on `task_id`, skipping the first `start` and returning at most `limit`.
+        pub fn bids_for_task(task_id: TaskId, start: u32, limit: u32) -> Vec<BidSummaryOf<T>> {
+            TaskBids::<T>::iter_prefix_values(task_id)
+                .skip(start as usize)
+                .take(limit as usize)
+                .map(|bid| BidSummary {
+                    bidder: bid.bidder,
+                    amount: bid.amount,
+                    proposal: bid.proposal.into_inner(),
+                    submitted_at: bid.submitted_at,
+                })
+                .collect()
+        }
+
+        /// Rewards and deposits currently locked by the market.
+        pub fn escrow_totals() -> EscrowTotals<BalanceOf<T>> {
+            Escrow::<T>::get()
+        }
+
+        /// Open, unexpired tasks that `bidder` could bid on, scanning task IDs from
+        /// `start`, at most `limit`. Empty if `bidder` is below `MinBidderReputation`
+        /// or a suspended agent; tasks requiring a capability `bidder` lacks, or in a
+        /// category where it is below `MinBidderReputation`, are skipped.
+        pub fn biddable_tasks(bidder: &T::AccountId, start: TaskId, limit: u32) -> TaskPageOf<T> {
+            let minimum = T::MinBidderReputation::get();
+            if !T::ReputationManager::meets_minimum_reputation(bidder, minimum, None)
+                || T::AgentStanding::is_suspended_agent(bidder)
+            {
+                return TaskPage {
+                    tasks: Vec::new(),
+                    next: None,
+                };
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::scan_tasks(start, limit, |task_id, task| {
+                task.status == TaskStatus::Open
+                    && now < task.deadline
+                    && &task.poster != bidder
+                    && !TaskBids::<T>::contains_key(task_id, bidder)
+                    && Self::meets_requirement(task_id, bidder)
+                    && T::ReputationManager::meets_minimum_reputation(
+                        bidder,
+                        minimum,
+                        Some(task.category),
+                    )
+            })
+        }
+
+        /// Tasks passing `filter`, at most `limit`, scanning at most
+        /// `MAX_SCANNED_TASKS` task IDs from `start` so a listing stays bounded
+        /// however many tasks have been posted.
+        fn scan_tasks(
+            start: TaskId,
+            limit: u32,
+            filter: impl Fn(TaskId, &TaskInfo<T>) -> bool,
+        ) -> TaskPageOf<T> {
+            let count = TaskCount::<T>::get();
+            let end = count.min(start.saturating_add(MAX_SCANNED_TASKS));
+            let mut tasks = Vec::new();
+            let mut task_id = start;
+            while task_id < end && tasks.len() < limit as usize {
+                if let Some(task) = Tasks::<T>::get(task_id).filter(|task| filter(task_id, task)) {
+                    tasks.push(Self::summarize(task_id, task));
+                }
+                task_id = task_id.saturating_add(1);
+            }
+            TaskPage {
+                tasks,
+                next: (task_id < count).then_some(task_id),
+            }
+        }
+
+        fn summarize_all(task_ids: impl Iterator<Item = TaskId>) -> Vec<TaskSummaryOf<T>> {
+            task_ids
+                .filter_map(|task_id| {
+                    Tasks::<T>::get(task_id).map(|task| Self::summarize(task_id, task))
+                })
+                .collect()
+        }
+
+        fn summarize(task_id: TaskId, task: TaskInfo<T>) -> TaskSummaryOf<T> {
+            TaskSummary {
+                task_id,
+                poster: task.poster,
+                title: task.title.into_inner(),
+                description: task.description.into_inner(),
+                category: task.category,
+                reward: task.reward,
+                deposit: TaskDeposits::<T>::get(task_id),
+                deadline: task.deadline,
+                status: task.status,
+                assigned_to: task.assigned_to,
+                created_at: task.created_at,
+                required_capability: TaskRequirements::<T>::get(task_id).map(|requirement| {
+                    (requirement.capability.into_inner(), requirement.min_version)
+                }),
+            }
+        }
+    }
+
+    // ========== Validation ==========
+
+    impl<T: Config> pallet_validation::ValidatedWork<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn worker_of(
+            task_id: TaskId,
+            requester: &T::AccountId,
+        ) -> Option<(T::AccountId, BalanceOf<T>)> {
+            Tasks::<T>::get(task_id)
+                .filter(|task| task.status == TaskStatus::Completed && task.poster == *requester)
+                .and_then(|task| Some((task.assigned_to?, task.reward)))
+        }
+
+        fn on_validation_concluded(task_id: TaskId, passed: bool) {
+            if passed {
+                // Work already approved or disputed by the poster is left alone.
+                let _ = frame_support::storage::with_storage_layer(|| {
+                    Self::do_approve_work(task_id)
+                });
+            }
+        }
+    }
+
+    // ========== Reviews ==========
+
+    impl<T: Config> pallet_reputation::ReviewableTasks<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn counterparty(
+            task_id: TaskId,
+            reviewer: &T::AccountId,
+        ) -> Option<(T::AccountId, BalanceOf<T>, TaskCategory)> {
+            let task =
+                Tasks::<T>::get(task_id).filter(|task| task.status == TaskStatus::Approved)?;
+            let worker = task.assigned_to?;
+            if *reviewer == task.poster {
+                Some((worker, task.reward, task.category))
+            } else if *reviewer == worker {
+                Some((task.poster, task.reward, task.category))
+            } else {
+                None
+            }
+        }
+    }
+
+    // ========== Messaging ==========
+
+    impl<T: Config> pallet_messaging::NegotiableTasks<T::AccountId> for Pallet<T> {
+        fn is_negotiable(task_id: TaskId) -> bool {
+            Tasks::<T>::get(task_id).is_some_and(|task| !task.status.is_terminal())
+        }
+
+        fn is_negotiator(task_id: TaskId, account: &T::AccountId) -> bool {
+            Tasks::<T>::get(task_id).is_some_and(|task| {
+                task.poster == *account
+                    || task.assigned_to.as_ref() == Some(account)
+                    || TaskBids::<T>::contains_key(task_id, account)
+            })
+        }
     }
 
     // ========== Weight Info Trait ==========
@@ -649,6 +1391,7 @@ pub mod pallet {
         fn dispute_task() -> Weight;
         fn cancel_task() -> Weight;
         fn resolve_dispute() -> Weight;
+        fn require_capability() -> Weight;
     }
 
     impl WeightInfo for () {
@@ -676,5 +1419,8 @@ pub mod pallet {
         fn resolve_dispute() -> Weight {
             Weight::from_parts(10_000, 0)
         }
+        fn require_capability() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
     }
 }
@@ -0,0 +1,297 @@
+//! Storage migrations for the Task Market pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+
+/// Version 0 -> 1: rebuild the `ActiveTasks` and `WorkerTasks` indices from `Tasks`.
+///
+/// Before v1, `ActiveTasks` was never pruned on terminal transitions and there was no
+/// per-worker index, so both are recomputed from scratch from the canonical task set.
+pub mod v1 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`RebuildTaskIndices`] in the runtime.
+    pub struct RebuildTaskIndicesInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for RebuildTaskIndicesInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut writes: u64 = 0;
+
+            let cleared_active = ActiveTasks::<T>::clear(u32::MAX, None).unique;
+            let cleared_worker = WorkerTasks::<T>::clear(u32::MAX, None).unique;
+            writes = writes.saturating_add(cleared_active as u64 + cleared_worker as u64);
+
+            for (task_id, task) in v4::Tasks::<T>::iter() {
+                reads = reads.saturating_add(1);
+                if task.status.is_terminal() {
+                    continue;
+                }
+
+                ActiveTasks::<T>::mutate(&task.poster, |tasks| {
+                    if tasks.try_push(task_id).is_err() {
+                        log::warn!(
+                            target: "runtime::task-market",
+                            "v1: poster active task index full, dropping task {}",
+                            task_id,
+                        );
+                    }
+                });
+                writes = writes.saturating_add(1);
+
+                if let Some(worker) = task.assigned_to {
+                    WorkerTasks::<T>::mutate(&worker, |tasks| {
+                        if tasks.try_push(task_id).is_err() {
+                            log::warn!(
+                                target: "runtime::task-market",
+                                "v1: worker active task index full, dropping task {}",
+                                task_id,
+                            );
+                        }
+                    });
+                    writes = writes.saturating_add(1);
+                }
+            }
+
+            log::info!(
+                target: "runtime::task-market",
+                "v1: rebuilt task indices from {} tasks",
+                reads,
+            );
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Rebuilds `ActiveTasks` and `WorkerTasks`, then bumps the storage version to 1.
+    pub type RebuildTaskIndices<T> = VersionedMigration<
+        0,
+        1,
+        RebuildTaskIndicesInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 1 -> 2: queue tasks that were already terminal for pruning.
+///
+/// Tasks that concluded before pruning existed were never added to `PruneQueue`. They
+/// are queued as of the upgrade block, so they are pruned once `TaskRetentionPeriod`
+/// has elapsed from the upgrade. They carry no storage deposit.
+pub mod v2 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`QueueTerminalTasks`] in the runtime.
+    pub struct QueueTerminalTasksInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for QueueTerminalTasksInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut queued: u64 = 0;
+
+            for (task_id, task) in v4::Tasks::<T>::iter() {
+                reads = reads.saturating_add(1);
+                if task.status.is_terminal() {
+                    Pallet::<T>::queue_for_pruning(task_id);
+                    queued = queued.saturating_add(1);
+                }
+            }
+
+            log::info!(
+                target: "runtime::task-market",
+                "v2: queued {} of {} tasks for pruning",
+                queued,
+                reads,
+            );
+
+            T::DbWeight::get().reads_writes(
+                reads.saturating_add(queued),
+                queued.saturating_mul(2),
+            )
+        }
+    }
+
+    /// Queues pre-existing terminal tasks for pruning, then bumps the storage version to 2.
+    pub type QueueTerminalTasks<T> = VersionedMigration<
+        1,
+        2,
+        QueueTerminalTasksInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 2 -> 3: count the bids held for each task.
+///
+/// Before v3 `MaxBidsPerTask` was not enforced, so `TaskBidCount` is filled in from
+/// `TaskBids`. Tasks already over the limit keep their bids and accept no more until
+/// they are pruned.
+pub mod v3 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`CountTaskBids`] in the runtime.
+    pub struct CountTaskBidsInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for CountTaskBidsInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut writes: u64 = 0;
+
+            for (task_id, _, _) in TaskBids::<T>::iter() {
+                reads = reads.saturating_add(1);
+                TaskBidCount::<T>::mutate(task_id, |count| *count = count.saturating_add(1));
+                writes = writes.saturating_add(1);
+            }
+
+            for (task_id, count) in TaskBidCount::<T>::iter() {
+                if count > T::MaxBidsPerTask::get() {
+                    log::warn!(
+                        target: "runtime::task-market",
+                        "v3: task {} holds {} bids, over MaxBidsPerTask",
+                        task_id,
+                        count,
+                    );
+                }
+            }
+
+            log::info!(
+                target: "runtime::task-market",
+                "v3: counted {} bids",
+                reads,
+            );
+
+            T::DbWeight::get().reads_writes(reads.saturating_mul(2), writes)
+        }
+    }
+
+    /// Fills in `TaskBidCount`, then bumps the storage version to 3.
+    pub type CountTaskBids<T> = VersionedMigration<
+        2,
+        3,
+        CountTaskBidsInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 3 -> 4: add `TaskInfo::category`.
+///
+/// Tasks posted before categories existed are moved to `DEFAULT_CATEGORY`.
+pub mod v4 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// `TaskInfo` before v4, without `category`.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct OldTaskInfo<T: Config> {
+        pub poster: T::AccountId,
+        pub title: BoundedVec<u8, T::MaxTitleLength>,
+        pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        pub reward: BalanceOf<T>,
+        pub deadline: BlockNumberFor<T>,
+        pub status: TaskStatus,
+        pub assigned_to: Option<T::AccountId>,
+        pub created_at: BlockNumberFor<T>,
+    }
+
+    /// `Tasks` before v4.
+    #[frame_support::storage_alias]
+    pub type Tasks<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, TaskId, OldTaskInfo<T>, OptionQuery>;
+
+    /// Unversioned inner migration. Use [`AddTaskCategory`] in the runtime.
+    pub struct AddTaskCategoryInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for AddTaskCategoryInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            crate::Tasks::<T>::translate_values::<OldTaskInfo<T>, _>(|old| {
+                translated = translated.saturating_add(1);
+                Some(TaskInfo {
+                    poster: old.poster,
+                    title: old.title,
+                    description: old.description,
+                    category: DEFAULT_CATEGORY,
+                    reward: old.reward,
+                    deadline: old.deadline,
+                    status: old.status,
+                    assigned_to: old.assigned_to,
+                    created_at: old.created_at,
+                })
+            });
+
+            log::info!(
+                target: "runtime::task-market",
+                "v4: added category to {} tasks",
+                translated,
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Adds `TaskInfo::category`, then bumps the storage version to 4.
+    pub type AddTaskCategory<T> = VersionedMigration<
+        3,
+        4,
+        AddTaskCategoryInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 4 -> 5: seed the running `Escrow` totals from `Tasks` and `TaskDeposits`.
+///
+/// Before v5, `escrow_totals` summed both maps on every call; the totals are now kept
+/// in storage and updated at each reserve and unreserve, so they are computed once here.
+pub mod v5 {
+    use super::*;
+    use sp_runtime::traits::Saturating;
+
+    /// Unversioned inner migration. Use [`SeedEscrowTotals`] in the runtime.
+    pub struct SeedEscrowTotalsInner<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for SeedEscrowTotalsInner<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads: u64 = 0;
+            let mut totals = EscrowTotals::<BalanceOf<T>>::default();
+
+            for task in crate::Tasks::<T>::iter_values() {
+                reads = reads.saturating_add(1);
+                if task.status.is_terminal() {
+                    continue;
+                }
+                totals.locked_rewards = totals.locked_rewards.saturating_add(task.reward);
+                totals.active_tasks = totals.active_tasks.saturating_add(1);
+            }
+            for deposit in TaskDeposits::<T>::iter_values() {
+                reads = reads.saturating_add(1);
+                totals.locked_deposits = totals.locked_deposits.saturating_add(deposit);
+            }
+
+            log::info!(
+                target: "runtime::task-market",
+                "v5: seeded escrow totals for {} active tasks",
+                totals.active_tasks,
+            );
+            Escrow::<T>::put(totals);
+
+            T::DbWeight::get().reads_writes(reads, 1)
+        }
+    }
+
+    /// Seeds `Escrow`, then bumps the storage version to 5.
+    pub type SeedEscrowTotals<T> = VersionedMigration<
+        4,
+        5,
+        SeedEscrowTotalsInner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -1,11 +1,13 @@
 use crate::{self as pallet_task_market, *};
 use frame_support::{
-    assert_ok, assert_noop, parameter_types,
-    PalletId,
+    assert_noop, assert_ok, parameter_types,
+    traits::{ConstU32, ConstU64},
+    weights::Weight,
+    BoundedVec, PalletId,
 };
 use sp_core::H256;
 use sp_runtime::{
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Hash, IdentityLookup},
     BuildStorage,
 };
 
@@ -19,6 +21,7 @@ frame_support::construct_runtime!(
         Balances: pallet_balances,
         Reputation: pallet_reputation,
         TaskMarket: pallet_task_market,
+        AgentRegistry: pallet_agent_registry,
     }
 );
 
@@ -83,7 +86,13 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const MaxCommentLength: u32 = 256;
     pub const InitialReputation: u32 = 5000;
-    pub const MaxReputationDelta: u32 = 500;
+    pub const PriorWeight: u32 = 4;
+    pub const RatingShare: u8 = 50;
+    pub const ReviewValueCap: u64 = 1000;
+    pub const TrustUpdatePeriod: u64 = 10;
+    pub const MaxTrustStepsPerBlock: u32 = 10;
+    pub const DecayGracePeriod: u64 = 0;
+    pub const DecayHalfLife: u64 = 0;
     pub const MaxHistoryLength: u32 = 100;
 }
 
@@ -93,8 +102,16 @@ impl pallet_reputation::Config for Test {
     type Currency = Balances;
     type MaxCommentLength = MaxCommentLength;
     type InitialReputation = InitialReputation;
-    type MaxReputationDelta = MaxReputationDelta;
+    type PriorWeight = PriorWeight;
+    type RatingShare = RatingShare;
+    type ReviewValueCap = ReviewValueCap;
+    type TrustUpdatePeriod = TrustUpdatePeriod;
+    type MaxTrustStepsPerBlock = MaxTrustStepsPerBlock;
+    type DecayGracePeriod = DecayGracePeriod;
+    type DecayHalfLife = DecayHalfLife;
     type MaxHistoryLength = MaxHistoryLength;
+    type OnReputationChanged = ();
+    type ReviewableTasks = TaskMarket;
 }
 
 parameter_types! {
@@ -105,6 +122,40 @@ parameter_types! {
     pub const MaxBidsPerTask: u32 = 20;
     pub const MinTaskReward: u64 = 100;
     pub const MaxActiveTasksPerAccount: u32 = 50;
+    pub const MaxActiveTasksPerWorker: u32 = 50;
+    pub const TaskRetentionPeriod: u64 = 10;
+    pub const MaxPrunedTasksPerBlock: u32 = 2;
+    pub static TaskDepositBase: u64 = 0;
+    pub static TaskDepositPerByte: u64 = 0;
+    pub const MinBidderReputation: u32 = 3000;
+    /// Accounts [`MockAgentStanding`] reports as inactive agents.
+    pub static InactiveAgents: Vec<u64> = vec![];
+    /// Accounts [`MockAgentStanding`] reports as suspended agents.
+    pub static SuspendedAgents: Vec<u64> = vec![];
+    /// Capabilities [`MockAgentStanding`] reports for each account, with their version.
+    pub static DeclaredCapabilities: Vec<(u64, Vec<u8>, u32)> = vec![];
+}
+
+pub struct MockAgentStanding;
+
+impl pallet_agent_registry::AgentStanding<u64> for MockAgentStanding {
+    fn is_agent(_account: &u64) -> bool {
+        true
+    }
+
+    fn is_inactive_agent(account: &u64) -> bool {
+        InactiveAgents::get().contains(account)
+    }
+
+    fn is_suspended_agent(account: &u64) -> bool {
+        SuspendedAgents::get().contains(account)
+    }
+
+    fn has_capability(account: &u64, capability: &[u8], min_version: u32) -> bool {
+        DeclaredCapabilities::get()
+            .iter()
+            .any(|(a, c, v)| a == account && c.as_slice() == capability && *v >= min_version)
+    }
 }
 
 impl pallet_task_market::Config for Test {
@@ -119,6 +170,57 @@ impl pallet_task_market::Config for Test {
     type MaxBidsPerTask = MaxBidsPerTask;
     type MinTaskReward = MinTaskReward;
     type MaxActiveTasksPerAccount = MaxActiveTasksPerAccount;
+    type MaxActiveTasksPerWorker = MaxActiveTasksPerWorker;
+    type TaskRetentionPeriod = TaskRetentionPeriod;
+    type TaskDepositBase = TaskDepositBase;
+    type TaskDepositPerByte = TaskDepositPerByte;
+    type MaxPrunedTasksPerBlock = MaxPrunedTasksPerBlock;
+    type MinBidderReputation = MinBidderReputation;
+    type AgentStanding = MockAgentStanding;
+    type MaxCapabilityIdLength = frame_support::traits::ConstU32<64>;
+}
+
+parameter_types! {
+    pub const AgentPalletId: PalletId = PalletId(*b"clw/agnt");
+    pub AllowedDidMethods: Vec<Vec<u8>> = vec![b"claw".to_vec()];
+}
+
+impl pallet_agent_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type RuntimeCall = RuntimeCall;
+    type PalletId = AgentPalletId;
+    type MaxOperatorsPerAgent = ConstU32<2>;
+    type MaxOperatorScopes = ConstU32<4>;
+    type OperatorSpendingPeriod = ConstU64<100>;
+    type InactivityPeriod = ConstU64<50>;
+    type MinHeartbeatInterval = ConstU64<5>;
+    type HeartbeatUnsignedPriority = ConstU64<100>;
+    type HeartbeatSignature = sp_runtime::testing::TestSignature;
+    type MaxInactivitySweepPerBlock = ConstU32<2>;
+    type MaxCapabilityIdLength = ConstU32<64>;
+    type MaxCapabilitiesPerAgent = ConstU32<3>;
+    type Currency = Balances;
+    type AgentDepositBase = ConstU64<0>;
+    type AgentDepositPerByte = ConstU64<0>;
+    type MaxDidLength = ConstU32<256>;
+    type MaxMetadataLength = ConstU32<256>;
+    type MaxAgentsPerOwner = ConstU32<10>;
+    type AllowedDidMethods = AllowedDidMethods;
+    type DidReleaseCooldown = ConstU64<10>;
+    type PurgeGracePeriod = ConstU64<20>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxFragmentLength = ConstU32<32>;
+    type MaxServiceEndpointLength = ConstU32<128>;
+    type Reputation = Reputation;
+    type ReputationOrigin = frame_system::EnsureRoot<u64>;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type MaxSuspensionReasonLength = ConstU32<32>;
+    type MaxDomainLength = ConstU32<32>;
+    type IssuerOrigin = frame_system::EnsureRoot<u64>;
+    type MaxIssuerNameLength = ConstU32<32>;
+    type MaxAttestationsPerAgent = ConstU32<2>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -152,7 +254,8 @@ fn post_task_works() {
             title.clone(),
             description,
             reward,
-            deadline
+            deadline,
+            DEFAULT_CATEGORY
         ));
 
         // Check task was created
@@ -180,7 +283,8 @@ fn post_task_fails_if_reward_too_low() {
                 b"Task".to_vec(),
                 b"Description".to_vec(),
                 50, // Below MinTaskReward (100)
-                1000
+                1000,
+                DEFAULT_CATEGORY
             ),
             Error::<Test>::RewardTooLow
         );
@@ -199,7 +303,8 @@ fn bid_on_task_works() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
 
         // Submit bid
@@ -228,19 +333,61 @@ fn cannot_bid_on_own_task() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
 
         // Try to bid on own task
         assert_noop!(
-            TaskMarket::bid_on_task(
-                RuntimeOrigin::signed(poster),
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(poster), 0, 800, b"Proposal".to_vec()),
+            Error::<Test>::CannotBidOnOwnTask
+        );
+    });
+}
+
+#[test]
+fn bids_are_capped_per_task() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        let max = MaxBidsPerTask::get() as u64;
+        for bidder in 2..2 + max {
+            assert_ok!(TaskMarket::bid_on_task(
+                RuntimeOrigin::signed(bidder),
                 0,
                 800,
                 b"Proposal".to_vec()
-            ),
-            Error::<Test>::CannotBidOnOwnTask
+            ));
+        }
+        assert_eq!(TaskBidCount::<Test>::get(0), MaxBidsPerTask::get());
+        assert_noop!(
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(2 + max), 0, 800, b"Proposal".to_vec()),
+            Error::<Test>::TooManyBids
         );
+
+        // Bidding again replaces the earlier bid without taking another slot
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(2),
+            0,
+            700,
+            b"Cheaper".to_vec()
+        ));
+        assert_eq!(TaskMarket::task_bids(0, 2).unwrap().amount, 700);
+        assert_eq!(TaskBidCount::<Test>::get(0), MaxBidsPerTask::get());
+
+        // Pruning the task frees its count along with its bids
+        assert_ok!(TaskMarket::cancel_task(RuntimeOrigin::signed(1), 0));
+        run_idle(11);
+        assert!(TaskMarket::tasks(0).is_none());
+        assert_eq!(TaskBidCount::<Test>::get(0), 0);
     });
 }
 
@@ -256,7 +403,8 @@ fn assign_task_works() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
 
         // Submit bid
@@ -294,7 +442,8 @@ fn only_poster_can_assign() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_ok!(TaskMarket::bid_on_task(
             RuntimeOrigin::signed(bidder),
@@ -311,6 +460,79 @@ fn only_poster_can_assign() {
     });
 }
 
+#[test]
+fn assign_task_rejects_inactive_agents() {
+    new_test_ext().execute_with(|| {
+        let poster = 1;
+        let bidder = 2;
+
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(poster),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(bidder),
+            0,
+            800,
+            b"Proposal".to_vec()
+        ));
+
+        InactiveAgents::set(vec![bidder]);
+        assert_noop!(
+            TaskMarket::assign_task(RuntimeOrigin::signed(poster), 0, bidder),
+            Error::<Test>::AgentInactive
+        );
+
+        InactiveAgents::set(vec![]);
+        assert_ok!(TaskMarket::assign_task(
+            RuntimeOrigin::signed(poster),
+            0,
+            bidder
+        ));
+    });
+}
+
+#[test]
+fn suspended_agents_cannot_bid_or_be_assigned() {
+    new_test_ext().execute_with(|| {
+        let poster = 1;
+        let bidder = 2;
+
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(poster),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        SuspendedAgents::set(vec![bidder]);
+        assert_noop!(
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(bidder), 0, 800, b"Proposal".to_vec()),
+            Error::<Test>::AgentSuspended
+        );
+
+        SuspendedAgents::set(vec![]);
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(bidder),
+            0,
+            800,
+            b"Proposal".to_vec()
+        ));
+
+        SuspendedAgents::set(vec![bidder]);
+        assert_noop!(
+            TaskMarket::assign_task(RuntimeOrigin::signed(poster), 0, bidder),
+            Error::<Test>::AgentSuspended
+        );
+    });
+}
+
 #[test]
 fn submit_and_approve_work_releases_escrow() {
     new_test_ext().execute_with(|| {
@@ -323,7 +545,8 @@ fn submit_and_approve_work_releases_escrow() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_ok!(TaskMarket::bid_on_task(
             RuntimeOrigin::signed(worker),
@@ -354,10 +577,7 @@ fn submit_and_approve_work_releases_escrow() {
         assert_eq!(task.status, TaskStatus::Approved);
 
         // Check payment transferred
-        assert_eq!(
-            Balances::free_balance(worker),
-            worker_balance_before + 1000
-        );
+        assert_eq!(Balances::free_balance(worker), worker_balance_before + 1000);
         // Poster started with 10000, now has 9000 (paid 1000 to worker)
         assert_eq!(Balances::free_balance(poster), 9000);
         assert_eq!(Balances::reserved_balance(poster), 0);
@@ -381,7 +601,8 @@ fn cancel_task_refunds_escrow() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
 
         // Check escrow reserved
@@ -411,7 +632,8 @@ fn cannot_cancel_assigned_task() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_ok!(TaskMarket::bid_on_task(
             RuntimeOrigin::signed(worker),
@@ -445,7 +667,8 @@ fn dispute_task_works() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_ok!(TaskMarket::bid_on_task(
             RuntimeOrigin::signed(worker),
@@ -489,7 +712,8 @@ fn resolve_dispute_updates_reputation() {
             b"Task".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_ok!(TaskMarket::bid_on_task(
             RuntimeOrigin::signed(worker),
@@ -524,10 +748,10 @@ fn resolve_dispute_updates_reputation() {
         ));
 
         // Check reputation changes
-        // Worker wins: +200, Poster loses: -500
+        // Worker's record rises to 6000, poster's falls to 4000
         assert_eq!(
             Reputation::reputations(worker).score,
-            worker_rep_before + 200
+            worker_rep_before + 500
         );
         assert_eq!(
             Reputation::reputations(poster).score,
@@ -550,7 +774,8 @@ fn task_count_increments() {
             b"Task 1".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_eq!(TaskMarket::task_count(), 1);
 
@@ -559,8 +784,934 @@ fn task_count_increments() {
             b"Task 2".to_vec(),
             b"Description".to_vec(),
             1000,
-            1000
+            1000,
+            DEFAULT_CATEGORY
         ));
         assert_eq!(TaskMarket::task_count(), 2);
     });
 }
+
+/// Post a task from `poster` and assign it to `worker`, returning its ID.
+fn post_and_assign(poster: u64, worker: u64) -> TaskId {
+    let task_id = TaskMarket::task_count();
+    assert_ok!(TaskMarket::post_task(
+        RuntimeOrigin::signed(poster),
+        b"Task".to_vec(),
+        b"Description".to_vec(),
+        100,
+        1000,
+        DEFAULT_CATEGORY
+    ));
+    assert_ok!(TaskMarket::bid_on_task(
+        RuntimeOrigin::signed(worker),
+        task_id,
+        100,
+        b"Proposal".to_vec()
+    ));
+    assert_ok!(TaskMarket::assign_task(
+        RuntimeOrigin::signed(poster),
+        task_id,
+        worker
+    ));
+    task_id
+}
+
+#[test]
+fn terminal_transitions_release_active_tasks() {
+    new_test_ext().execute_with(|| {
+        let poster = 1;
+        let worker = 2;
+
+        // Approved
+        let approved = post_and_assign(poster, worker);
+        assert_eq!(TaskMarket::active_tasks(poster).to_vec(), vec![approved]);
+        assert_eq!(TaskMarket::worker_tasks(worker).to_vec(), vec![approved]);
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(worker),
+            approved,
+            b"Proof".to_vec()
+        ));
+        assert_ok!(TaskMarket::approve_work(
+            RuntimeOrigin::signed(poster),
+            approved
+        ));
+        assert!(TaskMarket::active_tasks(poster).is_empty());
+        assert!(TaskMarket::worker_tasks(worker).is_empty());
+
+        // Cancelled
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(poster),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            100,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        assert_ok!(TaskMarket::cancel_task(RuntimeOrigin::signed(poster), 1));
+        assert!(TaskMarket::active_tasks(poster).is_empty());
+
+        // Resolved dispute
+        let disputed = post_and_assign(poster, worker);
+        assert_ok!(TaskMarket::dispute_task(
+            RuntimeOrigin::signed(worker),
+            disputed,
+            b"Dispute".to_vec()
+        ));
+        assert_eq!(TaskMarket::worker_tasks(worker).to_vec(), vec![disputed]);
+        assert_ok!(TaskMarket::resolve_dispute(
+            RuntimeOrigin::root(),
+            disputed,
+            poster
+        ));
+        assert!(TaskMarket::active_tasks(poster).is_empty());
+        assert!(TaskMarket::worker_tasks(worker).is_empty());
+    });
+}
+
+#[test]
+fn poster_not_blocked_after_max_active_tasks() {
+    new_test_ext().execute_with(|| {
+        let poster = 1;
+
+        // Post and cancel more tasks than the active cap allows at once
+        for task_id in 0..(MaxActiveTasksPerAccount::get() as u64 + 5) {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(poster),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                100,
+                1000,
+                DEFAULT_CATEGORY
+            ));
+            assert_ok!(TaskMarket::cancel_task(
+                RuntimeOrigin::signed(poster),
+                task_id
+            ));
+        }
+
+        assert!(TaskMarket::active_tasks(poster).is_empty());
+    });
+}
+
+#[test]
+fn worker_task_cap_is_enforced() {
+    new_test_ext().execute_with(|| {
+        let worker = 2;
+
+        // Fill the worker's index directly to the cap
+        let full: Vec<TaskId> = (100..100 + MaxActiveTasksPerWorker::get() as u64).collect();
+        WorkerTasks::<Test>::insert(worker, BoundedVec::try_from(full).unwrap());
+
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            100,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(worker),
+            0,
+            100,
+            b"Proposal".to_vec()
+        ));
+        assert_noop!(
+            TaskMarket::assign_task(RuntimeOrigin::signed(1), 0, worker),
+            Error::<Test>::TooManyWorkerTasks
+        );
+    });
+}
+
+#[test]
+fn v1_migration_rebuilds_task_indices() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let poster = 1;
+        let worker = 2;
+
+        let open = post_and_assign(poster, worker);
+        let done = post_and_assign(poster, worker);
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(worker),
+            done,
+            b"Proof".to_vec()
+        ));
+        assert_ok!(TaskMarket::approve_work(
+            RuntimeOrigin::signed(poster),
+            done
+        ));
+
+        // Simulate the pre-v1 state: stale poster index and no worker index
+        ActiveTasks::<Test>::insert(poster, BoundedVec::try_from(vec![open, done]).unwrap());
+        WorkerTasks::<Test>::remove(worker);
+        downgrade_tasks();
+        StorageVersion::new(0).put::<TaskMarket>();
+
+        migrations::v1::RebuildTaskIndices::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TaskMarket::active_tasks(poster).to_vec(), vec![open]);
+        assert_eq!(TaskMarket::worker_tasks(worker).to_vec(), vec![open]);
+        assert_eq!(TaskMarket::on_chain_storage_version(), 1);
+    });
+}
+
+/// Rewrite every task in the layout stored before v4, without a category.
+fn downgrade_tasks() {
+    let tasks: Vec<_> = Tasks::<Test>::drain().collect();
+    for (task_id, task) in tasks {
+        migrations::v4::Tasks::<Test>::insert(
+            task_id,
+            migrations::v4::OldTaskInfo {
+                poster: task.poster,
+                title: task.title,
+                description: task.description,
+                reward: task.reward,
+                deadline: task.deadline,
+                status: task.status,
+                assigned_to: task.assigned_to,
+                created_at: task.created_at,
+            },
+        );
+    }
+}
+
+/// Run `on_idle` for block `n` with unlimited weight.
+fn run_idle(n: u64) {
+    use frame_support::traits::OnIdle;
+    System::set_block_number(n);
+    <TaskMarket as OnIdle<u64>>::on_idle(n, Weight::MAX);
+}
+
+#[test]
+fn post_task_reserves_storage_deposit() {
+    new_test_ext().execute_with(|| {
+        TaskDepositBase::set(10);
+        TaskDepositPerByte::set(1);
+
+        // 4 byte title + 11 byte description
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        assert_eq!(TaskMarket::task_deposits(0), 25);
+        assert_eq!(Balances::reserved_balance(1), 1025);
+    });
+}
+
+#[test]
+fn terminal_task_pruned_after_retention_period() {
+    new_test_ext().execute_with(|| {
+        TaskDepositBase::set(10);
+        TaskDepositPerByte::set(1);
+        System::set_block_number(1);
+        let poster = 1;
+        let worker = 2;
+
+        let task_id = post_and_assign(poster, worker);
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(worker),
+            task_id,
+            b"Proof".to_vec()
+        ));
+        assert_ok!(TaskMarket::approve_work(
+            RuntimeOrigin::signed(poster),
+            task_id
+        ));
+        assert_eq!(Balances::reserved_balance(poster), 25);
+        let task = TaskMarket::tasks(task_id).unwrap();
+        let bids = vec![TaskMarket::task_bids(task_id, worker).unwrap()];
+
+        // Still retained before the period elapses
+        run_idle(10);
+        assert!(TaskMarket::tasks(task_id).is_some());
+
+        run_idle(11);
+        assert!(TaskMarket::tasks(task_id).is_none());
+        assert!(TaskMarket::task_bids(task_id, worker).is_none());
+        assert_eq!(TaskMarket::task_deposits(task_id), 0);
+        assert_eq!(Balances::reserved_balance(poster), 0);
+
+        let commitment = BlakeTwo256::hash_of(&(task_id, &task, &bids));
+        assert_eq!(TaskMarket::archived_tasks(task_id), Some(commitment));
+        System::assert_last_event(
+            Event::TaskPruned {
+                task_id,
+                poster,
+                worker: Some(worker),
+                status: TaskStatus::Approved,
+                reward: 100,
+                commitment,
+                deposit_refunded: 25,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn escrow_totals_track_reserves_and_releases() {
+    new_test_ext().execute_with(|| {
+        TaskDepositBase::set(10);
+        TaskDepositPerByte::set(1);
+        System::set_block_number(1);
+        let poster = 1;
+        let worker = 2;
+
+        // Each task escrows 100 and a 25 deposit
+        let approved = post_and_assign(poster, worker);
+        let disputed = post_and_assign(poster, worker);
+        let cancelled = TaskMarket::task_count();
+        for _ in 0..2 {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(poster),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                100,
+                1000,
+                DEFAULT_CATEGORY
+            ));
+        }
+        assert_eq!(
+            TaskMarket::escrow_totals(),
+            EscrowTotals {
+                locked_rewards: 400,
+                locked_deposits: 100,
+                active_tasks: 4,
+            }
+        );
+
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(worker),
+            approved,
+            b"Proof".to_vec()
+        ));
+        assert_ok!(TaskMarket::approve_work(
+            RuntimeOrigin::signed(poster),
+            approved
+        ));
+        assert_ok!(TaskMarket::dispute_task(
+            RuntimeOrigin::signed(poster),
+            disputed,
+            b"Reason".to_vec()
+        ));
+        assert_ok!(TaskMarket::resolve_dispute(
+            RuntimeOrigin::root(),
+            disputed,
+            poster
+        ));
+        assert_ok!(TaskMarket::cancel_task(
+            RuntimeOrigin::signed(poster),
+            cancelled
+        ));
+        assert_eq!(
+            TaskMarket::escrow_totals(),
+            EscrowTotals {
+                locked_rewards: 100,
+                locked_deposits: 100,
+                active_tasks: 1,
+            }
+        );
+
+        // Pruning refunds the deposits of the three terminal tasks, two per block
+        run_idle(11);
+        run_idle(12);
+        assert_eq!(
+            TaskMarket::escrow_totals(),
+            EscrowTotals {
+                locked_rewards: 100,
+                locked_deposits: 25,
+                active_tasks: 1,
+            }
+        );
+        assert_eq!(Balances::reserved_balance(poster), 125);
+    });
+}
+
+#[test]
+fn non_terminal_tasks_are_never_pruned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let task_id = post_and_assign(1, 2);
+
+        run_idle(1000);
+        assert!(TaskMarket::tasks(task_id).is_some());
+        assert_eq!(PruneQueueTail::<Test>::get(), 0);
+    });
+}
+
+#[test]
+fn pruning_respects_per_block_limit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for task_id in 0..3 {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(1),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                100,
+                1000,
+                DEFAULT_CATEGORY
+            ));
+            assert_ok!(TaskMarket::cancel_task(RuntimeOrigin::signed(1), task_id));
+        }
+
+        // MaxPrunedTasksPerBlock is 2
+        run_idle(11);
+        assert!(TaskMarket::tasks(0).is_none());
+        assert!(TaskMarket::tasks(1).is_none());
+        assert!(TaskMarket::tasks(2).is_some());
+
+        run_idle(12);
+        assert!(TaskMarket::tasks(2).is_none());
+        assert_eq!(PruneQueueHead::<Test>::get(), 3);
+    });
+}
+
+#[test]
+fn v2_migration_queues_existing_terminal_tasks() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            100,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        let open = post_and_assign(1, 2);
+
+        // Simulate a task cancelled before the prune queue existed
+        Tasks::<Test>::mutate(0, |t| t.as_mut().unwrap().status = TaskStatus::Cancelled);
+        downgrade_tasks();
+        StorageVersion::new(1).put::<TaskMarket>();
+
+        migrations::v2::QueueTerminalTasks::<Test>::on_runtime_upgrade();
+        assert_eq!(TaskMarket::on_chain_storage_version(), 2);
+        assert_eq!(PruneQueue::<Test>::get(0), Some((0, 1)));
+        StorageVersion::new(3).put::<TaskMarket>();
+        migrations::v4::AddTaskCategory::<Test>::on_runtime_upgrade();
+
+        run_idle(11);
+        assert!(TaskMarket::tasks(0).is_none());
+        assert!(TaskMarket::tasks(open).is_some());
+    });
+}
+
+#[test]
+fn v3_migration_counts_existing_bids() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        for bidder in [2, 3] {
+            assert_ok!(TaskMarket::bid_on_task(
+                RuntimeOrigin::signed(bidder),
+                0,
+                800,
+                b"Proposal".to_vec()
+            ));
+        }
+
+        // Simulate bids placed before they were counted
+        TaskBidCount::<Test>::remove(0);
+        StorageVersion::new(2).put::<TaskMarket>();
+
+        migrations::v3::CountTaskBids::<Test>::on_runtime_upgrade();
+        assert_eq!(TaskMarket::on_chain_storage_version(), 3);
+        assert_eq!(TaskBidCount::<Test>::get(0), 2);
+    });
+}
+
+#[test]
+fn v4_migration_moves_existing_tasks_to_the_default_category() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            7
+        ));
+
+        // Simulate a task posted before categories existed
+        downgrade_tasks();
+        StorageVersion::new(3).put::<TaskMarket>();
+
+        migrations::v4::AddTaskCategory::<Test>::on_runtime_upgrade();
+        assert_eq!(TaskMarket::on_chain_storage_version(), 4);
+        let task = TaskMarket::tasks(0).unwrap();
+        assert_eq!(task.category, DEFAULT_CATEGORY);
+        assert_eq!(task.reward, 1000);
+        assert_eq!(task.status, TaskStatus::Open);
+    });
+}
+
+#[test]
+fn v5_migration_seeds_escrow_totals() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        TaskDepositBase::set(10);
+        TaskDepositPerByte::set(1);
+        let task_id = post_and_assign(1, 2);
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            300,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+        assert_ok!(TaskMarket::cancel_task(
+            RuntimeOrigin::signed(1),
+            task_id + 1
+        ));
+
+        // Simulate tasks posted before the totals were kept
+        Escrow::<Test>::kill();
+        StorageVersion::new(4).put::<TaskMarket>();
+
+        migrations::v5::SeedEscrowTotals::<Test>::on_runtime_upgrade();
+        assert_eq!(TaskMarket::on_chain_storage_version(), 5);
+        assert_eq!(
+            TaskMarket::escrow_totals(),
+            EscrowTotals {
+                locked_rewards: 100,
+                locked_deposits: 50,
+                active_tasks: 1,
+            }
+        );
+    });
+}
+
+#[test]
+fn bid_requires_minimum_reputation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            1000,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        // Drop bidder from 5000 to below MinBidderReputation (3000)
+        assert_ok!(Reputation::slash_reputation(
+            RuntimeOrigin::root(),
+            2,
+            2500,
+            b"Spam".to_vec()
+        ));
+
+        assert_noop!(
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(2), 0, 800, b"Proposal".to_vec()),
+            Error::<Test>::InsufficientReputation
+        );
+    });
+}
+
+#[test]
+fn bid_requires_minimum_reputation_in_the_task_category() {
+    new_test_ext().execute_with(|| {
+        for category in [1, 2] {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(1),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                1000,
+                1000,
+                category
+            ));
+        }
+
+        // A poor record in category 1 leaves the bidder's global score untouched
+        pallet_reputation::CategoryReputations::<Test>::mutate(2, 1, |rep| {
+            rep.rating_weight = 100 * 10_000;
+            rep.record_weight = 100 * 10_000;
+        });
+        assert!(Reputation::category_score(&2, 1) < MinBidderReputation::get());
+        assert_eq!(Reputation::reputations(2).score, 5000);
+
+        assert_noop!(
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(2), 0, 800, b"Proposal".to_vec()),
+            Error::<Test>::InsufficientReputation
+        );
+        let biddable = TaskMarket::biddable_tasks(&2, 0, 10);
+        assert_eq!(
+            biddable.tasks.iter().map(|t| t.task_id).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(2),
+            1,
+            800,
+            b"Proposal".to_vec()
+        ));
+    });
+}
+
+#[test]
+fn runtime_api_task_queries_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let poster = 1;
+        let worker = 2;
+
+        let assigned = post_and_assign(poster, worker);
+        for _ in 0..3 {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(poster),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                200,
+                1000,
+                DEFAULT_CATEGORY
+            ));
+        }
+
+        let open = TaskMarket::tasks_by_status(TaskStatus::Open, 0, 10);
+        assert_eq!(
+            open.tasks.iter().map(|t| t.task_id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(open.next, None);
+        let page = TaskMarket::tasks_by_status(TaskStatus::Open, 2, 1);
+        assert_eq!(
+            page.tasks.iter().map(|t| t.task_id).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(page.next, Some(3));
+
+        assert_eq!(TaskMarket::tasks_by_poster(&poster).len(), 4);
+        let worker_tasks = TaskMarket::tasks_by_worker(&worker);
+        assert_eq!(worker_tasks.len(), 1);
+        assert_eq!(worker_tasks[0].task_id, assigned);
+        assert_eq!(worker_tasks[0].title, b"Task".to_vec());
+
+        let bids = TaskMarket::bids_for_task(assigned, 0, 10);
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].bidder, worker);
+        assert!(TaskMarket::bids_for_task(assigned, 1, 10).is_empty());
+
+        let totals = TaskMarket::escrow_totals();
+        assert_eq!(totals.locked_rewards, 700);
+        assert_eq!(totals.active_tasks, 4);
+    });
+}
+
+#[test]
+fn task_listings_scan_a_bounded_range() {
+    new_test_ext().execute_with(|| {
+        // Task IDs below 2000 were pruned long ago
+        TaskCount::<Test>::put(2 * MAX_SCANNED_TASKS);
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            100,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        let page = TaskMarket::tasks_by_status(TaskStatus::Open, 0, 10);
+        assert!(page.tasks.is_empty());
+        assert_eq!(page.next, Some(MAX_SCANNED_TASKS));
+        let page = TaskMarket::biddable_tasks(&2, MAX_SCANNED_TASKS, 10);
+        assert!(page.tasks.is_empty());
+        assert_eq!(page.next, Some(2 * MAX_SCANNED_TASKS));
+
+        let page = TaskMarket::biddable_tasks(&2, 2 * MAX_SCANNED_TASKS, 10);
+        assert_eq!(page.tasks.len(), 1);
+        assert_eq!(page.next, None);
+    });
+}
+
+#[test]
+fn biddable_tasks_applies_gating() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..3 {
+            assert_ok!(TaskMarket::post_task(
+                RuntimeOrigin::signed(1),
+                b"Task".to_vec(),
+                b"Description".to_vec(),
+                100,
+                1000,
+                DEFAULT_CATEGORY
+            ));
+        }
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(2),
+            1,
+            100,
+            b"Proposal".to_vec()
+        ));
+
+        // Own tasks are excluded, as are tasks already bid on
+        assert!(TaskMarket::biddable_tasks(&1, 0, 10).tasks.is_empty());
+        let ids: Vec<TaskId> = TaskMarket::biddable_tasks(&2, 0, 10)
+            .tasks
+            .iter()
+            .map(|t| t.task_id)
+            .collect();
+        assert_eq!(ids, vec![0, 2]);
+
+        // Nothing is biddable below the reputation threshold
+        assert_ok!(Reputation::slash_reputation(
+            RuntimeOrigin::root(),
+            3,
+            2500,
+            b"Spam".to_vec()
+        ));
+        assert!(TaskMarket::biddable_tasks(&3, 0, 10).tasks.is_empty());
+
+        // Nor for a suspended agent
+        SuspendedAgents::set(vec![4]);
+        assert!(TaskMarket::biddable_tasks(&4, 0, 10).tasks.is_empty());
+        SuspendedAgents::set(vec![]);
+        assert_eq!(TaskMarket::biddable_tasks(&4, 0, 10).tasks.len(), 3);
+    });
+}
+
+#[test]
+fn capability_requirement_gates_bids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TaskMarket::post_task(
+            RuntimeOrigin::signed(1),
+            b"Task".to_vec(),
+            b"Description".to_vec(),
+            100,
+            1000,
+            DEFAULT_CATEGORY
+        ));
+
+        // Only the poster can set a requirement, and it must be a valid ID
+        assert_noop!(
+            TaskMarket::require_capability(RuntimeOrigin::signed(2), 0, Some(b"ocr".to_vec()), 2),
+            Error::<Test>::NotPoster
+        );
+        assert_noop!(
+            TaskMarket::require_capability(RuntimeOrigin::signed(1), 0, Some(vec![]), 2),
+            Error::<Test>::InvalidCapability
+        );
+        assert_ok!(TaskMarket::require_capability(
+            RuntimeOrigin::signed(1),
+            0,
+            Some(b"ocr".to_vec()),
+            2
+        ));
+        System::assert_last_event(
+            Event::CapabilityRequired {
+                task_id: 0,
+                capability: Some(b"ocr".to_vec()),
+                min_version: 2,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TaskMarket::tasks_by_poster(&1)[0].required_capability,
+            Some((b"ocr".to_vec(), 2))
+        );
+
+        // A bidder below the required version neither sees nor bids on the task
+        DeclaredCapabilities::set(vec![(2, b"ocr".to_vec(), 1), (3, b"ocr".to_vec(), 2)]);
+        assert!(TaskMarket::biddable_tasks(&2, 0, 10).tasks.is_empty());
+        assert_noop!(
+            TaskMarket::bid_on_task(RuntimeOrigin::signed(2), 0, 100, b"Proposal".to_vec()),
+            Error::<Test>::MissingCapability
+        );
+        assert_eq!(TaskMarket::biddable_tasks(&3, 0, 10).tasks.len(), 1);
+        assert_ok!(TaskMarket::bid_on_task(
+            RuntimeOrigin::signed(3),
+            0,
+            100,
+            b"Proposal".to_vec()
+        ));
+
+        // Once bids arrive the requirement is fixed
+        assert_noop!(
+            TaskMarket::require_capability(RuntimeOrigin::signed(1), 0, None, 0),
+            Error::<Test>::TaskHasBids
+        );
+    });
+}
+
+#[test]
+fn passed_validation_approves_work() {
+    use pallet_validation::ValidatedWork;
+
+    new_test_ext().execute_with(|| {
+        let task_id = post_and_assign(1, 2);
+
+        // Only the poster can have work validated, and only once it is submitted
+        assert_eq!(TaskMarket::worker_of(task_id, &1), None);
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(2),
+            task_id,
+            b"https://proof.com".to_vec()
+        ));
+        assert_eq!(TaskMarket::worker_of(task_id, &3), None);
+        assert_eq!(TaskMarket::worker_of(task_id, &1), Some((2, 100)));
+
+        // Failed validation leaves the decision to the poster
+        TaskMarket::on_validation_concluded(task_id, false);
+        assert_eq!(
+            TaskMarket::tasks(task_id).unwrap().status,
+            TaskStatus::Completed
+        );
+
+        TaskMarket::on_validation_concluded(task_id, true);
+        assert_eq!(
+            TaskMarket::tasks(task_id).unwrap().status,
+            TaskStatus::Approved
+        );
+        assert_eq!(Balances::free_balance(2), 10100);
+        assert_eq!(Reputation::reputations(2).successful_completions, 1);
+
+        // A late outcome for an approved task is ignored
+        TaskMarket::on_validation_concluded(task_id, true);
+        assert_eq!(Balances::free_balance(2), 10100);
+    });
+}
+
+#[test]
+fn only_live_tasks_are_negotiable() {
+    use pallet_messaging::NegotiableTasks;
+
+    new_test_ext().execute_with(|| {
+        let task_id = post_and_assign(1, 2);
+        assert!(TaskMarket::is_negotiable(task_id));
+        assert!(!TaskMarket::is_negotiable(task_id + 1));
+
+        // The poster and the assigned bidder negotiate; outsiders do not
+        assert!(TaskMarket::is_negotiator(task_id, &1));
+        assert!(TaskMarket::is_negotiator(task_id, &2));
+        assert!(!TaskMarket::is_negotiator(task_id, &3));
+        assert!(!TaskMarket::is_negotiator(task_id + 1, &1));
+
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(2),
+            task_id,
+            b"https://proof.com".to_vec()
+        ));
+        assert!(TaskMarket::is_negotiable(task_id));
+
+        assert_ok!(TaskMarket::approve_work(RuntimeOrigin::signed(1), task_id));
+        assert!(!TaskMarket::is_negotiable(task_id));
+    });
+}
+
+#[test]
+fn approved_task_parties_can_review_each_other() {
+    new_test_ext().execute_with(|| {
+        let task_id = post_and_assign(1, 2);
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(1), 2, 5, b"Early".to_vec(), task_id),
+            pallet_reputation::Error::<Test>::NotTaskParticipant
+        );
+
+        assert_ok!(TaskMarket::submit_work(
+            RuntimeOrigin::signed(2),
+            task_id,
+            b"https://proof.com".to_vec()
+        ));
+        assert_ok!(TaskMarket::approve_work(RuntimeOrigin::signed(1), task_id));
+
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(1),
+            2,
+            5,
+            b"Great".to_vec(),
+            task_id
+        ));
+        assert_ok!(Reputation::submit_review(
+            RuntimeOrigin::signed(2),
+            1,
+            4,
+            b"Clear brief".to_vec(),
+            task_id
+        ));
+        assert_noop!(
+            Reputation::submit_review(RuntimeOrigin::signed(3), 2, 5, b"Friend".to_vec(), task_id),
+            pallet_reputation::Error::<Test>::NotTaskParticipant
+        );
+    });
+}
+
+#[test]
+fn operator_posting_a_task_is_charged_to_its_spending_cap() {
+    use pallet_agent_registry::CallScope;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        TaskDepositBase::set(50);
+        assert_ok!(AgentRegistry::register_agent(
+            RuntimeOrigin::signed(1),
+            b"did:claw:agent001".to_vec(),
+            b"{}".to_vec()
+        ));
+        let agent_account = AgentRegistry::agent_account(0);
+        assert_ok!(Balances::transfer_allow_death(
+            RuntimeOrigin::signed(1),
+            agent_account,
+            1000
+        ));
+        assert_ok!(AgentRegistry::add_operator(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            vec![CallScope::Pallet(3)],
+            Some(400)
+        ));
+        let post = |reward: u64| {
+            Box::new(RuntimeCall::TaskMarket(crate::Call::post_task {
+                title: b"Task".to_vec(),
+                description: b"Description".to_vec(),
+                reward,
+                deadline: 100,
+                category: DEFAULT_CATEGORY,
+            }))
+        };
+
+        // The escrowed reward and the deposit both leave the agent's free balance.
+        assert_ok!(AgentRegistry::operate(
+            RuntimeOrigin::signed(2),
+            0,
+            post(200)
+        ));
+        assert_eq!(Balances::reserved_balance(agent_account), 250);
+        assert_eq!(
+            pallet_agent_registry::Operators::<Test>::get(0, 2)
+                .unwrap()
+                .spent,
+            250
+        );
+
+        assert_noop!(
+            AgentRegistry::operate(RuntimeOrigin::signed(2), 0, post(200)),
+            pallet_agent_registry::Error::<Test>::SpendingCapExceeded
+        );
+    });
+}
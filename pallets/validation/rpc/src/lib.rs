@@ -0,0 +1,129 @@
+//! RPC interface for the Validation pallet.
+//!
+//! Exposes the `ValidationApi` runtime API as `validation_*` JSON-RPC methods.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_validation_runtime_api::{RequestId, RequestSummary, ValidatorSummary};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_validation_runtime_api::ValidationApi as ValidationRuntimeApi;
+
+#[rpc(client, server)]
+pub trait ValidationApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// A validation request with the scores posted so far, or `null`.
+    #[method(name = "validation_request")]
+    fn request_summary(
+        &self,
+        request_id: RequestId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<RequestSummary<AccountId, BlockNumber>>>;
+
+    /// Stake and pending request count of a validator, or `null`.
+    #[method(name = "validation_validator")]
+    fn validator(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<ValidatorSummary<Balance, BlockNumber>>>;
+
+    /// Pending requests awaiting a validator's response, oldest first.
+    #[method(name = "validation_pendingRequests")]
+    fn pending_requests(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RequestSummary<AccountId, BlockNumber>>>;
+
+    /// The latest validation request for a `0x`-prefixed output hash, or `null`.
+    #[method(name = "validation_outputRequest")]
+    fn output_request(
+        &self,
+        data_hash: H256,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<RequestSummary<AccountId, BlockNumber>>>;
+}
+
+/// Provides RPC methods to query validators and validation requests.
+pub struct Validation<C, Block> {
+    /// Shared reference to the client.
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Validation<C, Block> {
+    /// Creates a new instance of the Validation RPC helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(RUNTIME_ERROR, "Unable to query validation.", Some(error.to_string()))
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    ValidationApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for Validation<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: ValidationRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + Send + Sync + 'static,
+    Balance: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Send + Sync + 'static,
+{
+    fn request_summary(
+        &self,
+        request_id: RequestId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<RequestSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().request(at_hash, request_id).map_err(runtime_error)
+    }
+
+    fn validator(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<ValidatorSummary<Balance, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().validator(at_hash, account).map_err(runtime_error)
+    }
+
+    fn pending_requests(
+        &self,
+        validator: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<RequestSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .pending_requests(at_hash, validator)
+            .map_err(runtime_error)
+    }
+
+    fn output_request(
+        &self,
+        data_hash: H256,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<RequestSummary<AccountId, BlockNumber>>> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .output_request(at_hash, data_hash.0)
+            .map_err(runtime_error)
+    }
+}
@@ -0,0 +1,37 @@
+//! Runtime API definition for the Validation pallet.
+//!
+//! Lets validators find the requests awaiting them and clients follow a request
+//! to its conclusion.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_validation::{
+    DataHash, RequestId, RequestStatus, RequestSummary, ValidationSubject, ValidatorSummary,
+};
+
+sp_api::decl_runtime_apis! {
+    /// Queries over validators and validation requests.
+    pub trait ValidationApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// A validation request with the scores posted so far.
+        fn request(request_id: RequestId) -> Option<RequestSummary<AccountId, BlockNumber>>;
+
+        /// Stake and pending request count of `account`, if it is a validator.
+        fn validator(account: AccountId) -> Option<ValidatorSummary<Balance, BlockNumber>>;
+
+        /// Pending requests awaiting `validator`'s response, oldest first.
+        fn pending_requests(validator: AccountId) -> Vec<RequestSummary<AccountId, BlockNumber>>;
+
+        /// The latest validation request for an output hash (the ERC-8004 `dataHash`).
+        fn output_request(data_hash: DataHash) -> Option<RequestSummary<AccountId, BlockNumber>>;
+    }
+}
@@ -0,0 +1,867 @@
+//! # Validation Pallet
+//!
+//! Independent verification of agent work by staked validator agents.
+//!
+//! ## Overview
+//!
+//! This pallet provides functionality for:
+//! - Registering agents as validators against a reserved stake
+//! - Requesting validation of a task or of any agent output hash from a chosen set
+//!   of validators, with a quorum
+//! - Collecting validator scores (0-100) and concluding each request as passed,
+//!   failed or expired
+//! - Feeding outcomes into pallet-task-market (approval) and pallet-reputation
+//!
+//! ## Validators
+//!
+//! Any agent account in good standing (see `AgentStanding`) can `register_validator`
+//! by reserving at least `MinValidatorStake`. A validator leaves with
+//! `unregister_validator` once it has no pending requests. `SlashOrigin` can slash
+//! a validator's stake, and validators that let a request expire without
+//! responding lose `MissedResponseSlash` each. Validators whose stake has fallen
+//! below `MinValidatorStake` cannot be picked for new requests.
+//!
+//! ## Requests
+//!
+//! A request names its subject, the validators to ask and a quorum:
+//! - `ValidationSubject::Task` validates the submitted work of a task. Only the
+//!   task's poster may request it (checked through `ValidatedWork`), only while the
+//!   work awaits approval, and only once per task unless the earlier request expired.
+//!   `TaskValidations` records each task's request.
+//! - `ValidationSubject::Output` validates any output hash. The requester is the
+//!   agent whose output it is. `OutputValidations` records each hash's latest request.
+//!
+//! Validators `respond` with a score within `ResponseWindow` blocks. A request
+//! passes as soon as `quorum` validators have scored at least `PassThreshold`, and
+//! fails as soon as that can no longer happen. Once the window has closed, anyone
+//! can `expire_request` a request still pending.
+//!
+//! When a request concludes, a passed task is approved through `ValidatedWork`
+//! (releasing the escrow to the worker), and the worker's reputation moves with the
+//! validators' average score through `OnWorkValidated`. Like a review, the score is
+//! weighted by the validators' standing and the task's reward. Reputation only moves
+//! for work validated at someone else's request, so an agent cannot raise its own
+//! score by having its outputs validated.
+//!
+//! ## ERC-8004 Validation Registry
+//!
+//! This pallet is ClawChain's ERC-8004 validation registry. `validationRequest`
+//! maps to `request_validation` with `ValidationSubject::Output(dataHash)`,
+//! `validationResponse` to `respond`, and `output_request` looks up the latest
+//! request for a data hash.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `register_validator` / `unregister_validator` - Join or leave the validator set
+//! - `request_validation` - Ask validators to score a task or an output hash
+//! - `respond` - Post a validator's score for a request
+//! - `expire_request` - Close a request whose response window has passed
+//! - `slash_validator` - Slash a validator's stake (`SlashOrigin` only)
+//!
+//! ### Runtime API Helpers
+//!
+//! - `request` - A validation request with its responses
+//! - `validator` - A validator's stake and pending request count
+//! - `pending_requests` - Requests awaiting a validator's response
+//! - `output_request` - The latest request for an output hash
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+/// Work whose validation is tied to another pallet, such as tasks in
+/// pallet-task-market.
+pub trait ValidatedWork<AccountId, Balance> {
+    /// The worker whose submitted work for `task_id` awaits approval, and the task's
+    /// reward, if `requester` may ask for it to be validated.
+    fn worker_of(task_id: u64, requester: &AccountId) -> Option<(AccountId, Balance)>;
+
+    /// Called when validation of `task_id` concludes, `passed` if a quorum of
+    /// validators scored the work at least `PassThreshold`.
+    fn on_validation_concluded(task_id: u64, passed: bool);
+}
+
+impl<AccountId, Balance> ValidatedWork<AccountId, Balance> for () {
+    fn worker_of(_task_id: u64, _requester: &AccountId) -> Option<(AccountId, Balance)> {
+        None
+    }
+
+    fn on_validation_concluded(_task_id: u64, _passed: bool) {}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Currency, ReservableCurrency},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_agent_registry::AgentStanding;
+    use pallet_reputation::OnWorkValidated;
+    use sp_runtime::traits::Saturating;
+
+    /// Type alias for validation request IDs (sequential u64).
+    pub type RequestId = u64;
+
+    /// Hash of an agent output submitted for validation.
+    pub type DataHash = [u8; 32];
+
+    /// Type alias for balance (compatible with pallet-balances).
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// What a validation request is about.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        codec::DecodeWithMemTracking,
+    )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum ValidationSubject {
+        /// The submitted work of a task in pallet-task-market.
+        Task(u64),
+        /// Any agent output, by hash.
+        Output(DataHash),
+    }
+
+    /// Status of a validation request.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        codec::DecodeWithMemTracking,
+    )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum RequestStatus {
+        /// Awaiting validator responses.
+        Pending,
+        /// A quorum of validators scored the work at least `PassThreshold`.
+        Passed,
+        /// A quorum can no longer be reached.
+        Failed,
+        /// The response window closed before the request concluded.
+        Expired,
+    }
+
+    /// A registered validator.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct ValidatorInfo<T: Config> {
+        /// Stake reserved from the validator.
+        pub stake: BalanceOf<T>,
+        /// Block at which the validator registered.
+        pub registered_at: BlockNumberFor<T>,
+    }
+
+    /// A request for validators to score a piece of work.
+    #[derive(
+        CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct ValidationRequest<T: Config> {
+        /// Account that requested validation.
+        pub requester: T::AccountId,
+        /// Account whose work is validated.
+        pub worker: T::AccountId,
+        /// What is validated.
+        pub subject: ValidationSubject,
+        /// Reward of the validated task; zero for outputs.
+        pub value: BalanceOf<T>,
+        /// Validators asked to respond.
+        pub validators: BoundedVec<T::AccountId, T::MaxValidatorsPerRequest>,
+        /// Passing scores needed for the request to pass.
+        pub quorum: u32,
+        /// Block at which validation was requested.
+        pub created_at: BlockNumberFor<T>,
+        /// Current status.
+        pub status: RequestStatus,
+    }
+
+    /// Validation request view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct RequestSummary<AccountId, BlockNumber> {
+        /// The request ID.
+        pub request_id: RequestId,
+        /// Account that requested validation.
+        pub requester: AccountId,
+        /// Account whose work is validated.
+        pub worker: AccountId,
+        /// What is validated.
+        pub subject: ValidationSubject,
+        /// Validators asked to respond.
+        pub validators: Vec<AccountId>,
+        /// Passing scores needed for the request to pass.
+        pub quorum: u32,
+        /// Scores posted so far.
+        pub responses: Vec<(AccountId, u8)>,
+        /// Block at which validation was requested.
+        pub created_at: BlockNumber,
+        /// Last block at which validators may respond.
+        pub expires_at: BlockNumber,
+        /// Current status.
+        pub status: RequestStatus,
+    }
+
+    /// Validator view returned by the runtime API.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct ValidatorSummary<Balance, BlockNumber> {
+        /// Stake reserved from the validator.
+        pub stake: Balance,
+        /// Block at which the validator registered.
+        pub registered_at: BlockNumber,
+        /// Number of requests awaiting the validator's response.
+        pub pending_requests: u32,
+    }
+
+    /// Request summary type for a given runtime.
+    pub type RequestSummaryOf<T> =
+        RequestSummary<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// Validator summary type for a given runtime.
+    pub type ValidatorSummaryOf<T> = ValidatorSummary<BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching runtime event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Currency used for validator stakes.
+        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+
+        /// Minimum stake a validator must hold to be picked for a request.
+        #[pallet::constant]
+        type MinValidatorStake: Get<BalanceOf<Self>>;
+
+        /// Maximum number of validators asked in a single request.
+        #[pallet::constant]
+        type MaxValidatorsPerRequest: Get<u32>;
+
+        /// Blocks validators have to respond to a request.
+        #[pallet::constant]
+        type ResponseWindow: Get<BlockNumberFor<Self>>;
+
+        /// Lowest score (0-100) that counts towards the quorum.
+        #[pallet::constant]
+        type PassThreshold: Get<u8>;
+
+        /// Stake slashed from each validator that lets a request expire unanswered.
+        #[pallet::constant]
+        type MissedResponseSlash: Get<BalanceOf<Self>>;
+
+        /// Origin allowed to slash validators (e.g. root or governance).
+        type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Standing of agent accounts; only agents in good standing can validate.
+        type AgentStanding: AgentStanding<Self::AccountId>;
+
+        /// Receives the validators' average score for the worker.
+        type Reputation: OnWorkValidated<Self::AccountId, BalanceOf<Self>>;
+
+        /// Tasks that can be validated, approved when validation passes.
+        type ValidatedWork: ValidatedWork<Self::AccountId, BalanceOf<Self>>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    // ========== Storage ==========
+
+    /// Registered validators.
+    #[pallet::storage]
+    #[pallet::getter(fn validators)]
+    pub type Validators<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ValidatorInfo<T>, OptionQuery>;
+
+    /// Map from RequestId to ValidationRequest.
+    #[pallet::storage]
+    #[pallet::getter(fn requests)]
+    pub type Requests<T: Config> =
+        StorageMap<_, Blake2_128Concat, RequestId, ValidationRequest<T>, OptionQuery>;
+
+    /// Total number of validation requests made.
+    #[pallet::storage]
+    #[pallet::getter(fn request_count)]
+    pub type RequestCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Scores posted by validators, by request.
+    #[pallet::storage]
+    pub type Responses<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        RequestId,
+        Blake2_128Concat,
+        T::AccountId,
+        u8,
+        OptionQuery,
+    >;
+
+    /// The latest validation request of each task.
+    #[pallet::storage]
+    pub type TaskValidations<T: Config> = StorageMap<_, Twox64Concat, u64, RequestId, OptionQuery>;
+
+    /// The latest validation request of each output hash.
+    #[pallet::storage]
+    pub type OutputValidations<T: Config> =
+        StorageMap<_, Blake2_128Concat, DataHash, RequestId, OptionQuery>;
+
+    /// Pending requests awaiting each validator's response.
+    #[pallet::storage]
+    pub type PendingRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        RequestId,
+        (),
+        OptionQuery,
+    >;
+
+    // ========== Events ==========
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An agent registered as a validator.
+        ValidatorRegistered {
+            validator: T::AccountId,
+            stake: BalanceOf<T>,
+        },
+        /// A validator left and its stake was released.
+        ValidatorUnregistered { validator: T::AccountId },
+        /// A validator's stake was slashed.
+        ValidatorSlashed {
+            validator: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Validation was requested.
+        ValidationRequested {
+            request_id: RequestId,
+            requester: T::AccountId,
+            worker: T::AccountId,
+            subject: ValidationSubject,
+        },
+        /// A validator posted its score.
+        ValidationResponded {
+            request_id: RequestId,
+            validator: T::AccountId,
+            score: u8,
+        },
+        /// A request concluded. `average_score` is over the scores posted, if any.
+        ValidationConcluded {
+            request_id: RequestId,
+            status: RequestStatus,
+            average_score: Option<u8>,
+        },
+    }
+
+    // ========== Errors ==========
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The stake is below `MinValidatorStake`.
+        StakeTooLow,
+        /// The account is already a validator.
+        AlreadyValidator,
+        /// The account is not a validator.
+        NotValidator,
+        /// The account is not a registered agent.
+        NotAnAgent,
+        /// The agent is suspended or inactive.
+        AgentNotInGoodStanding,
+        /// The validator still has requests awaiting its response.
+        HasPendingRequests,
+        /// Cannot reserve the stake.
+        InsufficientBalance,
+        /// The task has no work awaiting approval, or the caller is not its poster.
+        SubjectNotValidatable,
+        /// The task already has a pending or concluded validation request.
+        TaskAlreadyValidated,
+        /// The quorum must be between 1 and the number of validators.
+        InvalidQuorum,
+        /// The same validator is listed twice.
+        DuplicateValidator,
+        /// A listed validator is not registered, under-staked or not in good standing.
+        ValidatorNotEligible,
+        /// A listed validator is the requester or the worker.
+        ConflictOfInterest,
+        /// The request does not exist.
+        RequestNotFound,
+        /// The caller is not a validator of this request.
+        NotRequestValidator,
+        /// The validator has already responded.
+        AlreadyResponded,
+        /// The request has already concluded.
+        RequestConcluded,
+        /// The response window has closed.
+        ResponseWindowClosed,
+        /// The response window is still open.
+        ResponseWindowOpen,
+        /// Scores range from 0 to 100.
+        InvalidScore,
+    }
+
+    // ========== Extrinsics ==========
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register the calling agent as a validator, reserving `stake`.
+        ///
+        /// # Arguments
+        /// * `stake` - Amount to reserve, at least `MinValidatorStake`
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(3, 2))]
+        pub fn register_validator(origin: OriginFor<T>, stake: BalanceOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                stake >= T::MinValidatorStake::get(),
+                Error::<T>::StakeTooLow
+            );
+            ensure!(
+                !Validators::<T>::contains_key(&who),
+                Error::<T>::AlreadyValidator
+            );
+            ensure!(T::AgentStanding::is_agent(&who), Error::<T>::NotAnAgent);
+            ensure!(
+                Self::in_good_standing(&who),
+                Error::<T>::AgentNotInGoodStanding
+            );
+
+            T::Currency::reserve(&who, stake).map_err(|_| Error::<T>::InsufficientBalance)?;
+            Validators::<T>::insert(
+                &who,
+                ValidatorInfo {
+                    stake,
+                    registered_at: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::ValidatorRegistered {
+                validator: who,
+                stake,
+            });
+
+            Ok(())
+        }
+
+        /// Leave the validator set and release the remaining stake.
+        ///
+        /// Only possible once no request awaits the validator's response.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        pub fn unregister_validator(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let info = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
+            ensure!(
+                PendingRequests::<T>::iter_prefix(&who).next().is_none(),
+                Error::<T>::HasPendingRequests
+            );
+
+            T::Currency::unreserve(&who, info.stake);
+            Validators::<T>::remove(&who);
+
+            Self::deposit_event(Event::ValidatorUnregistered { validator: who });
+
+            Ok(())
+        }
+
+        /// Ask `validators` to score a task's submitted work or an output hash.
+        ///
+        /// A task can be validated once; only an expired request lets the poster ask
+        /// again.
+        ///
+        /// # Arguments
+        /// * `subject` - The task or output to validate
+        /// * `validators` - Registered validators to ask, other than the requester and worker
+        /// * `quorum` - Passing scores needed for the request to pass
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(5, 3)
+                + T::DbWeight::get().reads_writes(
+                    T::MaxValidatorsPerRequest::get().into(),
+                    T::MaxValidatorsPerRequest::get().into(),
+                )
+        )]
+        pub fn request_validation(
+            origin: OriginFor<T>,
+            subject: ValidationSubject,
+            validators: BoundedVec<T::AccountId, T::MaxValidatorsPerRequest>,
+            quorum: u32,
+        ) -> DispatchResult {
+            let requester = ensure_signed(origin)?;
+
+            let (worker, value) = match subject {
+                ValidationSubject::Task(task_id) => {
+                    let earlier = TaskValidations::<T>::get(task_id).and_then(Requests::<T>::get);
+                    ensure!(
+                        earlier.is_none_or(|request| request.status == RequestStatus::Expired),
+                        Error::<T>::TaskAlreadyValidated
+                    );
+                    T::ValidatedWork::worker_of(task_id, &requester)
+                        .ok_or(Error::<T>::SubjectNotValidatable)?
+                }
+                ValidationSubject::Output(_) => (requester.clone(), Zero::zero()),
+            };
+            ensure!(
+                quorum >= 1 && quorum as usize <= validators.len(),
+                Error::<T>::InvalidQuorum
+            );
+            for (i, validator) in validators.iter().enumerate() {
+                ensure!(
+                    !validators[..i].contains(validator),
+                    Error::<T>::DuplicateValidator
+                );
+                ensure!(
+                    *validator != requester && *validator != worker,
+                    Error::<T>::ConflictOfInterest
+                );
+                ensure!(
+                    Validators::<T>::get(validator)
+                        .is_some_and(|info| info.stake >= T::MinValidatorStake::get())
+                        && Self::in_good_standing(validator),
+                    Error::<T>::ValidatorNotEligible
+                );
+            }
+
+            let request_id = RequestCount::<T>::get();
+            RequestCount::<T>::put(request_id.saturating_add(1));
+            for validator in validators.iter() {
+                PendingRequests::<T>::insert(validator, request_id, ());
+            }
+            match subject {
+                ValidationSubject::Task(task_id) => {
+                    TaskValidations::<T>::insert(task_id, request_id)
+                }
+                ValidationSubject::Output(data_hash) => {
+                    OutputValidations::<T>::insert(data_hash, request_id)
+                }
+            }
+            Requests::<T>::insert(
+                request_id,
+                ValidationRequest {
+                    requester: requester.clone(),
+                    worker: worker.clone(),
+                    subject,
+                    value,
+                    validators,
+                    quorum,
+                    created_at: <frame_system::Pallet<T>>::block_number(),
+                    status: RequestStatus::Pending,
+                },
+            );
+
+            Self::deposit_event(Event::ValidationRequested {
+                request_id,
+                requester,
+                worker,
+                subject,
+            });
+
+            Ok(())
+        }
+
+        /// Post the caller's score for a request it was asked to validate.
+        ///
+        /// The request concludes as soon as its outcome is settled.
+        ///
+        /// # Arguments
+        /// * `request_id` - The request to respond to
+        /// * `score` - 0 (invalid) to 100 (fully valid)
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(3, 3)
+                + T::DbWeight::get().reads_writes(
+                    T::MaxValidatorsPerRequest::get().into(),
+                    T::MaxValidatorsPerRequest::get().into(),
+                )
+        )]
+        pub fn respond(origin: OriginFor<T>, request_id: RequestId, score: u8) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            ensure!(score <= 100, Error::<T>::InvalidScore);
+
+            let mut request = Requests::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+            ensure!(
+                request.status == RequestStatus::Pending,
+                Error::<T>::RequestConcluded
+            );
+            ensure!(
+                request.validators.contains(&validator),
+                Error::<T>::NotRequestValidator
+            );
+            ensure!(
+                !Responses::<T>::contains_key(request_id, &validator),
+                Error::<T>::AlreadyResponded
+            );
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= Self::expiry(&request),
+                Error::<T>::ResponseWindowClosed
+            );
+
+            Responses::<T>::insert(request_id, &validator, score);
+            PendingRequests::<T>::remove(&validator, request_id);
+            Self::deposit_event(Event::ValidationResponded {
+                request_id,
+                validator,
+                score,
+            });
+
+            let threshold = T::PassThreshold::get();
+            let (mut passing, mut responded) = (0u32, 0u32);
+            for (_, score) in Responses::<T>::iter_prefix(request_id) {
+                responded = responded.saturating_add(1);
+                if score >= threshold {
+                    passing = passing.saturating_add(1);
+                }
+            }
+            let outstanding = (request.validators.len() as u32).saturating_sub(responded);
+
+            if passing >= request.quorum {
+                Self::conclude(request_id, &mut request, RequestStatus::Passed);
+            } else if passing.saturating_add(outstanding) < request.quorum {
+                Self::conclude(request_id, &mut request, RequestStatus::Failed);
+            }
+
+            Ok(())
+        }
+
+        /// Close a pending request whose response window has passed.
+        ///
+        /// Anyone can call this. Validators that did not respond are slashed
+        /// `MissedResponseSlash`.
+        #[pallet::call_index(4)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                + T::DbWeight::get().reads_writes(1, 1)
+                + T::DbWeight::get().reads_writes(
+                    3u64.saturating_mul(T::MaxValidatorsPerRequest::get().into()),
+                    3u64.saturating_mul(T::MaxValidatorsPerRequest::get().into()),
+                )
+        )]
+        pub fn expire_request(origin: OriginFor<T>, request_id: RequestId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut request = Requests::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+            ensure!(
+                request.status == RequestStatus::Pending,
+                Error::<T>::RequestConcluded
+            );
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() > Self::expiry(&request),
+                Error::<T>::ResponseWindowOpen
+            );
+
+            for validator in request.validators.iter() {
+                if !Responses::<T>::contains_key(request_id, validator) {
+                    Self::do_slash(validator, T::MissedResponseSlash::get());
+                }
+            }
+            Self::conclude(request_id, &mut request, RequestStatus::Expired);
+
+            Ok(())
+        }
+
+        /// Slash a validator's stake (`SlashOrigin` only).
+        ///
+        /// # Arguments
+        /// * `validator` - The validator to slash
+        /// * `amount` - Amount to slash, capped at the remaining stake
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+        pub fn slash_validator(
+            origin: OriginFor<T>,
+            validator: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::SlashOrigin::ensure_origin(origin)?;
+            ensure!(
+                Validators::<T>::contains_key(&validator),
+                Error::<T>::NotValidator
+            );
+            Self::do_slash(&validator, amount);
+            Ok(())
+        }
+    }
+
+    // ========== Internal Functions ==========
+
+    impl<T: Config> Pallet<T> {
+        /// Whether `account` is neither a suspended nor an inactive agent.
+        fn in_good_standing(account: &T::AccountId) -> bool {
+            !T::AgentStanding::is_suspended_agent(account)
+                && !T::AgentStanding::is_inactive_agent(account)
+        }
+
+        /// Last block at which validators of `request` may respond.
+        fn expiry(request: &ValidationRequest<T>) -> BlockNumberFor<T> {
+            request.created_at.saturating_add(T::ResponseWindow::get())
+        }
+
+        /// Slash up to `amount` from a validator's reserved stake.
+        fn do_slash(validator: &T::AccountId, amount: BalanceOf<T>) {
+            Validators::<T>::mutate(validator, |maybe_info| {
+                let Some(info) = maybe_info else { return };
+                let amount = amount.min(info.stake);
+                if amount.is_zero() {
+                    return;
+                }
+                let _ = T::Currency::slash_reserved(validator, amount);
+                info.stake = info.stake.saturating_sub(amount);
+                Self::deposit_event(Event::ValidatorSlashed {
+                    validator: validator.clone(),
+                    amount,
+                });
+            });
+        }
+
+        /// Settle `request` with `status`: release outstanding validators, notify
+        /// the task market and reputation, and store the outcome.
+        fn conclude(
+            request_id: RequestId,
+            request: &mut ValidationRequest<T>,
+            status: RequestStatus,
+        ) {
+            for validator in request.validators.iter() {
+                PendingRequests::<T>::remove(validator, request_id);
+            }
+
+            let scores: Vec<u32> = Responses::<T>::iter_prefix_values(request_id)
+                .map(u32::from)
+                .collect();
+            let average_score = (!scores.is_empty())
+                .then(|| (scores.iter().sum::<u32>() / scores.len() as u32) as u8);
+
+            if status != RequestStatus::Expired {
+                if let ValidationSubject::Task(task_id) = request.subject {
+                    T::ValidatedWork::on_validation_concluded(
+                        task_id,
+                        status == RequestStatus::Passed,
+                    );
+                }
+                if let Some(score) = average_score.filter(|_| request.requester != request.worker) {
+                    T::Reputation::on_work_validated(
+                        &request.worker,
+                        &request.validators,
+                        request.value,
+                        score,
+                    );
+                }
+            }
+
+            request.status = status;
+            Requests::<T>::insert(request_id, request.clone());
+
+            Self::deposit_event(Event::ValidationConcluded {
+                request_id,
+                status,
+                average_score,
+            });
+        }
+    }
+
+    // ========== Runtime API Helpers ==========
+
+    impl<T: Config> Pallet<T> {
+        /// A validation request with the scores posted so far.
+        pub fn request(request_id: RequestId) -> Option<RequestSummaryOf<T>> {
+            let request = Requests::<T>::get(request_id)?;
+            Some(RequestSummary {
+                request_id,
+                expires_at: Self::expiry(&request),
+                requester: request.requester,
+                worker: request.worker,
+                subject: request.subject,
+                responses: request
+                    .validators
+                    .iter()
+                    .filter_map(|validator| {
+                        Responses::<T>::get(request_id, validator)
+                            .map(|score| (validator.clone(), score))
+                    })
+                    .collect(),
+                validators: request.validators.into_inner(),
+                quorum: request.quorum,
+                created_at: request.created_at,
+                status: request.status,
+            })
+        }
+
+        /// A validator's stake and number of pending requests.
+        pub fn validator(account: &T::AccountId) -> Option<ValidatorSummaryOf<T>> {
+            let info = Validators::<T>::get(account)?;
+            Some(ValidatorSummary {
+                stake: info.stake,
+                registered_at: info.registered_at,
+                pending_requests: PendingRequests::<T>::iter_key_prefix(account).count() as u32,
+            })
+        }
+
+        /// Pending requests awaiting `validator`'s response, oldest first.
+        pub fn pending_requests(validator: &T::AccountId) -> Vec<RequestSummaryOf<T>> {
+            let mut request_ids: Vec<RequestId> =
+                PendingRequests::<T>::iter_key_prefix(validator).collect();
+            request_ids.sort_unstable();
+            request_ids.into_iter().filter_map(Self::request).collect()
+        }
+
+        /// The latest validation request for an output hash.
+        pub fn output_request(data_hash: DataHash) -> Option<RequestSummaryOf<T>> {
+            OutputValidations::<T>::get(data_hash).and_then(Self::request)
+        }
+    }
+
+    // ========== Weight Info Trait ==========
+
+    /// Weight information for the pallet's extrinsics.
+    pub trait WeightInfo {
+        fn register_validator() -> Weight;
+        fn unregister_validator() -> Weight;
+        fn request_validation() -> Weight;
+        fn respond() -> Weight;
+        fn expire_request() -> Weight;
+        fn slash_validator() -> Weight;
+    }
+
+    /// Default weights for testing.
+    impl WeightInfo for () {
+        fn register_validator() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn unregister_validator() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn request_validation() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn respond() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn expire_request() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+        fn slash_validator() -> Weight {
+            Weight::from_parts(10_000, 0)
+        }
+    }
+}
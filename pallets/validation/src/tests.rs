@@ -0,0 +1,500 @@
+use crate::{self as pallet_validation, *};
+use frame_support::{assert_noop, assert_ok, parameter_types, BoundedVec};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Validation: pallet_validation,
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+    type RuntimeTask = ();
+    type ExtensionsWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type DoneSlashHandler = ();
+}
+
+parameter_types! {
+    pub const MinValidatorStake: u64 = 1000;
+    pub const MaxValidatorsPerRequest: u32 = 5;
+    pub const ResponseWindow: u64 = 10;
+    pub const PassThreshold: u8 = 80;
+    pub const MissedResponseSlash: u64 = 200;
+    /// Accounts [`MockAgentStanding`] reports as suspended agents.
+    pub static SuspendedAgents: Vec<u64> = vec![];
+    /// Task 0, rewarded with 100, has work by account 5 awaiting approval by poster 1.
+    pub static TaskWorker: Option<u64> = Some(5);
+    /// Outcomes passed to [`MockWork::on_validation_concluded`].
+    pub static ConcludedTasks: Vec<(u64, bool)> = vec![];
+    /// (worker, value, score) passed to [`MockReputation::on_work_validated`].
+    pub static ValidatedScores: Vec<(u64, u64, u8)> = vec![];
+}
+
+pub struct MockAgentStanding;
+
+impl pallet_agent_registry::AgentStanding<u64> for MockAgentStanding {
+    fn is_agent(account: &u64) -> bool {
+        *account != 99
+    }
+
+    fn is_inactive_agent(_account: &u64) -> bool {
+        false
+    }
+
+    fn is_suspended_agent(account: &u64) -> bool {
+        SuspendedAgents::get().contains(account)
+    }
+
+    fn has_capability(_account: &u64, _capability: &[u8], _min_version: u32) -> bool {
+        true
+    }
+}
+
+pub struct MockWork;
+
+impl ValidatedWork<u64, u64> for MockWork {
+    fn worker_of(task_id: u64, requester: &u64) -> Option<(u64, u64)> {
+        TaskWorker::get()
+            .filter(|_| task_id == 0 && *requester == 1)
+            .map(|worker| (worker, 100))
+    }
+
+    fn on_validation_concluded(task_id: u64, passed: bool) {
+        ConcludedTasks::mutate(|tasks| tasks.push((task_id, passed)));
+    }
+}
+
+pub struct MockReputation;
+
+impl pallet_reputation::OnWorkValidated<u64, u64> for MockReputation {
+    fn on_work_validated(worker: &u64, _validators: &[u64], value: u64, score: u8) {
+        ValidatedScores::mutate(|scores| scores.push((*worker, value, score)));
+    }
+}
+
+impl pallet_validation::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type MinValidatorStake = MinValidatorStake;
+    type MaxValidatorsPerRequest = MaxValidatorsPerRequest;
+    type ResponseWindow = ResponseWindow;
+    type PassThreshold = PassThreshold;
+    type MissedResponseSlash = MissedResponseSlash;
+    type SlashOrigin = EnsureRoot<u64>;
+    type AgentStanding = MockAgentStanding;
+    type Reputation = MockReputation;
+    type ValidatedWork = MockWork;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=5).map(|account| (account, 10000)).collect(),
+        dev_accounts: Default::default(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Registers accounts 2, 3 and 4 as validators with the minimum stake.
+fn register_validators() {
+    for validator in [2, 3, 4] {
+        assert_ok!(Validation::register_validator(
+            RuntimeOrigin::signed(validator),
+            1000
+        ));
+    }
+}
+
+fn validators(accounts: &[u64]) -> BoundedVec<u64, MaxValidatorsPerRequest> {
+    accounts.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn register_and_unregister_validator_works() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Validation::register_validator(RuntimeOrigin::signed(2), 999),
+            Error::<Test>::StakeTooLow
+        );
+        assert_noop!(
+            Validation::register_validator(RuntimeOrigin::signed(99), 1000),
+            Error::<Test>::NotAnAgent
+        );
+        SuspendedAgents::set(vec![3]);
+        assert_noop!(
+            Validation::register_validator(RuntimeOrigin::signed(3), 1000),
+            Error::<Test>::AgentNotInGoodStanding
+        );
+
+        assert_ok!(Validation::register_validator(
+            RuntimeOrigin::signed(2),
+            1500
+        ));
+        assert_eq!(Balances::reserved_balance(2), 1500);
+        assert_eq!(Validation::validators(2).unwrap().stake, 1500);
+        assert_noop!(
+            Validation::register_validator(RuntimeOrigin::signed(2), 1000),
+            Error::<Test>::AlreadyValidator
+        );
+
+        assert_ok!(Validation::unregister_validator(RuntimeOrigin::signed(2)));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert!(Validation::validators(2).is_none());
+        assert_noop!(
+            Validation::unregister_validator(RuntimeOrigin::signed(2)),
+            Error::<Test>::NotValidator
+        );
+    });
+}
+
+#[test]
+fn request_validation_checks_validators_and_subject() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        let task = ValidationSubject::Task(0);
+
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(2), task, validators(&[3]), 1),
+            Error::<Test>::SubjectNotValidatable
+        );
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(1), task, validators(&[2]), 2),
+            Error::<Test>::InvalidQuorum
+        );
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(1), task, validators(&[2, 2]), 1),
+            Error::<Test>::DuplicateValidator
+        );
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(1), task, validators(&[5]), 1),
+            Error::<Test>::ConflictOfInterest
+        );
+        assert_noop!(
+            Validation::request_validation(
+                RuntimeOrigin::signed(2),
+                ValidationSubject::Output([7u8; 32]),
+                validators(&[2]),
+                1
+            ),
+            Error::<Test>::ConflictOfInterest
+        );
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(1), task, validators(&[1]), 1),
+            Error::<Test>::ConflictOfInterest
+        );
+        SuspendedAgents::set(vec![4]);
+        assert_noop!(
+            Validation::request_validation(RuntimeOrigin::signed(1), task, validators(&[2, 4]), 1),
+            Error::<Test>::ValidatorNotEligible
+        );
+        SuspendedAgents::set(vec![]);
+
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            task,
+            validators(&[2, 3, 4]),
+            2
+        ));
+        let request = Validation::requests(0).unwrap();
+        assert_eq!(request.worker, 5);
+        assert_eq!(request.value, 100);
+        assert_eq!(request.status, RequestStatus::Pending);
+        assert_eq!(Validation::pending_requests(&3).len(), 1);
+
+        // Validators cannot leave while a request awaits them
+        assert_noop!(
+            Validation::unregister_validator(RuntimeOrigin::signed(3)),
+            Error::<Test>::HasPendingRequests
+        );
+    });
+}
+
+#[test]
+fn quorum_of_passing_scores_approves_task() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            ValidationSubject::Task(0),
+            validators(&[2, 3, 4]),
+            2
+        ));
+
+        assert_noop!(
+            Validation::respond(RuntimeOrigin::signed(2), 0, 101),
+            Error::<Test>::InvalidScore
+        );
+        assert_noop!(
+            Validation::respond(RuntimeOrigin::signed(5), 0, 90),
+            Error::<Test>::NotRequestValidator
+        );
+
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(2), 0, 90));
+        assert_noop!(
+            Validation::respond(RuntimeOrigin::signed(2), 0, 90),
+            Error::<Test>::AlreadyResponded
+        );
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Pending
+        );
+
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(3), 0, 80));
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Passed
+        );
+        assert_eq!(ConcludedTasks::get(), vec![(0, true)]);
+        assert_eq!(ValidatedScores::get(), vec![(5, 100, 85)]);
+        System::assert_last_event(RuntimeEvent::Validation(Event::ValidationConcluded {
+            request_id: 0,
+            status: RequestStatus::Passed,
+            average_score: Some(85),
+        }));
+
+        // The remaining validator is released and can no longer respond
+        assert!(Validation::pending_requests(&4).is_empty());
+        assert_noop!(
+            Validation::respond(RuntimeOrigin::signed(4), 0, 10),
+            Error::<Test>::RequestConcluded
+        );
+    });
+}
+
+#[test]
+fn request_fails_once_quorum_is_unreachable() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            ValidationSubject::Task(0),
+            validators(&[2, 3, 4]),
+            2
+        ));
+
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(2), 0, 20));
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Pending
+        );
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(3), 0, 79));
+
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Failed
+        );
+        assert_eq!(ConcludedTasks::get(), vec![(0, false)]);
+        assert_eq!(ValidatedScores::get(), vec![(5, 100, 49)]);
+
+        // The poster cannot shop for another verdict on the same task
+        assert_noop!(
+            Validation::request_validation(
+                RuntimeOrigin::signed(1),
+                ValidationSubject::Task(0),
+                validators(&[4]),
+                1
+            ),
+            Error::<Test>::TaskAlreadyValidated
+        );
+    });
+}
+
+#[test]
+fn self_requested_output_validation_leaves_reputation_alone() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            ValidationSubject::Output([7u8; 32]),
+            validators(&[2]),
+            1
+        ));
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(2), 0, 100));
+
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Passed
+        );
+        assert!(ConcludedTasks::get().is_empty());
+        assert!(ValidatedScores::get().is_empty());
+    });
+}
+
+#[test]
+fn output_request_finds_latest_request_for_hash() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        for _ in 0..2 {
+            assert_ok!(Validation::request_validation(
+                RuntimeOrigin::signed(1),
+                ValidationSubject::Output([7u8; 32]),
+                validators(&[2]),
+                1
+            ));
+        }
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(2), 1, 90));
+
+        let request = Validation::output_request([7u8; 32]).unwrap();
+        assert_eq!(request.request_id, 1);
+        assert_eq!(request.responses, vec![(2, 90)]);
+        assert_eq!(request.status, RequestStatus::Passed);
+        assert!(Validation::output_request([8u8; 32]).is_none());
+    });
+}
+
+#[test]
+fn expire_request_slashes_silent_validators() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            ValidationSubject::Task(0),
+            validators(&[2, 3, 4]),
+            3
+        ));
+        assert_ok!(Validation::respond(RuntimeOrigin::signed(2), 0, 90));
+
+        System::set_block_number(11);
+        assert_noop!(
+            Validation::expire_request(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::ResponseWindowOpen
+        );
+
+        System::set_block_number(12);
+        assert_noop!(
+            Validation::respond(RuntimeOrigin::signed(3), 0, 90),
+            Error::<Test>::ResponseWindowClosed
+        );
+        assert_ok!(Validation::expire_request(RuntimeOrigin::signed(1), 0));
+
+        assert_eq!(
+            Validation::requests(0).unwrap().status,
+            RequestStatus::Expired
+        );
+        assert_eq!(Validation::validators(2).unwrap().stake, 1000);
+        assert_eq!(Validation::validators(3).unwrap().stake, 800);
+        assert_eq!(Balances::reserved_balance(4), 800);
+        assert!(ConcludedTasks::get().is_empty());
+        assert!(ValidatedScores::get().is_empty());
+
+        // Under-staked validators can no longer be picked
+        assert_noop!(
+            Validation::request_validation(
+                RuntimeOrigin::signed(1),
+                ValidationSubject::Task(0),
+                validators(&[3]),
+                1
+            ),
+            Error::<Test>::ValidatorNotEligible
+        );
+        assert_ok!(Validation::unregister_validator(RuntimeOrigin::signed(3)));
+        assert_eq!(Balances::free_balance(3), 9800);
+
+        // An expired request lets the poster ask again
+        assert_ok!(Validation::request_validation(
+            RuntimeOrigin::signed(1),
+            ValidationSubject::Task(0),
+            validators(&[2]),
+            1
+        ));
+        assert_eq!(TaskValidations::<Test>::get(0), Some(1));
+    });
+}
+
+#[test]
+fn slash_validator_requires_slash_origin() {
+    new_test_ext().execute_with(|| {
+        register_validators();
+
+        assert_noop!(
+            Validation::slash_validator(RuntimeOrigin::signed(1), 2, 500),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Validation::slash_validator(RuntimeOrigin::root(), 5, 500),
+            Error::<Test>::NotValidator
+        );
+
+        assert_ok!(Validation::slash_validator(RuntimeOrigin::root(), 2, 5000));
+        assert_eq!(Validation::validators(2).unwrap().stake, 0);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 9000);
+        System::assert_last_event(RuntimeEvent::Validation(Event::ValidatorSlashed {
+            validator: 2,
+            amount: 1000,
+        }));
+    });
+}
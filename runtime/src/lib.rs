@@ -42,7 +42,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, curve::PiecewiseLinear, generic, impl_opaque_keys,
     traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, OpaqueKeys, Verify, AccountIdConversion},
-    transaction_validity::{TransactionSource, TransactionValidity},
+    transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, MultiSignature, Permill,
 };
 use sp_staking::SessionIndex;
@@ -99,10 +99,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("clawchain"),
     impl_name: create_runtime_str!("clawchain-node"),
     authoring_version: 1,
-    spec_version: 100,
+    spec_version: 101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     system_version: 1,
 };
 
@@ -449,13 +449,56 @@ impl pallet_sudo::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const AgentDepositBase: Balance = 10 * UNITS; // 10 CLAW per agent
+    pub const AgentDepositPerByte: Balance = UNITS / 100; // 0.01 CLAW per byte
+    pub AllowedDidMethods: Vec<Vec<u8>> = vec![b"claw".to_vec()];
+    pub const DidReleaseCooldown: BlockNumber = 30 * DAYS;
+    pub const PurgeGracePeriod: BlockNumber = 90 * DAYS;
+    pub const AgentRegistryPalletId: PalletId = PalletId(*b"clw/agnt");
+    pub const OperatorSpendingPeriod: BlockNumber = DAYS;
+    pub const InactivityPeriod: BlockNumber = DAYS;
+    pub const MinHeartbeatInterval: BlockNumber = HOURS;
+    pub const HeartbeatUnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
+}
+
 /// Configure the agent registry pallet.
 impl pallet_agent_registry::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type RuntimeCall = RuntimeCall;
+    type PalletId = AgentRegistryPalletId;
+    type MaxOperatorsPerAgent = ConstU32<8>;
+    type MaxOperatorScopes = ConstU32<16>;
+    type OperatorSpendingPeriod = OperatorSpendingPeriod;
+    type InactivityPeriod = InactivityPeriod;
+    type MinHeartbeatInterval = MinHeartbeatInterval;
+    type HeartbeatUnsignedPriority = HeartbeatUnsignedPriority;
+    type HeartbeatSignature = Signature;
+    type MaxInactivitySweepPerBlock = ConstU32<50>;
+    type MaxCapabilityIdLength = ConstU32<64>;
+    type MaxCapabilitiesPerAgent = ConstU32<32>;
+    type Currency = Balances;
+    type AgentDepositBase = AgentDepositBase;
+    type AgentDepositPerByte = AgentDepositPerByte;
     type MaxDidLength = ConstU32<256>;
     type MaxMetadataLength = ConstU32<4096>;
     type MaxAgentsPerOwner = ConstU32<100>;
+    type AllowedDidMethods = AllowedDidMethods;
+    type DidReleaseCooldown = DidReleaseCooldown;
+    type PurgeGracePeriod = PurgeGracePeriod;
+    type MaxVerificationMethods = ConstU32<16>;
+    type MaxServices = ConstU32<16>;
+    type MaxFragmentLength = ConstU32<64>;
+    type MaxServiceEndpointLength = ConstU32<512>;
+    type Reputation = Reputation;
+    type ReputationOrigin = frame_system::EnsureRoot<AccountId>;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxSuspensionReasonLength = ConstU32<256>;
+    type MaxDomainLength = ConstU32<253>;
+    type IssuerOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxIssuerNameLength = ConstU32<64>;
+    type MaxAttestationsPerAgent = ConstU32<64>;
 }
 
 /// Configure the CLAW token pallet.
@@ -471,7 +514,13 @@ parameter_types! {
     // Reputation parameters
     pub const MaxCommentLength: u32 = 256;
     pub const InitialReputation: u32 = 5000;
-    pub const MaxReputationDelta: u32 = 500;
+    pub const ReputationPriorWeight: u32 = 5;
+    pub const RatingShare: u8 = 60; // 60% ratings, 40% completion record
+    pub const ReviewValueCap: Balance = 1_000 * UNITS; // Full-weight reviews from 1,000 CLAW tasks
+    pub const TrustUpdatePeriod: BlockNumber = DAYS;
+    pub const MaxTrustStepsPerBlock: u32 = 500; // ~200ms of RocksDb weight, a tenth of a block
+    pub const DecayGracePeriod: BlockNumber = 30 * DAYS; // Dormant for a month before decaying
+    pub const DecayHalfLife: BlockNumber = 90 * DAYS;
     pub const MaxHistoryLength: u32 = 100;
     
     // Task Market parameters
@@ -482,6 +531,12 @@ parameter_types! {
     pub const MaxBidsPerTask: u32 = 20;
     pub const MinTaskReward: Balance = 100 * UNITS; // 100 CLAW minimum
     pub const MaxActiveTasksPerAccount: u32 = 50;
+    pub const MaxActiveTasksPerWorker: u32 = 50;
+    pub const TaskRetentionPeriod: BlockNumber = 30 * DAYS;
+    pub const TaskDepositBase: Balance = UNITS; // 1 CLAW per task
+    pub const TaskDepositPerByte: Balance = UNITS / 100; // 0.01 CLAW per byte
+    pub const MaxPrunedTasksPerBlock: u32 = 20;
+    pub const MinBidderReputation: u32 = 3000; // 30%
 }
 
 impl pallet_reputation::Config for Runtime {
@@ -490,8 +545,16 @@ impl pallet_reputation::Config for Runtime {
     type Currency = Balances;
     type MaxCommentLength = MaxCommentLength;
     type InitialReputation = InitialReputation;
-    type MaxReputationDelta = MaxReputationDelta;
+    type PriorWeight = ReputationPriorWeight;
+    type RatingShare = RatingShare;
+    type ReviewValueCap = ReviewValueCap;
+    type TrustUpdatePeriod = TrustUpdatePeriod;
+    type MaxTrustStepsPerBlock = MaxTrustStepsPerBlock;
+    type DecayGracePeriod = DecayGracePeriod;
+    type DecayHalfLife = DecayHalfLife;
     type MaxHistoryLength = MaxHistoryLength;
+    type OnReputationChanged = ();
+    type ReviewableTasks = TaskMarket;
 }
 
 impl pallet_task_market::Config for Runtime {
@@ -506,6 +569,58 @@ impl pallet_task_market::Config for Runtime {
     type MaxBidsPerTask = MaxBidsPerTask;
     type MinTaskReward = MinTaskReward;
     type MaxActiveTasksPerAccount = MaxActiveTasksPerAccount;
+    type MaxActiveTasksPerWorker = MaxActiveTasksPerWorker;
+    type TaskRetentionPeriod = TaskRetentionPeriod;
+    type TaskDepositBase = TaskDepositBase;
+    type TaskDepositPerByte = TaskDepositPerByte;
+    type MaxPrunedTasksPerBlock = MaxPrunedTasksPerBlock;
+    type MinBidderReputation = MinBidderReputation;
+    type AgentStanding = AgentRegistry;
+    type MaxCapabilityIdLength = <Runtime as pallet_agent_registry::Config>::MaxCapabilityIdLength;
+}
+
+parameter_types! {
+    // Validation parameters
+    pub const MinWorkValidatorStake: Balance = 1_000 * UNITS; // 1,000 CLAW
+    pub const MaxValidatorsPerRequest: u32 = 7;
+    pub const WorkValidationResponseWindow: BlockNumber = DAYS;
+    pub const WorkValidationPassThreshold: u8 = 80;
+    pub const MissedResponseSlash: Balance = 10 * UNITS; // 10 CLAW per missed response
+}
+
+impl pallet_validation::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type MinValidatorStake = MinWorkValidatorStake;
+    type MaxValidatorsPerRequest = MaxValidatorsPerRequest;
+    type ResponseWindow = WorkValidationResponseWindow;
+    type PassThreshold = WorkValidationPassThreshold;
+    type MissedResponseSlash = MissedResponseSlash;
+    type SlashOrigin = frame_system::EnsureRoot<AccountId>;
+    type AgentStanding = AgentRegistry;
+    type Reputation = Reputation;
+    type ValidatedWork = TaskMarket;
+}
+
+parameter_types! {
+    // Messaging parameters
+    pub const MaxMessageContentLength: u32 = 1024;
+    pub const MaxInboxSize: u32 = 100;
+    pub const MessageDepositBase: Balance = UNITS / 10; // 0.1 CLAW per message
+    pub const MessageDepositPerByte: Balance = UNITS / 1_000; // 0.001 CLAW per byte
+}
+
+impl pallet_messaging::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type Agents = AgentRegistry;
+    type Tasks = TaskMarket;
+    type MaxContentLength = MaxMessageContentLength;
+    type MaxInboxSize = MaxInboxSize;
+    type MessageDepositBase = MessageDepositBase;
+    type MessageDepositPerByte = MessageDepositPerByte;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -533,6 +648,8 @@ frame_support::construct_runtime!(
         ClawToken: pallet_claw_token,
         Reputation: pallet_reputation,
         TaskMarket: pallet_task_market,
+        Validation: pallet_validation,
+        Messaging: pallet_messaging,
     }
 );
 
@@ -556,7 +673,25 @@ pub type SignedExtra = (
 
 /// All migrations of the runtime, in order.
 /// Add new migrations here.
-type Migrations = ();
+type Migrations = (
+    pallet_agent_registry::migrations::v1::BuildDidIndex<Runtime>,
+    pallet_agent_registry::migrations::v2::SyncAgentReputation<Runtime>,
+    pallet_agent_registry::migrations::v3::DropStoredReputation<Runtime>,
+    pallet_agent_registry::migrations::v4::AddLastHeartbeat<Runtime>,
+    pallet_agent_registry::migrations::v5::DropValidations<Runtime>,
+    pallet_reputation::migrations::v1::RecomputeScores<Runtime>,
+    pallet_reputation::migrations::v2::WeightRatings<Runtime>,
+    pallet_reputation::migrations::v3::WeightRecord<Runtime>,
+    pallet_reputation::migrations::v4::ReviewPerTask<Runtime>,
+    pallet_reputation::migrations::v5::AddSettledWeight<Runtime>,
+    pallet_reputation::migrations::v6::AddDecayedAt<Runtime>,
+    pallet_reputation::migrations::v7::IndexReviewsReceived<Runtime>,
+    pallet_task_market::migrations::v1::RebuildTaskIndices<Runtime>,
+    pallet_task_market::migrations::v2::QueueTerminalTasks<Runtime>,
+    pallet_task_market::migrations::v3::CountTaskBids<Runtime>,
+    pallet_task_market::migrations::v4::AddTaskCategory<Runtime>,
+    pallet_task_market::migrations::v5::SeedEscrowTotals<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -727,6 +862,231 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_agent_registry_runtime_api::AgentRegistryApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn agent(
+            agent_id: pallet_agent_registry::AgentId,
+        ) -> Option<pallet_agent_registry::AgentSummary<AccountId, BlockNumber>> {
+            AgentRegistry::agent(agent_id)
+        }
+
+        fn agent_by_did(
+            did: Vec<u8>,
+        ) -> Option<pallet_agent_registry::AgentSummary<AccountId, BlockNumber>> {
+            AgentRegistry::agent_by_did(&did)
+        }
+
+        fn agents_by_owner(
+            owner: AccountId,
+        ) -> Vec<pallet_agent_registry::AgentSummary<AccountId, BlockNumber>> {
+            AgentRegistry::agents_by_owner(&owner)
+        }
+
+        fn list_agents(
+            status: Option<pallet_agent_registry::AgentStatus>,
+            start: pallet_agent_registry::AgentId,
+            limit: u32,
+        ) -> pallet_agent_registry::AgentPage<AccountId, BlockNumber> {
+            AgentRegistry::list_agents(status, start, limit)
+        }
+
+        fn did_document(did: Vec<u8>) -> Option<Vec<u8>> {
+            AgentRegistry::did_document(&did)
+        }
+
+        fn key_valid_at(
+            agent_id: pallet_agent_registry::AgentId,
+            public_key: Vec<u8>,
+            at: BlockNumber,
+        ) -> bool {
+            AgentRegistry::key_valid_at(agent_id, &public_key, at)
+        }
+
+        fn agent_capabilities(
+            agent_id: pallet_agent_registry::AgentId,
+        ) -> Vec<pallet_agent_registry::CapabilitySummary<Balance>> {
+            AgentRegistry::agent_capabilities(agent_id)
+        }
+
+        fn agents_with_capability(
+            capability: Vec<u8>,
+            min_version: u32,
+            start: pallet_agent_registry::AgentId,
+            limit: u32,
+        ) -> pallet_agent_registry::AgentPage<AccountId, BlockNumber> {
+            AgentRegistry::agents_with_capability(&capability, min_version, start, limit)
+        }
+
+        fn agent_by_domain(
+            domain: Vec<u8>,
+        ) -> Option<pallet_agent_registry::AgentSummary<AccountId, BlockNumber>> {
+            AgentRegistry::agent_by_domain(&domain)
+        }
+
+        fn agent_by_address(
+            account: AccountId,
+        ) -> Option<pallet_agent_registry::AgentSummary<AccountId, BlockNumber>> {
+            AgentRegistry::agent_by_address(&account)
+        }
+
+        fn has_valid_attestation(
+            agent_id: pallet_agent_registry::AgentId,
+            schema: pallet_agent_registry::SchemaId,
+            issuer: AccountId,
+        ) -> bool {
+            AgentRegistry::has_valid_attestation(agent_id, schema, &issuer)
+        }
+
+        fn attestations(
+            agent_id: pallet_agent_registry::AgentId,
+        ) -> Vec<pallet_agent_registry::AttestationSummary<AccountId, BlockNumber>> {
+            AgentRegistry::attestations(agent_id)
+        }
+    }
+
+    impl pallet_reputation_runtime_api::ReputationApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn score(account: AccountId) -> u32 {
+            <Reputation as pallet_reputation::ReputationManager<_, _>>::get_reputation(&account)
+        }
+
+        fn category_score(account: AccountId, category: pallet_reputation::TaskCategory) -> u32 {
+            Reputation::category_score(&account, category)
+        }
+
+        fn reputation_info(
+            account: AccountId,
+        ) -> pallet_reputation::ReputationSummary<Balance, BlockNumber> {
+            Reputation::reputation_summary(&account)
+        }
+
+        fn history(
+            account: AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<pallet_reputation::HistoryEntry<AccountId, Balance>> {
+            Reputation::history(&account, start, limit)
+        }
+
+        fn reviews_received(
+            account: AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<pallet_reputation::ReviewSummary<AccountId, BlockNumber>> {
+            Reputation::reviews_received(&account, start, limit)
+        }
+
+        fn reviews_given(
+            account: AccountId,
+        ) -> Vec<pallet_reputation::ReviewSummary<AccountId, BlockNumber>> {
+            Reputation::reviews_given(&account)
+        }
+
+        fn leaderboard(
+            start: Option<AccountId>,
+            limit: u32,
+        ) -> pallet_reputation::LeaderboardPage<AccountId> {
+            Reputation::leaderboard(start.as_ref(), limit)
+        }
+
+        fn feedback_authorization(
+            server: AccountId,
+            client: AccountId,
+        ) -> Option<pallet_reputation::FeedbackAuthId> {
+            Reputation::feedback_authorization(&server, &client)
+        }
+
+        fn global_trust(account: AccountId) -> Option<u32> {
+            Reputation::global_trust(account)
+        }
+    }
+
+    impl pallet_task_market_runtime_api::TaskMarketApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn tasks_by_status(
+            status: pallet_task_market::TaskStatus,
+            start: pallet_task_market::TaskId,
+            limit: u32,
+        ) -> pallet_task_market::TaskPage<AccountId, Balance, BlockNumber> {
+            TaskMarket::tasks_by_status(status, start, limit)
+        }
+
+        fn tasks_by_poster(
+            poster: AccountId,
+        ) -> Vec<pallet_task_market::TaskSummary<AccountId, Balance, BlockNumber>> {
+            TaskMarket::tasks_by_poster(&poster)
+        }
+
+        fn tasks_by_worker(
+            worker: AccountId,
+        ) -> Vec<pallet_task_market::TaskSummary<AccountId, Balance, BlockNumber>> {
+            TaskMarket::tasks_by_worker(&worker)
+        }
+
+        fn bids_for_task(
+            task_id: pallet_task_market::TaskId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<pallet_task_market::BidSummary<AccountId, Balance, BlockNumber>> {
+            TaskMarket::bids_for_task(task_id, start, limit)
+        }
+
+        fn escrow_totals() -> pallet_task_market::EscrowTotals<Balance> {
+            TaskMarket::escrow_totals()
+        }
+
+        fn biddable_tasks(
+            bidder: AccountId,
+            start: pallet_task_market::TaskId,
+            limit: u32,
+        ) -> pallet_task_market::TaskPage<AccountId, Balance, BlockNumber> {
+            TaskMarket::biddable_tasks(&bidder, start, limit)
+        }
+    }
+
+    impl pallet_validation_runtime_api::ValidationApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn request(
+            request_id: pallet_validation::RequestId,
+        ) -> Option<pallet_validation::RequestSummary<AccountId, BlockNumber>> {
+            Validation::request(request_id)
+        }
+
+        fn validator(
+            account: AccountId,
+        ) -> Option<pallet_validation::ValidatorSummary<Balance, BlockNumber>> {
+            Validation::validator(&account)
+        }
+
+        fn pending_requests(
+            validator: AccountId,
+        ) -> Vec<pallet_validation::RequestSummary<AccountId, BlockNumber>> {
+            Validation::pending_requests(&validator)
+        }
+
+        fn output_request(
+            data_hash: pallet_validation::DataHash,
+        ) -> Option<pallet_validation::RequestSummary<AccountId, BlockNumber>> {
+            Validation::output_request(data_hash)
+        }
+    }
+
+    impl pallet_messaging_runtime_api::MessagingApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn inbox(
+            agent_id: pallet_agent_registry::AgentId,
+            start: pallet_messaging::MessageId,
+            limit: u32,
+        ) -> Vec<pallet_messaging::MessageSummary<AccountId, Balance, BlockNumber>> {
+            Messaging::inbox(agent_id, start, limit)
+        }
+
+        fn thread(
+            task_id: pallet_messaging::TaskId,
+        ) -> Vec<pallet_messaging::MessageSummary<AccountId, Balance, BlockNumber>> {
+            Messaging::thread(task_id)
+        }
+
+        fn next_message_id() -> pallet_messaging::MessageId {
+            Messaging::next_message_id()
+        }
+    }
+
     impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
         fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
             build_state::<RuntimeGenesisConfig>(config)